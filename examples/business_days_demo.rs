@@ -0,0 +1,74 @@
+use brazilian_utils::business_days::{
+    add_business_days, count_business_days, is_business_day, next_business_day, BusinessCalendar,
+};
+use chrono::NaiveDate;
+
+fn main() {
+    println!("=== Demonstração do Módulo Business Days ===\n");
+
+    // Verificação de dias úteis
+    println!("1. Verificação de dias úteis:");
+    let date = NaiveDate::from_ymd_opt(2024, 7, 9).unwrap();
+    println!("   09/07/2024 (Brasil): {:?}", is_business_day(date, None));
+    println!(
+        "   09/07/2024 (SP): {:?}\n",
+        is_business_day(date, Some("SP"))
+    );
+
+    // Soma de dias úteis
+    println!("2. Soma de dias úteis:");
+    let date = NaiveDate::from_ymd_opt(2024, 3, 28).unwrap();
+    println!(
+        "   28/03/2024 + 1 dia útil: {:?}",
+        add_business_days(date, 1, None)
+    );
+    println!(
+        "   28/03/2024 - 5 dias úteis: {:?}",
+        add_business_days(date, -5, None)
+    );
+    println!(
+        "   Próximo dia útil após 28/03/2024: {:?}\n",
+        next_business_day(date, None)
+    );
+
+    // Contagem de dias úteis
+    println!("3. Contagem de dias úteis:");
+    let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let end = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+    println!(
+        "   Dias úteis em 2024 (Brasil): {:?}",
+        count_business_days(start, end, None)
+    );
+    println!(
+        "   Dias úteis em 2024 (SP): {:?}\n",
+        count_business_days(start, end, Some("SP"))
+    );
+
+    // Calendário reutilizável para grandes volumes
+    println!("4. Calendário reutilizável (RJ):");
+    let calendar = BusinessCalendar::new(Some("RJ")).unwrap();
+    let first = NaiveDate::from_ymd_opt(2024, 4, 19).unwrap();
+    let last = NaiveDate::from_ymd_opt(2024, 4, 26).unwrap();
+    for day in calendar.business_days(first, last) {
+        println!("   {}", day.format("%d/%m/%Y"));
+    }
+
+    // Calendários ANBIMA e B3
+    println!("\n5. Calendários ANBIMA e B3:");
+    let anbima = BusinessCalendar::anbima();
+    let b3 = BusinessCalendar::b3();
+    println!(
+        "   Dias úteis em 2024: ANBIMA {}, B3 {}",
+        anbima.business_days_in_year(2024),
+        b3.business_days_in_year(2024)
+    );
+    let trade = NaiveDate::from_ymd_opt(2024, 12, 20).unwrap();
+    println!(
+        "   Liquidação D+2 de operação em 20/12/2024: {:?}",
+        b3.settlement_date(trade, 2)
+    );
+    println!(
+        "   Prazo em anos (DU/252) de 2024: {:.6}",
+        anbima.year_fraction_252(start, end)
+    );
+}
@@ -1,4 +1,6 @@
-use brazilian_utils::currency::{convert_real_to_text, format_currency, number_to_words};
+use brazilian_utils::currency::{
+    convert_amount_to_text, convert_real_to_text, format_currency, number_to_words, Unit,
+};
 
 fn main() {
     println!("=== Demonstração do Módulo Currency ===\n");
@@ -24,5 +26,14 @@ fn main() {
     println!("   R$ 1.111,11: {}", convert_real_to_text(1111.11));
     println!("   R$ 1.000.000,00: {}", convert_real_to_text(1_000_000.0));
     println!("   R$ 1.000.000,50: {}", convert_real_to_text(1_000_000.50));
-    println!("   R$ -50,00: {}", convert_real_to_text(-50.0));
+    println!("   R$ -50,00: {}\n", convert_real_to_text(-50.0));
+
+    // Conversão de outras moedas para texto
+    println!("4. Outras moedas por extenso:");
+    println!(
+        "   US$ 1.000,10: {}",
+        convert_amount_to_text(100_010, &Unit::USD)
+    );
+    println!("   € 1,00: {}", convert_amount_to_text(100, &Unit::EUR));
+    println!("   £ 2,01: {}", convert_amount_to_text(201, &Unit::GBP));
 }
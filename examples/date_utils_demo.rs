@@ -1,4 +1,8 @@
-use brazilian_utils::date_utils::{convert_date_to_text, is_holiday};
+use brazilian_utils::date_utils::{
+    convert_date_to_text, date_to_text, datetime_to_text, epi_week, epi_week_range, get_holiday,
+    is_holiday, list_holidays, list_municipal_holidays, list_optional_days, parse_date,
+    parse_month_year, DateParser, DateStyle, HolidayScope,
+};
 use chrono::NaiveDate;
 
 fn main() {
@@ -155,4 +159,88 @@ fn main() {
             "Dia comum"
         }
     );
+
+    // Consulta de feriados com nome e base legal
+    println!("\n4. Feriados de 2024 na Bahia:");
+    for holiday in list_holidays(2024, Some("BA")).unwrap() {
+        println!(
+            "   {} - {} ({})",
+            holiday.date.format("%d/%m/%Y"),
+            holiday.name,
+            holiday.legal_basis
+        );
+    }
+
+    let holiday = get_holiday(sp_revolution, Some("SP")).unwrap();
+    println!("\n   09/07/2024 em SP: {}", holiday.name);
+
+    // Feriados municipais pelo código IBGE (ex.: cep::Address.ibge)
+    println!("\n5. Feriados municipais de 2024 em Belo Horizonte (IBGE 3106200):");
+    for holiday in list_municipal_holidays(2024, "3106200").unwrap() {
+        if let HolidayScope::Municipal(_) = holiday.scope {
+            println!("   {} - {}", holiday.date.format("%d/%m/%Y"), holiday.name);
+        }
+    }
+
+    // Pontos facultativos
+    println!("\n6. Pontos facultativos de 2024:");
+    for day in list_optional_days(2024) {
+        println!(
+            "   {} - {} ({:?})",
+            day.date.format("%d/%m/%Y"),
+            day.name,
+            day.kind
+        );
+    }
+
+    // Leitura de datas em texto e em formatos numéricos
+    println!("\n7. Leitura de datas:");
+    for text in [
+        "15 de agosto de 1990",
+        "Primeiro de janeiro de dois mil e vinte",
+        "São Paulo, 1º de março de 2024",
+        "15/08/90",
+        "15.08.1990",
+    ] {
+        println!("   \"{}\" -> {:?}", text, parse_date(text));
+    }
+    println!("   \"ago/1990\" -> {:?}", parse_month_year("ago/1990"));
+    println!(
+        "   \"15/08/30\" (pivô 30) -> {:?}",
+        DateParser::new()
+            .with_century_pivot(30)
+            .parse_date("15/08/30")
+    );
+
+    // Estilos formais de data
+    println!("\n8. Estilos formais de data:");
+    let date = NaiveDate::from_ymd_opt(1990, 8, 15).unwrap();
+    for style in [
+        DateStyle::Plain,
+        DateStyle::Long,
+        DateStyle::Notarial,
+        DateStyle::WithWeekday,
+        DateStyle::WithCity {
+            city: "São Paulo".to_string(),
+            weekday: true,
+        },
+    ] {
+        println!("   {}", date_to_text(date, &style));
+    }
+    let datetime = date.and_hms_opt(14, 30, 0).unwrap();
+    println!("   {}", datetime_to_text(datetime, &DateStyle::Notarial));
+
+    // Semana epidemiológica
+    println!("\n9. Semana epidemiológica:");
+    for (year, month, day) in [(2024, 3, 15), (2022, 1, 1), (2024, 12, 31)] {
+        let date = NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        println!("   {} -> {}", date.format("%d/%m/%Y"), epi_week(date));
+    }
+    if let Some(range) = epi_week_range(2020, 53) {
+        println!(
+            "   SE 53/2020: {} a {}",
+            range.start().format("%d/%m/%Y"),
+            range.end().format("%d/%m/%Y")
+        );
+    }
 }
@@ -0,0 +1,38 @@
+use brazilian_utils::currency::{format_currency, Brl};
+use brazilian_utils::financing::{
+    price_payment, schedule, AmortizationSystem, DayCount, IofRates, Loan,
+};
+use chrono::NaiveDate;
+
+fn main() {
+    println!("=== Demonstração do Módulo Financing ===\n");
+
+    let principal = Brl::from_reais(10_000).unwrap();
+
+    // Valor da parcela pela Tabela Price
+    println!("1. Parcela pela Tabela Price:");
+    let payment = price_payment(principal, 0.0199, 12).unwrap();
+    println!(
+        "   R$ 10.000,00 em 12x a 1,99% a.m.: {}\n",
+        format_currency(payment).unwrap()
+    );
+
+    // Tabela Price com datas e IOF
+    println!("2. Tabela Price (30/360, com IOF):");
+    let loan = Loan {
+        release_date: NaiveDate::from_ymd_opt(2024, 1, 15),
+        iof: Some(IofRates::INDIVIDUAL),
+        ..Loan::new(principal, 0.0199, 6, AmortizationSystem::Price)
+    };
+    println!("{}\n", schedule(&loan).unwrap());
+
+    // SAC em dias úteis (252)
+    println!("3. SAC (dias úteis/252, feriados de SP):");
+    let loan = Loan {
+        release_date: NaiveDate::from_ymd_opt(2024, 1, 15),
+        day_count: DayCount::Business252,
+        uf: Some("SP".to_string()),
+        ..Loan::new(principal, 0.0199, 6, AmortizationSystem::Sac)
+    };
+    println!("{}", schedule(&loan).unwrap());
+}
@@ -0,0 +1,86 @@
+use brazilian_utils::business_days::BusinessCalendar;
+use brazilian_utils::holiday_calendar::{HolidayCalendar, HolidayRule};
+use chrono::{NaiveDate, Weekday};
+
+const CALENDARIO_TOML: &str = r#"
+uf = "SP"
+
+[[holidays]]
+name = "Aniversário da empresa"
+type = "fixed"
+month = 3
+day = 15
+since = 2010
+
+[[holidays]]
+name = "Dia do Comerciário"
+type = "nth_weekday"
+month = 10
+weekday = "monday"
+nth = 3
+kind = "optional"
+
+[[holidays]]
+name = "Revolução Constitucionalista de 1932"
+type = "fixed"
+month = 7
+day = 9
+since = 2025
+remove = true
+"#;
+
+fn main() {
+    println!("=== Demonstração do Módulo Holiday Calendar ===\n");
+
+    // Calendário carregado de TOML
+    println!("1. Calendário carregado de TOML (SP):");
+    let calendar = HolidayCalendar::from_toml(CALENDARIO_TOML).unwrap();
+    for holiday in calendar.list_holidays(2025) {
+        println!(
+            "   {} - {} ({:?})",
+            holiday.date.format("%d/%m/%Y"),
+            holiday.name,
+            holiday.kind
+        );
+    }
+
+    // Regras com vigência
+    println!("\n2. Regras com vigência:");
+    for year in [2024, 2025] {
+        let date = NaiveDate::from_ymd_opt(year, 7, 9).unwrap();
+        println!("   09/07/{} é feriado? {}", year, calendar.is_holiday(date));
+    }
+
+    // Regras definidas no código
+    println!("\n3. Regras definidas no código:");
+    let calendar = HolidayCalendar::empty()
+        .with_rule(HolidayRule::nth_weekday("Dia das Mães", 5, Weekday::Sun, 2))
+        .with_rule(HolidayRule::easter_offset("Corpus Christi", 60));
+    for holiday in calendar.list_holidays(2024) {
+        println!("   {} - {}", holiday.date.format("%d/%m/%Y"), holiday.name);
+    }
+
+    // Calendário de dias úteis a partir do calendário personalizado
+    println!("\n4. Dias úteis com o calendário personalizado:");
+    let calendar =
+        BusinessCalendar::from_holidays(HolidayCalendar::from_toml(CALENDARIO_TOML).unwrap());
+    let date = NaiveDate::from_ymd_opt(2024, 3, 14).unwrap();
+    println!(
+        "   Próximo dia útil após 14/03/2024: {:?}",
+        calendar.next_business_day(date)
+    );
+
+    // Exportação e importação iCalendar (.ics)
+    println!("\n5. Exportação iCalendar (.ics):");
+    let ics = HolidayCalendar::new(Some("SP"))
+        .unwrap()
+        .to_ics(2025..=2025);
+    for line in ics.lines().skip(6).take(10) {
+        println!("   {}", line);
+    }
+    let imported = HolidayCalendar::from_ics(&ics).unwrap();
+    println!(
+        "   Feriados importados de volta: {}",
+        imported.list_holidays(2025).len()
+    );
+}
@@ -1,4 +1,6 @@
+use brazilian_utils::court_deadline::DeadlineCalculator;
 use brazilian_utils::legal_process;
+use chrono::NaiveDate;
 
 fn main() {
     println!("=== Brazilian Legal Process ID Utilities Demo ===\n");
@@ -94,18 +96,38 @@ fn main() {
 
     println!("6. Legal Organs (Justiça):");
     let organs = vec![
-        (1, "Federal"),
-        (2, "Trabalho (Labor)"),
-        (3, "Eleitoral (Electoral)"),
-        (4, "Militar (Military)"),
-        (5, "Estadual (State)"),
-        (6, "Distrito Federal e Territórios"),
-        (7, "Tribunal Superior"),
-        (8, "Estadual (State) - Additional"),
-        (9, "Outros (Others)"),
+        (1, "Supremo Tribunal Federal"),
+        (2, "Conselho Nacional de Justiça"),
+        (3, "Superior Tribunal de Justiça"),
+        (4, "Federal"),
+        (5, "Trabalho (Labor)"),
+        (6, "Eleitoral (Electoral)"),
+        (7, "Militar da União (Military)"),
+        (8, "Estadual (State)"),
+        (9, "Militar Estadual (State Military)"),
     ];
 
     for (code, name) in organs {
         println!("   {} - {}", code, name);
     }
+
+    println!();
+
+    println!("7. Parse and Court Deadlines:");
+    let process_id = "1234567-21.2024.8.26.0000";
+    if let Some(process) = legal_process::parse(process_id) {
+        println!(
+            "   {}: J = {}, TR = {:02}, UF = {:?}",
+            process_id,
+            process.segment,
+            process.tribunal,
+            process.uf()
+        );
+    }
+    let calculator = DeadlineCalculator::for_legal_process(process_id).unwrap();
+    let intimation = NaiveDate::from_ymd_opt(2024, 12, 18).unwrap();
+    println!(
+        "   15 business days from 18/12/2024: {:?}",
+        calculator.deadline(intimation, 15)
+    );
 }
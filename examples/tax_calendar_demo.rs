@@ -0,0 +1,76 @@
+use brazilian_utils::business_days::BusinessCalendar;
+use brazilian_utils::tax_calendar::{
+    due_date, Adjustment, Competence, DueDateRule, DueDay, Obligation,
+};
+
+fn main() {
+    println!("=== Demonstração do Módulo Tax Calendar ===\n");
+
+    // Competências
+    println!("1. Competências:");
+    for text in ["08/2024", "082024", "ago/2024", "13/2024", "14/2024"] {
+        match Competence::parse(text) {
+            Some(competence) => println!(
+                "   \"{}\" -> {} (13º salário: {})",
+                text,
+                competence,
+                competence.is_thirteenth_salary()
+            ),
+            None => println!("   \"{}\" -> inválida", text),
+        }
+    }
+
+    // Vencimentos das obrigações federais
+    println!("\n2. Vencimentos da competência 10/2024:");
+    let competence = Competence::new(2024, 10).unwrap();
+    for (name, obligation) in [
+        ("IRRF (DARF)", Obligation::Irrf),
+        ("PIS/Cofins (DARF)", Obligation::PisCofins),
+        ("IRPJ/CSLL estimativa (DARF)", Obligation::IrpjCsll),
+        ("Simples Nacional (DAS)", Obligation::Das),
+        ("FGTS", Obligation::Fgts),
+        ("INSS empresa (GPS)", Obligation::Gps),
+        (
+            "INSS contribuinte individual (GPS)",
+            Obligation::GpsSelfEmployed,
+        ),
+    ] {
+        if let Some(date) = obligation.due_date(competence) {
+            println!("   {}: {}", name, date.format("%d/%m/%Y"));
+        }
+    }
+
+    // Regras que mudam com a competência
+    println!("\n3. FGTS Digital e 13º salário:");
+    for (obligation, text) in [
+        (Obligation::Fgts, "02/2024"),
+        (Obligation::Fgts, "03/2024"),
+        (Obligation::Gps, "13/2024"),
+        (Obligation::Fgts, "13/2024"),
+    ] {
+        println!(
+            "   {:?} {}: {:?}",
+            obligation,
+            text,
+            due_date(obligation, text)
+        );
+    }
+
+    // Regras personalizadas
+    println!("\n4. Regras personalizadas:");
+    let competence = Competence::new(2024, 6).unwrap();
+    let rule = DueDateRule::new(DueDay::Day(9));
+    let calendar = BusinessCalendar::new(Some("SP")).unwrap();
+    println!(
+        "   Dia 9, calendário nacional: {:?}",
+        rule.due_date(competence)
+    );
+    println!(
+        "   Dia 9, calendário de SP: {:?}",
+        rule.due_date_in(competence, &calendar)
+    );
+    let rule = DueDateRule::new(DueDay::BusinessDay(5)).months_after(1);
+    println!("   5º dia útil: {:?}", rule.due_date(competence));
+    let rule = DueDateRule::new(DueDay::Day(20)).adjustment(Adjustment::Next);
+    println!("   Dia 20, prorrogado: {:?}", rule.due_date(competence));
+}
@@ -0,0 +1,87 @@
+use brazilian_utils::court_deadline::DeadlineCalculator;
+use brazilian_utils::time_zone::{
+    from_local, is_daylight_saving, local_date, municipality_time_zone, time_zone, StandardTime,
+};
+use chrono::{NaiveDate, TimeZone, Utc};
+
+fn main() {
+    println!("=== Demonstração do Módulo Time Zone ===\n");
+
+    // Fuso horário por UF
+    println!("1. Fuso horário por UF:");
+    for uf in ["SP", "BA", "AM", "MT", "AC"] {
+        let tz = time_zone(uf).unwrap();
+        let standard = StandardTime::from_time_zone(tz).unwrap();
+        println!(
+            "   {} - {} ({:?}, {})",
+            uf,
+            tz,
+            standard,
+            standard.utc_offset()
+        );
+    }
+
+    // Fuso horário por município (estados divididos)
+    println!("\n2. Fuso horário por município (código IBGE):");
+    for (name, ibge) in [
+        ("Manaus", "1302603"),
+        ("Tabatinga", "1304062"),
+        ("Belém", "1501402"),
+        ("Santarém", "1506807"),
+        ("Fernando de Noronha", "2605459"),
+    ] {
+        println!(
+            "   {} ({}) - {:?}",
+            name,
+            ibge,
+            municipality_time_zone(ibge)
+        );
+    }
+
+    // Horário de verão histórico
+    println!("\n3. Horário de verão (até 2019):");
+    let sao_paulo = time_zone("SP").unwrap();
+    for year in [2017, 2018, 2019] {
+        let instant = Utc.with_ymd_and_hms(year, 12, 15, 12, 0, 0).unwrap();
+        println!(
+            "   15/12/{} em São Paulo: {}",
+            year,
+            is_daylight_saving(instant, sao_paulo)
+        );
+    }
+
+    // Conversão entre horário local e UTC
+    println!("\n4. Conversão entre horário local e UTC:");
+    let instant = Utc.with_ymd_and_hms(2024, 3, 1, 2, 30, 0).unwrap();
+    println!("   Instante: {}", instant);
+    for uf in ["SP", "AC"] {
+        println!(
+            "   Data local em {}: {}",
+            uf,
+            local_date(instant, time_zone(uf).unwrap()).format("%d/%m/%Y")
+        );
+    }
+    let local = NaiveDate::from_ymd_opt(2018, 11, 4)
+        .unwrap()
+        .and_hms_opt(0, 30, 0)
+        .unwrap();
+    println!(
+        "   04/11/2018 00:30 em São Paulo (hora inexistente): {:?}",
+        from_local(local, sao_paulo)
+    );
+
+    // Prazos processuais no horário local do tribunal
+    println!("\n5. Prazos processuais no horário local:");
+    let intimation = NaiveDate::from_ymd_opt(2024, 6, 3).unwrap();
+    let filed_at = Utc.with_ymd_and_hms(2024, 6, 11, 4, 0, 0).unwrap();
+    for uf in ["SP", "AC"] {
+        let calculator = DeadlineCalculator::new(Some(uf)).unwrap();
+        println!(
+            "   {}: prazo termina em {:?}, petição às {} tempestiva? {:?}",
+            uf,
+            calculator.deadline_end(intimation, 5),
+            filed_at,
+            calculator.is_timely(intimation, 5, filed_at)
+        );
+    }
+}
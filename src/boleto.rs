@@ -5,6 +5,7 @@
 /// payment information and verification digits.
 
 /// Length of a valid boleto digitable line (47 digits)
+#[allow(clippy::empty_line_after_doc_comments)]
 const DIGITABLE_LINE_LENGTH: usize = 47;
 
 /// Position of the check digit in the parsed barcode (mod 11)
@@ -0,0 +1,1217 @@
+//! Business-day (dias úteis) arithmetic over the Brazilian holiday calendar.
+
+use crate::date_utils::{is_holiday, is_municipal_holiday, HolidayKind};
+use crate::holiday_calendar::HolidayCalendar;
+use crate::time_zone::{local_date, municipality_time_zone, time_zone, Tz};
+use chrono::{DateTime, Datelike, NaiveDate, Utc, Weekday};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, RwLock};
+
+// TYPES
+// =====
+
+/// Business days of a single year, stored as running totals.
+///
+/// `cumulative[k]` is the number of business days among the first `k` days of
+/// the year, so the table has one entry more than the number of days.
+#[derive(Debug)]
+struct YearTable {
+    cumulative: Box<[u16]>,
+}
+
+impl YearTable {
+    /// Number of business days in the whole year.
+    fn total(&self) -> i64 {
+        self.cumulative[self.cumulative.len() - 1] as i64
+    }
+
+    /// Number of business days in the year before the given day.
+    fn before(&self, date: NaiveDate) -> i64 {
+        self.cumulative[date.ordinal0() as usize] as i64
+    }
+
+    /// Whether the given day of the year is a business day.
+    fn contains(&self, date: NaiveDate) -> bool {
+        let index = date.ordinal0() as usize;
+        self.cumulative[index + 1] > self.cumulative[index]
+    }
+
+    /// The day of the year holding the `position`-th business day (1-based).
+    fn nth(&self, year: i32, position: i64) -> Option<NaiveDate> {
+        let index = self
+            .cumulative
+            .partition_point(|&count| (count as i64) < position);
+        NaiveDate::from_yo_opt(year, index as u32)
+    }
+}
+
+/// A business-day calendar for the whole country, a state (UF) or a municipality.
+///
+/// Saturdays, Sundays and the holidays reported by
+/// [`is_holiday`](crate::date_utils::is_holiday) (or
+/// [`is_municipal_holiday`](crate::date_utils::is_municipal_holiday) for a
+/// municipality) are not business days. A calendar can also be built from a
+/// user-defined [`HolidayCalendar`] with [`from_holidays`](Self::from_holidays).
+/// Holidays are computed once per year and cached, so a single calendar can
+/// be shared (it is `Sync`) and reused to process large batches of dates.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::business_days::BusinessCalendar;
+/// use chrono::NaiveDate;
+///
+/// let calendar = BusinessCalendar::new(Some("SP")).unwrap();
+/// let date = NaiveDate::from_ymd_opt(2024, 7, 8).unwrap();
+///
+/// // July 9 is a holiday in São Paulo
+/// assert_eq!(
+///     calendar.add_business_days(date, 1),
+///     NaiveDate::from_ymd_opt(2024, 7, 10)
+/// );
+/// assert_eq!(
+///     calendar.count_business_days(date, NaiveDate::from_ymd_opt(2024, 7, 15).unwrap()),
+///     4
+/// );
+/// ```
+#[derive(Debug)]
+pub struct BusinessCalendar {
+    holidays: HolidayCalendar,
+    time_zone: Tz,
+    excluded_kinds: Vec<HolidayKind>,
+    market: Market,
+    forensic_recess: bool,
+    suspensions: Vec<(NaiveDate, NaiveDate)>,
+    years: RwLock<HashMap<i32, Arc<YearTable>>>,
+}
+
+/// Closing rules of a market on top of the civil holidays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Market {
+    /// No closing rules besides the holidays.
+    None,
+    /// B3: closed on December 24 and 31, and until 2021 on the São Paulo
+    /// state and municipal holidays.
+    B3,
+}
+
+/// IBGE code of São Paulo, whose holidays closed B3 until 2021.
+const SAO_PAULO_IBGE: &str = "3550308";
+
+/// More business days than there are days in the range supported by
+/// `chrono`, so any move larger than this is out of range.
+const MAX_BUSINESS_DAYS: i64 = 200_000_000;
+
+impl Clone for BusinessCalendar {
+    fn clone(&self) -> Self {
+        let years = self
+            .years
+            .read()
+            .map(|years| years.clone())
+            .unwrap_or_default();
+
+        BusinessCalendar {
+            holidays: self.holidays.clone(),
+            time_zone: self.time_zone,
+            excluded_kinds: self.excluded_kinds.clone(),
+            market: self.market,
+            forensic_recess: self.forensic_recess,
+            suspensions: self.suspensions.clone(),
+            years: RwLock::new(years),
+        }
+    }
+}
+
+impl BusinessCalendar {
+    /// Creates a calendar with the national holidays and, if given, the
+    /// holidays of a state.
+    ///
+    /// # Arguments
+    ///
+    /// * `uf` - The state abbreviation (UF) whose holidays are observed.
+    ///
+    /// # Returns
+    ///
+    /// The calendar, or `None` if the UF is invalid.
+    pub fn new(uf: Option<&str>) -> Option<Self> {
+        HolidayCalendar::new(uf).map(Self::from_holidays)
+    }
+
+    /// Creates a calendar with the national, state and municipal holidays of
+    /// a municipality.
+    ///
+    /// # Arguments
+    ///
+    /// * `ibge` - The 7-digit IBGE code of the municipality.
+    ///
+    /// # Returns
+    ///
+    /// The calendar, or `None` if there is no holiday data for the municipality.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::business_days::BusinessCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = BusinessCalendar::for_municipality("3550308").unwrap();
+    /// assert_eq!(calendar.uf(), Some("SP"));
+    ///
+    /// // São Paulo anniversary
+    /// assert!(!calendar.is_business_day(NaiveDate::from_ymd_opt(2024, 1, 25).unwrap()));
+    /// ```
+    pub fn for_municipality(ibge: &str) -> Option<Self> {
+        HolidayCalendar::for_municipality(ibge).map(Self::from_holidays)
+    }
+
+    /// Creates a calendar observing the holidays of a [`HolidayCalendar`].
+    ///
+    /// The calendar's time zone is the one of its municipality or state, or
+    /// Brasília time for a national calendar.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::business_days::BusinessCalendar;
+    /// use brazilian_utils::holiday_calendar::{HolidayCalendar, HolidayRule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let holidays = HolidayCalendar::new(Some("SP"))
+    ///     .unwrap()
+    ///     .with_rule(HolidayRule::fixed("Aniversário da empresa", 3, 15));
+    /// let calendar = BusinessCalendar::from_holidays(holidays);
+    ///
+    /// assert_eq!(
+    ///     calendar.next_business_day(NaiveDate::from_ymd_opt(2024, 3, 14).unwrap()),
+    ///     NaiveDate::from_ymd_opt(2024, 3, 18)
+    /// );
+    /// ```
+    pub fn from_holidays(holidays: HolidayCalendar) -> Self {
+        let time_zone = holidays
+            .ibge()
+            .and_then(municipality_time_zone)
+            .or_else(|| holidays.uf().and_then(time_zone))
+            .unwrap_or(Tz::America__Sao_Paulo);
+
+        BusinessCalendar {
+            holidays,
+            time_zone,
+            excluded_kinds: Vec::new(),
+            market: Market::None,
+            forensic_recess: false,
+            suspensions: Vec::new(),
+            years: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Creates the ANBIMA national calendar used to settle financial products.
+    ///
+    /// It observes the national holidays plus Carnival Monday and Tuesday and
+    /// Corpus Christi, and ignores state and municipal holidays.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::business_days::BusinessCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let anbima = BusinessCalendar::anbima();
+    /// assert!(!anbima.is_business_day(NaiveDate::from_ymd_opt(2024, 2, 13).unwrap()));
+    /// assert!(anbima.is_business_day(NaiveDate::from_ymd_opt(2024, 7, 9).unwrap()));
+    /// ```
+    pub fn anbima() -> Self {
+        Self::new(None)
+            .expect("the national calendar is always available")
+            .excluding(HolidayKind::Optional)
+    }
+
+    /// Creates the B3 exchange trading calendar.
+    ///
+    /// It follows the [ANBIMA calendar](Self::anbima) and also closes on
+    /// December 24 and 31. Until 2021 the exchange also closed on the São
+    /// Paulo state and municipal holidays.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::business_days::BusinessCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let b3 = BusinessCalendar::b3();
+    /// assert!(!b3.is_business_day(NaiveDate::from_ymd_opt(2024, 12, 24).unwrap()));
+    /// assert!(!b3.is_business_day(NaiveDate::from_ymd_opt(2021, 1, 25).unwrap()));
+    /// assert!(b3.is_business_day(NaiveDate::from_ymd_opt(2024, 1, 25).unwrap()));
+    /// ```
+    pub fn b3() -> Self {
+        BusinessCalendar {
+            market: Market::B3,
+            ..Self::anbima()
+        }
+    }
+
+    /// The state (UF) of the calendar, if any.
+    pub fn uf(&self) -> Option<&str> {
+        self.holidays.uf()
+    }
+
+    /// The holiday calendar observed by the calendar.
+    pub fn holidays(&self) -> &HolidayCalendar {
+        &self.holidays
+    }
+
+    /// Also treats pontos facultativos of the given kind as non-business days.
+    ///
+    /// By default only holidays are skipped: Carnival, Corpus Christi
+    /// ([`HolidayKind::Optional`]) and Ash Wednesday ([`HolidayKind::HalfDay`])
+    /// are business days. Banks and federal agencies usually close on the
+    /// optional days and open in the afternoon of the half days.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::business_days::BusinessCalendar;
+    /// use brazilian_utils::date_utils::HolidayKind;
+    /// use chrono::NaiveDate;
+    ///
+    /// let carnival = NaiveDate::from_ymd_opt(2024, 2, 13).unwrap();
+    /// let calendar = BusinessCalendar::new(None).unwrap();
+    /// assert!(calendar.is_business_day(carnival));
+    ///
+    /// let calendar = calendar.excluding(HolidayKind::Optional);
+    /// assert!(!calendar.is_business_day(carnival));
+    /// ```
+    pub fn excluding(mut self, kind: HolidayKind) -> Self {
+        if kind != HolidayKind::Holiday && !self.excluded_kinds.contains(&kind) {
+            self.excluded_kinds.push(kind);
+            self.clear_cache();
+        }
+        self
+    }
+
+    /// Also treats every day from `from` to `to` (inclusive) as a non-business day.
+    ///
+    /// Used for closures that are not holidays, such as court suspensions or
+    /// system outages. An empty range (`to` before `from`) is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::business_days::BusinessCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let from = NaiveDate::from_ymd_opt(2024, 5, 6).unwrap();
+    /// let to = NaiveDate::from_ymd_opt(2024, 5, 10).unwrap();
+    /// let calendar = BusinessCalendar::new(Some("RS")).unwrap().with_suspension(from, to);
+    ///
+    /// assert!(!calendar.is_business_day(from));
+    /// assert!(calendar.is_business_day(NaiveDate::from_ymd_opt(2024, 5, 13).unwrap()));
+    /// ```
+    pub fn with_suspension(mut self, from: NaiveDate, to: NaiveDate) -> Self {
+        if from <= to {
+            self.suspensions.push((from, to));
+            self.clear_cache();
+        }
+        self
+    }
+
+    /// Also treats the forensic recess, from December 20 to January 20
+    /// (inclusive), as non-business days.
+    ///
+    /// Procedural deadlines are suspended in this period (CPC, art. 220).
+    pub fn with_forensic_recess(mut self) -> Self {
+        self.forensic_recess = true;
+        self.clear_cache();
+        self
+    }
+
+    /// The IBGE code of the municipality of the calendar, if any.
+    pub fn ibge(&self) -> Option<&str> {
+        self.holidays.ibge()
+    }
+
+    /// The time zone where the calendar's days are counted.
+    pub fn time_zone(&self) -> Tz {
+        self.time_zone
+    }
+
+    /// Counts the calendar's days in another time zone, e.g. the one of a
+    /// court whose holidays come from a custom [`HolidayCalendar`].
+    pub fn with_time_zone(mut self, time_zone: Tz) -> Self {
+        self.time_zone = time_zone;
+        self
+    }
+
+    /// Returns the local date of an instant in the calendar's time zone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::business_days::BusinessCalendar;
+    /// use chrono::{NaiveDate, TimeZone, Utc};
+    ///
+    /// // 02:00 UTC on a Saturday is still Friday night in Acre
+    /// let calendar = BusinessCalendar::new(Some("AC")).unwrap();
+    /// let instant = Utc.with_ymd_and_hms(2024, 6, 8, 2, 0, 0).unwrap();
+    /// let date = calendar.local_date(instant);
+    ///
+    /// assert_eq!(date, NaiveDate::from_ymd_opt(2024, 6, 7).unwrap());
+    /// assert!(calendar.is_business_day(date));
+    /// ```
+    pub fn local_date(&self, instant: DateTime<Utc>) -> NaiveDate {
+        local_date(instant, self.time_zone)
+    }
+
+    /// Checks whether a date is a business day.
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        self.year(date.year()).contains(date)
+    }
+
+    /// Returns the first business day strictly after the given date.
+    ///
+    /// Returns `None` only past the last date supported by `chrono`.
+    pub fn next_business_day(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.add_business_days(date, 1)
+    }
+
+    /// Returns the last business day strictly before the given date.
+    ///
+    /// Returns `None` only before the first date supported by `chrono`.
+    pub fn previous_business_day(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.add_business_days(date, -1)
+    }
+
+    /// Moves a date by a number of business days.
+    ///
+    /// A positive `days` returns the `days`-th business day after `date`, a
+    /// negative one the business day as many days before it, and zero returns
+    /// `date` unchanged. The date itself does not need to be a business day:
+    /// adding one business day to a Saturday gives the following Monday (if it
+    /// is not a holiday).
+    ///
+    /// # Returns
+    ///
+    /// The resulting date, or `None` if it is outside the range supported by
+    /// `chrono`.
+    pub fn add_business_days(&self, date: NaiveDate, days: i64) -> Option<NaiveDate> {
+        if days.unsigned_abs() > MAX_BUSINESS_DAYS as u64 {
+            return None;
+        }
+
+        let mut year = date.year();
+        let mut table = self.year(year);
+
+        if days > 0 {
+            // Position of the target counting from the start of the year
+            let mut position =
+                (table.before(date) + i64::from(table.contains(date))).checked_add(days)?;
+
+            while position > table.total() {
+                position -= table.total();
+                year = year.checked_add(1)?;
+                NaiveDate::from_ymd_opt(year, 1, 1)?;
+                table = self.year(year);
+            }
+
+            table.nth(year, position)
+        } else if days < 0 {
+            let mut remaining = days.checked_neg()?;
+            let mut available = table.before(date);
+
+            while remaining > available {
+                remaining -= available;
+                year = year.checked_sub(1)?;
+                NaiveDate::from_ymd_opt(year, 1, 1)?;
+                table = self.year(year);
+                available = table.total();
+            }
+
+            table.nth(year, available - remaining + 1)
+        } else {
+            Some(date)
+        }
+    }
+
+    /// Moves a date back by a number of business days.
+    ///
+    /// Equivalent to [`add_business_days`](Self::add_business_days) with
+    /// `-days`.
+    pub fn subtract_business_days(&self, date: NaiveDate, days: i64) -> Option<NaiveDate> {
+        self.add_business_days(date, days.checked_neg()?)
+    }
+
+    /// Counts the business days in `[start, end)`.
+    ///
+    /// The start date is included and the end date is not, the convention
+    /// used to count the days of an interest period. If `end` is before
+    /// `start` the result is negative.
+    pub fn count_business_days(&self, start: NaiveDate, end: NaiveDate) -> i64 {
+        if end < start {
+            return -self.count_business_days(end, start);
+        }
+
+        let start_table = self.year(start.year());
+
+        if start.year() == end.year() {
+            return start_table.before(end) - start_table.before(start);
+        }
+
+        let mut count = start_table.total() - start_table.before(start);
+        for year in start.year() + 1..end.year() {
+            count += self.year(year).total();
+        }
+
+        count + self.year(end.year()).before(end)
+    }
+
+    /// Iterates over the business days in `[start, end)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::business_days::BusinessCalendar;
+    /// use chrono::{Datelike, NaiveDate};
+    ///
+    /// let calendar = BusinessCalendar::new(None).unwrap();
+    /// let start = NaiveDate::from_ymd_opt(2024, 12, 23).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2024, 12, 30).unwrap();
+    ///
+    /// let days: Vec<u32> = calendar.business_days(start, end).map(|d| d.day()).collect();
+    /// assert_eq!(days, vec![23, 24, 26, 27]);
+    /// ```
+    pub fn business_days(&self, start: NaiveDate, end: NaiveDate) -> BusinessDays<'_> {
+        BusinessDays {
+            calendar: self,
+            table: self.year(start.year()),
+            table_year: start.year(),
+            next: Some(start),
+            end,
+        }
+    }
+
+    /// Computes the settlement date of a trade in D+N.
+    ///
+    /// A trade made on a non-business day is considered made on the next
+    /// business day, and settles `days` business days later.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::business_days::BusinessCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let b3 = BusinessCalendar::b3();
+    ///
+    /// // Trade on the Friday before Carnival settles in D+2 on Thursday
+    /// let trade = NaiveDate::from_ymd_opt(2024, 2, 9).unwrap();
+    /// assert_eq!(b3.settlement_date(trade, 2), NaiveDate::from_ymd_opt(2024, 2, 15));
+    /// ```
+    pub fn settlement_date(&self, trade_date: NaiveDate, days: u32) -> Option<NaiveDate> {
+        let trade_date = if self.is_business_day(trade_date) {
+            trade_date
+        } else {
+            self.next_business_day(trade_date)?
+        };
+
+        self.add_business_days(trade_date, days as i64)
+    }
+
+    /// Counts the business days of a year.
+    pub fn business_days_in_year(&self, year: i32) -> i64 {
+        self.year(year).total()
+    }
+
+    /// Computes the length of `[start, end)` in years of 252 business days.
+    ///
+    /// This is the day-count convention (DU/252) used to price fixed-income
+    /// products indexed to the CDI or the Selic rate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::business_days::BusinessCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let anbima = BusinessCalendar::anbima();
+    /// let start = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+    /// let end = NaiveDate::from_ymd_opt(2024, 7, 1).unwrap();
+    ///
+    /// assert_eq!(anbima.count_business_days(start, end), 124);
+    /// assert!((anbima.year_fraction_252(start, end) - 124.0 / 252.0).abs() < 1e-12);
+    /// ```
+    pub fn year_fraction_252(&self, start: NaiveDate, end: NaiveDate) -> f64 {
+        self.count_business_days(start, end) as f64 / 252.0
+    }
+
+    /// Drops the cached tables after a change of rules.
+    fn clear_cache(&mut self) {
+        if let Ok(years) = self.years.get_mut() {
+            years.clear();
+        }
+    }
+
+    /// Returns the cached table of a year, building it on first use.
+    fn year(&self, year: i32) -> Arc<YearTable> {
+        if let Some(table) = self
+            .years
+            .read()
+            .ok()
+            .and_then(|years| years.get(&year).cloned())
+        {
+            return table;
+        }
+
+        let table = Arc::new(self.build_year(year));
+        if let Ok(mut years) = self.years.write() {
+            years.insert(year, Arc::clone(&table));
+        }
+        table
+    }
+
+    /// Checks whether a date is a holiday in the calendar.
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        let holiday = self.holidays.holidays_on(date).iter().any(|holiday| {
+            holiday.kind == HolidayKind::Holiday || self.excluded_kinds.contains(&holiday.kind)
+        });
+
+        let market_closed = match self.market {
+            Market::None => false,
+            Market::B3 => {
+                (date.month() == 12 && matches!(date.day(), 24 | 31))
+                    || (date.year() <= 2021
+                        && is_municipal_holiday(date, SAO_PAULO_IBGE) == Some(true))
+            }
+        };
+
+        let suspended = (self.forensic_recess
+            && matches!((date.month(), date.day()), (12, 20..) | (1, ..=20)))
+            || self
+                .suspensions
+                .iter()
+                .any(|(from, to)| (*from..=*to).contains(&date));
+
+        holiday || market_closed || suspended
+    }
+
+    /// Computes the business days of a year.
+    fn build_year(&self, year: i32) -> YearTable {
+        let mut cumulative = Vec::with_capacity(367);
+        let mut count = 0u16;
+        cumulative.push(count);
+
+        for date in (1..=366).map_while(|ordinal| NaiveDate::from_yo_opt(year, ordinal)) {
+            let weekend = matches!(date.weekday(), Weekday::Sat | Weekday::Sun);
+            if !weekend && !self.is_holiday(date) {
+                count += 1;
+            }
+            cumulative.push(count);
+        }
+
+        YearTable {
+            cumulative: cumulative.into_boxed_slice(),
+        }
+    }
+}
+
+/// Iterator over the business days of a range.
+///
+/// Created by [`BusinessCalendar::business_days`].
+#[derive(Debug)]
+pub struct BusinessDays<'a> {
+    calendar: &'a BusinessCalendar,
+    table: Arc<YearTable>,
+    table_year: i32,
+    next: Option<NaiveDate>,
+    end: NaiveDate,
+}
+
+impl Iterator for BusinessDays<'_> {
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<NaiveDate> {
+        while let Some(date) = self.next.filter(|date| *date < self.end) {
+            if date.year() != self.table_year {
+                self.table = self.calendar.year(date.year());
+                self.table_year = date.year();
+            }
+
+            self.next = date.succ_opt();
+
+            if self.table.contains(date) {
+                return Some(date);
+            }
+        }
+
+        None
+    }
+}
+
+// OPERATIONS
+// ==========
+
+/// Checks if a date is a business day (dia útil) in Brazil.
+///
+/// Saturdays, Sundays and national holidays are not business days, nor are
+/// the holidays of the given state.
+///
+/// # Arguments
+///
+/// * `date` - The date to be checked.
+/// * `uf` - The state abbreviation (UF) whose holidays are considered.
+///
+/// # Returns
+///
+/// `Some(true)` if the date is a business day, `Some(false)` if it is not, or
+/// `None` if the UF is invalid.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::business_days::is_business_day;
+/// use chrono::NaiveDate;
+///
+/// assert_eq!(is_business_day(NaiveDate::from_ymd_opt(2024, 7, 9).unwrap(), None), Some(true));
+/// assert_eq!(is_business_day(NaiveDate::from_ymd_opt(2024, 7, 9).unwrap(), Some("SP")), Some(false));
+/// assert_eq!(is_business_day(NaiveDate::from_ymd_opt(2024, 7, 13).unwrap(), None), Some(false));
+/// assert_eq!(is_business_day(NaiveDate::from_ymd_opt(2024, 7, 9).unwrap(), Some("XX")), None);
+/// ```
+pub fn is_business_day(date: NaiveDate, uf: Option<&str>) -> Option<bool> {
+    let holiday = is_holiday(date, uf)?;
+    Some(!holiday && !matches!(date.weekday(), Weekday::Sat | Weekday::Sun))
+}
+
+/// Moves a date by a number of business days.
+///
+/// See [`BusinessCalendar::add_business_days`]. The free functions share one
+/// calendar per UF, so its holidays are computed once per year for the whole
+/// process.
+///
+/// # Returns
+///
+/// The resulting date, or `None` if the UF is invalid or the date is out of
+/// range.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::business_days::add_business_days;
+/// use chrono::NaiveDate;
+///
+/// // Friday before Christmas plus one business day
+/// let date = NaiveDate::from_ymd_opt(2024, 12, 20).unwrap();
+/// assert_eq!(add_business_days(date, 3, None), NaiveDate::from_ymd_opt(2024, 12, 26));
+/// assert_eq!(add_business_days(date, -5, None), NaiveDate::from_ymd_opt(2024, 12, 13));
+/// ```
+pub fn add_business_days(date: NaiveDate, days: i64, uf: Option<&str>) -> Option<NaiveDate> {
+    shared_calendar(uf)?.add_business_days(date, days)
+}
+
+/// Moves a date back by a number of business days.
+///
+/// See [`BusinessCalendar::subtract_business_days`].
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::business_days::subtract_business_days;
+/// use chrono::NaiveDate;
+///
+/// let date = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+/// assert_eq!(subtract_business_days(date, 1, None), NaiveDate::from_ymd_opt(2023, 12, 29));
+/// ```
+pub fn subtract_business_days(date: NaiveDate, days: i64, uf: Option<&str>) -> Option<NaiveDate> {
+    shared_calendar(uf)?.subtract_business_days(date, days)
+}
+
+/// Returns the first business day strictly after a date.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::business_days::next_business_day;
+/// use chrono::NaiveDate;
+///
+/// let date = NaiveDate::from_ymd_opt(2024, 3, 28).unwrap();
+/// assert_eq!(next_business_day(date, None), NaiveDate::from_ymd_opt(2024, 4, 1));
+/// ```
+pub fn next_business_day(date: NaiveDate, uf: Option<&str>) -> Option<NaiveDate> {
+    shared_calendar(uf)?.next_business_day(date)
+}
+
+/// Returns the last business day strictly before a date.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::business_days::previous_business_day;
+/// use chrono::NaiveDate;
+///
+/// let date = NaiveDate::from_ymd_opt(2024, 4, 1).unwrap();
+/// assert_eq!(previous_business_day(date, None), NaiveDate::from_ymd_opt(2024, 3, 28));
+/// ```
+pub fn previous_business_day(date: NaiveDate, uf: Option<&str>) -> Option<NaiveDate> {
+    shared_calendar(uf)?.previous_business_day(date)
+}
+
+/// Counts the business days in `[start, end)`.
+///
+/// See [`BusinessCalendar::count_business_days`].
+///
+/// # Returns
+///
+/// The number of business days, negative if `end` is before `start`, or
+/// `None` if the UF is invalid.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::business_days::count_business_days;
+/// use chrono::NaiveDate;
+///
+/// let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+/// let end = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+/// assert_eq!(count_business_days(start, end, None), Some(256));
+/// ```
+pub fn count_business_days(start: NaiveDate, end: NaiveDate, uf: Option<&str>) -> Option<i64> {
+    Some(shared_calendar(uf)?.count_business_days(start, end))
+}
+
+// HELPER FUNCTIONS
+// ================
+
+/// Calendars used by the free functions, one per UF.
+static SHARED_CALENDARS: OnceLock<RwLock<HashMap<Option<String>, Arc<BusinessCalendar>>>> =
+    OnceLock::new();
+
+/// Returns the calendar of a UF shared by the whole process, creating it on
+/// first use.
+fn shared_calendar(uf: Option<&str>) -> Option<Arc<BusinessCalendar>> {
+    let calendars = SHARED_CALENDARS.get_or_init(Default::default);
+    let key = uf.map(str::to_string);
+
+    if let Some(calendar) = calendars
+        .read()
+        .ok()
+        .and_then(|calendars| calendars.get(&key).cloned())
+    {
+        return Some(calendar);
+    }
+
+    let calendar = Arc::new(BusinessCalendar::new(uf)?);
+    match calendars.write() {
+        Ok(mut calendars) => Some(Arc::clone(calendars.entry(key).or_insert(calendar))),
+        Err(_) => Some(calendar),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    /// Reference implementation stepping one day at a time.
+    fn naive_add(start: NaiveDate, days: i64, uf: Option<&str>) -> NaiveDate {
+        let mut current = start;
+        let mut remaining = days.abs();
+        while remaining > 0 {
+            current = if days > 0 {
+                current.succ_opt().unwrap()
+            } else {
+                current.pred_opt().unwrap()
+            };
+            if is_business_day(current, uf).unwrap() {
+                remaining -= 1;
+            }
+        }
+        current
+    }
+
+    #[test]
+    fn test_is_business_day() {
+        assert_eq!(is_business_day(date(2024, 1, 2), None), Some(true));
+        assert_eq!(is_business_day(date(2024, 1, 1), None), Some(false));
+        assert_eq!(is_business_day(date(2024, 1, 6), None), Some(false));
+        assert_eq!(is_business_day(date(2024, 1, 7), None), Some(false));
+        assert_eq!(is_business_day(date(2024, 7, 2), Some("BA")), Some(false));
+        assert_eq!(is_business_day(date(2024, 7, 2), Some("SP")), Some(true));
+        assert_eq!(is_business_day(date(2024, 7, 2), Some("XX")), None);
+
+        let calendar = BusinessCalendar::new(Some("BA")).unwrap();
+        assert!(!calendar.is_business_day(date(2024, 7, 2)));
+        assert!(calendar.is_business_day(date(2024, 7, 3)));
+        assert_eq!(calendar.uf(), Some("BA"));
+    }
+
+    #[test]
+    fn test_calendar_matches_is_business_day() {
+        for uf in [None, Some("SP"), Some("SC")] {
+            let calendar = BusinessCalendar::new(uf).unwrap();
+            for day in date(2023, 1, 1).iter_days().take(800) {
+                assert_eq!(
+                    Some(calendar.is_business_day(day)),
+                    is_business_day(day, uf),
+                    "{day} {uf:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_add_business_days() {
+        // Friday before Carnival is just a weekend away (Carnival is not a holiday)
+        assert_eq!(
+            add_business_days(date(2024, 2, 9), 1, None),
+            Some(date(2024, 2, 12))
+        );
+        // Over Good Friday
+        assert_eq!(
+            add_business_days(date(2024, 3, 28), 1, None),
+            Some(date(2024, 4, 1))
+        );
+        // From a weekend
+        assert_eq!(
+            add_business_days(date(2024, 1, 6), 1, None),
+            Some(date(2024, 1, 8))
+        );
+        assert_eq!(
+            add_business_days(date(2024, 1, 6), -1, None),
+            Some(date(2024, 1, 5))
+        );
+        assert_eq!(
+            add_business_days(date(2024, 1, 6), 0, None),
+            Some(date(2024, 1, 6))
+        );
+        // Across the year boundary
+        assert_eq!(
+            add_business_days(date(2024, 12, 30), 2, None),
+            Some(date(2025, 1, 2))
+        );
+        assert_eq!(
+            subtract_business_days(date(2025, 1, 2), 2, None),
+            Some(date(2024, 12, 30))
+        );
+        assert_eq!(add_business_days(date(2024, 1, 1), 1, Some("XX")), None);
+        // Out of range
+        assert_eq!(add_business_days(date(2024, 1, 1), i64::MAX, None), None);
+        assert_eq!(add_business_days(date(2024, 1, 1), i64::MIN, None), None);
+        assert_eq!(
+            subtract_business_days(date(2024, 1, 1), i64::MAX, None),
+            None
+        );
+        assert_eq!(
+            subtract_business_days(date(2024, 1, 1), i64::MIN, None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_free_functions_share_calendar() {
+        let first = shared_calendar(Some("PE")).unwrap();
+        assert!(Arc::ptr_eq(&first, &shared_calendar(Some("PE")).unwrap()));
+        assert!(!Arc::ptr_eq(&first, &shared_calendar(None).unwrap()));
+        assert!(shared_calendar(Some("XX")).is_none());
+
+        // The year tables built by one call are reused by the next ones
+        assert!(!first.years.read().unwrap().contains_key(&1999));
+        add_business_days(date(1999, 3, 1), 1, Some("PE")).unwrap();
+        let table = Arc::clone(&first.years.read().unwrap()[&1999]);
+        count_business_days(date(1999, 1, 1), date(1999, 6, 1), Some("PE")).unwrap();
+        assert!(Arc::ptr_eq(&table, &first.years.read().unwrap()[&1999]));
+    }
+
+    #[test]
+    fn test_add_business_days_matches_stepping() {
+        let calendar = BusinessCalendar::new(Some("RJ")).unwrap();
+
+        for start in date(2023, 12, 1).iter_days().take(60) {
+            for days in [-300, -23, -5, -1, 1, 2, 5, 21, 252, 600] {
+                assert_eq!(
+                    calendar.add_business_days(start, days),
+                    Some(naive_add(start, days, Some("RJ"))),
+                    "{start} {days}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_next_and_previous_business_day() {
+        assert_eq!(
+            next_business_day(date(2024, 7, 8), Some("SP")),
+            Some(date(2024, 7, 10))
+        );
+        assert_eq!(
+            previous_business_day(date(2024, 7, 10), Some("SP")),
+            Some(date(2024, 7, 8))
+        );
+        assert_eq!(
+            next_business_day(date(2024, 1, 2), None),
+            Some(date(2024, 1, 3))
+        );
+        assert_eq!(next_business_day(NaiveDate::MAX, None), None);
+        assert_eq!(previous_business_day(NaiveDate::MIN, None), None);
+    }
+
+    #[test]
+    fn test_count_business_days() {
+        assert_eq!(
+            count_business_days(date(2024, 1, 1), date(2025, 1, 1), None),
+            Some(256)
+        );
+        assert_eq!(
+            count_business_days(date(2024, 1, 1), date(2025, 1, 1), Some("SP")),
+            Some(255)
+        );
+        assert_eq!(
+            count_business_days(date(2024, 3, 25), date(2024, 4, 1), None),
+            Some(4)
+        );
+        assert_eq!(
+            count_business_days(date(2024, 4, 1), date(2024, 3, 25), None),
+            Some(-4)
+        );
+        assert_eq!(
+            count_business_days(date(2024, 4, 1), date(2024, 4, 1), None),
+            Some(0)
+        );
+        assert_eq!(
+            count_business_days(date(2024, 4, 1), date(2024, 4, 1), Some("XX")),
+            None
+        );
+
+        let calendar = BusinessCalendar::new(None).unwrap();
+        let start = date(2020, 6, 15);
+        let end = date(2023, 2, 3);
+        assert_eq!(
+            calendar.count_business_days(start, end),
+            calendar.business_days(start, end).count() as i64
+        );
+    }
+
+    #[test]
+    fn test_business_days_iterator() {
+        let calendar = BusinessCalendar::new(None).unwrap();
+
+        let days: Vec<NaiveDate> = calendar
+            .business_days(date(2024, 12, 28), date(2025, 1, 4))
+            .collect();
+        assert_eq!(
+            days,
+            vec![
+                date(2024, 12, 30),
+                date(2024, 12, 31),
+                date(2025, 1, 2),
+                date(2025, 1, 3)
+            ]
+        );
+
+        assert_eq!(
+            calendar
+                .business_days(date(2024, 1, 5), date(2024, 1, 1))
+                .next(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_municipal_calendar() {
+        let calendar = BusinessCalendar::for_municipality("3304557").unwrap();
+        assert_eq!(calendar.uf(), Some("RJ"));
+        assert_eq!(calendar.ibge(), Some("3304557"));
+
+        // São Sebastião (municipal) and São Jorge (state)
+        assert!(!calendar.is_business_day(date(2025, 1, 20)));
+        assert!(!calendar.is_business_day(date(2025, 4, 23)));
+        assert!(BusinessCalendar::new(Some("RJ"))
+            .unwrap()
+            .is_business_day(date(2025, 1, 20)));
+
+        assert!(BusinessCalendar::for_municipality("0000000").is_none());
+    }
+
+    #[test]
+    fn test_excluding_optional_days() {
+        let calendar = BusinessCalendar::new(None).unwrap();
+        let optional = BusinessCalendar::new(None)
+            .unwrap()
+            .excluding(HolidayKind::Optional);
+        let all = optional.clone().excluding(HolidayKind::HalfDay);
+
+        // Carnival Monday, Ash Wednesday and Corpus Christi in 2024
+        for (day, expected) in [
+            (date(2024, 2, 12), [true, false, false]),
+            (date(2024, 2, 14), [true, true, false]),
+            (date(2024, 5, 30), [true, false, false]),
+        ] {
+            assert_eq!(
+                [
+                    calendar.is_business_day(day),
+                    optional.is_business_day(day),
+                    all.is_business_day(day)
+                ],
+                expected,
+                "{day}"
+            );
+        }
+
+        assert_eq!(
+            optional.add_business_days(date(2024, 2, 9), 1),
+            Some(date(2024, 2, 14))
+        );
+        assert_eq!(
+            all.count_business_days(date(2024, 2, 12), date(2024, 2, 16)),
+            1
+        );
+
+        // Excluding holidays is a no-op
+        let same = BusinessCalendar::new(None)
+            .unwrap()
+            .excluding(HolidayKind::Holiday);
+        assert!(same.is_business_day(date(2024, 2, 12)));
+    }
+
+    #[test]
+    fn test_anbima_calendar() {
+        let anbima = BusinessCalendar::anbima();
+
+        // Carnival and Corpus Christi are closed, Ash Wednesday is open
+        assert!(!anbima.is_business_day(date(2024, 2, 12)));
+        assert!(!anbima.is_business_day(date(2024, 2, 13)));
+        assert!(anbima.is_business_day(date(2024, 2, 14)));
+        assert!(!anbima.is_business_day(date(2024, 5, 30)));
+
+        // State holidays are ignored
+        assert!(anbima.is_business_day(date(2024, 7, 9)));
+        assert_eq!(anbima.uf(), None);
+
+        // December 24 and 31 are business days for ANBIMA
+        assert!(anbima.is_business_day(date(2024, 12, 24)));
+        assert!(anbima.is_business_day(date(2024, 12, 31)));
+
+        assert_eq!(anbima.business_days_in_year(2024), 253);
+        assert_eq!(anbima.business_days_in_year(2023), 249);
+    }
+
+    #[test]
+    fn test_b3_calendar() {
+        let b3 = BusinessCalendar::b3();
+
+        assert!(!b3.is_business_day(date(2024, 12, 24)));
+        assert!(!b3.is_business_day(date(2024, 12, 31)));
+        assert!(!b3.is_business_day(date(2024, 2, 13)));
+        assert!(b3.is_business_day(date(2024, 2, 14)));
+
+        // São Paulo holidays closed the exchange until 2021
+        assert!(!b3.is_business_day(date(2021, 7, 9)));
+        assert!(!b3.is_business_day(date(2019, 11, 20)));
+        assert!(b3.is_business_day(date(2022, 1, 25)));
+        assert!(b3.is_business_day(date(2024, 7, 9)));
+
+        assert_eq!(b3.business_days_in_year(2024), 251);
+    }
+
+    #[test]
+    fn test_settlement_date() {
+        let b3 = BusinessCalendar::b3();
+
+        assert_eq!(
+            b3.settlement_date(date(2024, 12, 20), 2),
+            Some(date(2024, 12, 26))
+        );
+        assert_eq!(
+            b3.settlement_date(date(2024, 12, 27), 2),
+            Some(date(2025, 1, 2))
+        );
+        // D+0 on a weekend settles on the next business day
+        assert_eq!(
+            b3.settlement_date(date(2024, 6, 1), 0),
+            Some(date(2024, 6, 3))
+        );
+        // A trade on a weekend is considered made on Monday
+        assert_eq!(
+            b3.settlement_date(date(2024, 6, 1), 2),
+            Some(date(2024, 6, 5))
+        );
+        assert_eq!(
+            b3.settlement_date(date(2024, 6, 3), 2),
+            Some(date(2024, 6, 5))
+        );
+    }
+
+    #[test]
+    fn test_year_fraction_252() {
+        let anbima = BusinessCalendar::anbima();
+        let start = date(2024, 1, 1);
+        let end = date(2025, 1, 1);
+
+        assert!((anbima.year_fraction_252(start, end) - 253.0 / 252.0).abs() < 1e-12);
+        assert!((anbima.year_fraction_252(end, start) + 253.0 / 252.0).abs() < 1e-12);
+        assert_eq!(anbima.year_fraction_252(start, start), 0.0);
+    }
+
+    #[test]
+    fn test_suspensions_and_recess() {
+        let calendar = BusinessCalendar::new(None)
+            .unwrap()
+            .with_suspension(date(2024, 3, 4), date(2024, 3, 5))
+            .with_suspension(date(2024, 3, 10), date(2024, 3, 1));
+
+        assert!(!calendar.is_business_day(date(2024, 3, 4)));
+        assert!(!calendar.is_business_day(date(2024, 3, 5)));
+        assert!(calendar.is_business_day(date(2024, 3, 6)));
+        assert!(calendar.is_business_day(date(2024, 3, 8)));
+
+        let courts = BusinessCalendar::new(None).unwrap().with_forensic_recess();
+        assert!(courts.is_business_day(date(2024, 12, 19)));
+        assert!(!courts.is_business_day(date(2024, 12, 20)));
+        assert!(!courts.is_business_day(date(2025, 1, 20)));
+        assert!(courts.is_business_day(date(2025, 1, 21)));
+        assert_eq!(
+            courts.next_business_day(date(2024, 12, 19)),
+            Some(date(2025, 1, 21))
+        );
+    }
+
+    #[test]
+    fn test_calendar_is_shareable() {
+        fn assert_sync<T: Send + Sync>() {}
+        assert_sync::<BusinessCalendar>();
+
+        let calendar = BusinessCalendar::new(Some("MG")).unwrap();
+        calendar.is_business_day(date(2024, 1, 2));
+        let copy = calendar.clone();
+        assert_eq!(copy.uf(), Some("MG"));
+        assert!(!copy.is_business_day(date(2024, 4, 21)));
+    }
+
+    #[test]
+    fn test_calendar_time_zone() {
+        use chrono::TimeZone;
+
+        assert_eq!(
+            BusinessCalendar::new(None).unwrap().time_zone(),
+            Tz::America__Sao_Paulo
+        );
+        assert_eq!(
+            BusinessCalendar::new(Some("MT")).unwrap().time_zone(),
+            Tz::America__Cuiaba
+        );
+        assert_eq!(
+            BusinessCalendar::for_municipality("3550308")
+                .unwrap()
+                .time_zone(),
+            Tz::America__Sao_Paulo
+        );
+        assert_eq!(BusinessCalendar::b3().time_zone(), Tz::America__Sao_Paulo);
+
+        let instant = Utc.with_ymd_and_hms(2024, 1, 1, 2, 30, 0).unwrap();
+        let calendar = BusinessCalendar::new(Some("PE")).unwrap();
+        assert_eq!(calendar.local_date(instant), date(2023, 12, 31));
+
+        let calendar = calendar.with_time_zone(Tz::America__Noronha);
+        assert_eq!(calendar.time_zone(), Tz::America__Noronha);
+        assert_eq!(calendar.local_date(instant), date(2024, 1, 1));
+    }
+}
@@ -2,11 +2,31 @@ use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::error::Error;
 use std::fmt;
+use std::sync::{Arc, RwLock};
+
+#[cfg(feature = "async")]
+mod async_provider;
+mod batch;
+mod cache;
+mod provider;
+
+#[cfg(feature = "async")]
+pub use async_provider::{
+    get_address_from_cep_async, get_cep_information_from_address_async, lookup_address_async,
+    lookup_addresses_async, set_default_async_provider, AsyncCepProvider, BoxFuture,
+};
+pub use batch::{BatchLookup, BatchResult, RateLimitedProvider};
+pub use cache::{CacheStats, CachedProvider};
+pub use provider::{BrasilApi, CepProvider, FallbackProvider, MockProvider, OpenCep, ViaCep};
 
 // TYPES
 // ======
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+/// An address as returned by the CEP providers, in the ViaCEP format.
+///
+/// Fields a provider does not return are left empty.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(default)]
 pub struct Address {
     pub cep: String,
     pub logradouro: String,
@@ -15,7 +35,6 @@ pub struct Address {
     pub localidade: String,
     pub uf: String,
     pub ibge: String,
-    #[serde(default)]
     pub gia: String,
     pub ddd: String,
     pub siafi: String,
@@ -50,6 +69,68 @@ impl fmt::Display for CEPNotFound {
 
 impl Error for CEPNotFound {}
 
+/// Error returned by the CEP lookups.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CepError {
+    /// The CEP does not have 8 digits. Holds the input.
+    InvalidCep(String),
+    /// The UF is not a Brazilian state. Holds the input.
+    InvalidUf(String),
+    /// The CEP or address does not exist. Holds the CEP or address searched.
+    NotFound(String),
+    /// The provider could not be reached or answered with an unexpected HTTP
+    /// status. Holds the message.
+    Network(String),
+    /// The provider did not answer within the timeout.
+    Timeout,
+    /// The provider refused the request for exceeding its rate limit (HTTP
+    /// 429).
+    RateLimited,
+    /// The response of the provider could not be decoded. Holds the parser's
+    /// message.
+    Decode(String),
+    /// The provider does not support the lookup, e.g. address search on
+    /// BrasilAPI. Holds the message.
+    Unsupported(String),
+}
+
+impl fmt::Display for CepError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CepError::InvalidCep(cep) => write!(f, "CEP '{}' is invalid.", cep),
+            CepError::InvalidUf(uf) => write!(f, "Invalid UF: {}", uf),
+            CepError::NotFound(searched) => write!(f, "not found: {}", searched),
+            CepError::Network(message) => write!(f, "network error: {}", message),
+            CepError::Timeout => write!(f, "CEP provider timed out"),
+            CepError::RateLimited => write!(f, "CEP provider rate limit exceeded"),
+            CepError::Decode(message) => write!(f, "invalid CEP provider response: {}", message),
+            CepError::Unsupported(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl CepError {
+    /// Whether the lookup may succeed if retried later: network failures,
+    /// timeouts and rate limiting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::cep::CepError;
+    ///
+    /// assert!(CepError::Timeout.is_transient());
+    /// assert!(!CepError::NotFound("99999999".to_string()).is_transient());
+    /// ```
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            CepError::Network(_) | CepError::Timeout | CepError::RateLimited
+        )
+    }
+}
+
+impl Error for CepError {}
+
 // FORMATTING
 // ==========
 
@@ -164,7 +245,110 @@ pub fn generate() -> String {
 // API FUNCTIONS
 // =============
 
-/// Fetches address information from a given CEP (Postal Code) using the ViaCEP API.
+/// The provider of the lookup functions, ViaCEP when `None`.
+static DEFAULT_PROVIDER: RwLock<Option<Arc<dyn CepProvider>>> = RwLock::new(None);
+
+/// Sets the provider used by [`lookup_address`], [`lookup_addresses`],
+/// [`get_address_from_cep`] and [`get_cep_information_from_address`] for the
+/// whole process. The default is [`ViaCep`]. The async functions have their
+/// own default, set with `set_default_async_provider`.
+///
+/// # Arguments
+///
+/// * `provider` - The provider, e.g. a [`CachedProvider`] so repeated
+///   lookups stop hitting the network.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::cep::{self, CachedProvider, ViaCep};
+///
+/// cep::set_default_provider(CachedProvider::new(ViaCep::new()));
+/// ```
+pub fn set_default_provider(provider: impl CepProvider + 'static) {
+    let mut default = DEFAULT_PROVIDER
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    *default = Some(Arc::new(provider));
+}
+
+/// Looks up the address of a CEP (Postal Code) using the default provider,
+/// the ViaCEP API unless changed with [`set_default_provider`].
+///
+/// # Arguments
+///
+/// * `cep` - The CEP (Postal Code), with or without symbols.
+///
+/// # Returns
+///
+/// The address of the CEP.
+///
+/// # Errors
+///
+/// * `CepError::InvalidCep` - When the input CEP is invalid.
+/// * `CepError::NotFound` - When the CEP does not exist.
+/// * `CepError::Network`, `Timeout`, `RateLimited` or `Decode` - When the
+///   provider could not be queried.
+///
+/// # Examples
+///
+/// ```no_run
+/// use brazilian_utils::cep::{lookup_address, CepError};
+///
+/// match lookup_address("01310-200") {
+///     Ok(address) => println!("{}, {}", address.logradouro, address.localidade),
+///     Err(CepError::NotFound(_)) => println!("CEP not found"),
+///     Err(e) => eprintln!("Error: {}", e),
+/// }
+/// ```
+pub fn lookup_address(cep: &str) -> Result<Address, CepError> {
+    default_provider().get_address(cep)
+}
+
+/// Looks up the CEPs (Postal Codes) of an address using the default
+/// provider, the ViaCEP API unless changed with [`set_default_provider`].
+///
+/// # Arguments
+///
+/// * `federal_unit` - The two-letter abbreviation of the Brazilian state.
+/// * `city` - The name of the city.
+/// * `street` - The name (or substring) of the street.
+///
+/// # Returns
+///
+/// The addresses matching the search.
+///
+/// # Errors
+///
+/// * `CepError::InvalidUf` - When the input UF is invalid.
+/// * `CepError::NotFound` - When no CEP matches the address.
+/// * `CepError::Network`, `Timeout`, `RateLimited` or `Decode` - When the
+///   provider could not be queried.
+///
+/// # Examples
+///
+/// ```no_run
+/// use brazilian_utils::cep::lookup_addresses;
+///
+/// if let Ok(addresses) = lookup_addresses("SP", "São Paulo", "Avenida Paulista") {
+///     for addr in addresses {
+///         println!("CEP: {}", addr.cep);
+///     }
+/// }
+/// ```
+pub fn lookup_addresses(
+    federal_unit: &str,
+    city: &str,
+    street: &str,
+) -> Result<Vec<Address>, CepError> {
+    default_provider().get_addresses(federal_unit, city, street)
+}
+
+/// Fetches address information from a given CEP (Postal Code) using the default
+/// provider, the ViaCEP API unless changed with [`set_default_provider`].
+///
+/// Prefer [`lookup_address`], whose [`CepError`] tells a missing CEP apart
+/// from a failed request.
 ///
 /// # Arguments
 ///
@@ -200,50 +384,15 @@ pub fn get_address_from_cep(
     cep: &str,
     raise_exceptions: bool,
 ) -> Result<Option<Address>, Box<dyn Error>> {
-    let base_api_url = "https://viacep.com.br/ws/{}/json/";
-
-    let clean_cep = remove_symbols(cep);
-    let cep_is_valid = is_valid(&clean_cep);
-
-    if !cep_is_valid {
-        if raise_exceptions {
-            return Err(Box::new(InvalidCEP {
-                cep: cep.to_string(),
-            }));
-        }
-        return Ok(None);
-    }
-
-    let url = base_api_url.replace("{}", &clean_cep);
-
-    match reqwest::blocking::get(&url) {
-        Ok(response) => {
-            let json: serde_json::Value = response.json()?;
-
-            if json.get("erro").is_some() {
-                if raise_exceptions {
-                    return Err(Box::new(CEPNotFound {
-                        message: cep.to_string(),
-                    }));
-                }
-                return Ok(None);
-            }
-
-            let address: Address = serde_json::from_value(json)?;
-            Ok(Some(address))
-        }
-        Err(_e) => {
-            if raise_exceptions {
-                return Err(Box::new(CEPNotFound {
-                    message: cep.to_string(),
-                }));
-            }
-            Ok(None)
-        }
-    }
+    raise_legacy(lookup_address(cep), cep, raise_exceptions)
+        .map_err(|error| error as Box<dyn Error>)
 }
 
-/// Fetches CEP (Postal Code) options from a given address using the ViaCEP API.
+/// Fetches CEP (Postal Code) options from a given address using the default
+/// provider, the ViaCEP API unless changed with [`set_default_provider`].
+///
+/// Prefer [`lookup_addresses`], whose [`CepError`] tells a missing address
+/// apart from a failed request.
 ///
 /// # Arguments
 ///
@@ -259,7 +408,7 @@ pub fn get_address_from_cep(
 ///
 /// # Errors
 ///
-/// * `ValueError` - When the input UF is invalid and `raise_exceptions` is `true`.
+/// * `CepError::InvalidUf` - When the input UF is invalid and `raise_exceptions` is `true`.
 /// * `CEPNotFound` - When the input address is not found and `raise_exceptions` is `true`.
 ///
 /// # Examples
@@ -287,60 +436,49 @@ pub fn get_cep_information_from_address(
     street: &str,
     raise_exceptions: bool,
 ) -> Result<Option<Vec<Address>>, Box<dyn Error>> {
-    // Valid Brazilian state abbreviations
-    const VALID_UFS: &[&str] = &[
-        "AC", "AL", "AP", "AM", "BA", "CE", "DF", "ES", "GO", "MA", "MT", "MS", "MG", "PA", "PB",
-        "PR", "PE", "PI", "RJ", "RN", "RS", "RO", "RR", "SC", "SP", "SE", "TO",
-    ];
+    let result = lookup_addresses(federal_unit, city, street);
+    let searched = format!("{} - {} - {}", federal_unit, city, street);
+    raise_legacy(result, &searched, raise_exceptions).map_err(|error| error as Box<dyn Error>)
+}
 
-    let federal_unit_upper = federal_unit.to_uppercase();
+// HELPER FUNCTIONS
+// ================
 
-    if !VALID_UFS.contains(&federal_unit_upper.as_str()) {
-        if raise_exceptions {
-            return Err(format!("Invalid UF: {}", federal_unit).into());
-        }
-        return Ok(None);
+/// The provider set with [`set_default_provider`], or ViaCEP.
+fn default_provider() -> Arc<dyn CepProvider> {
+    let default = DEFAULT_PROVIDER
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    match default.as_ref() {
+        Some(provider) => Arc::clone(provider),
+        None => Arc::new(ViaCep::new()),
     }
+}
 
-    let base_api_url = "https://viacep.com.br/ws/{}/{}/{}/json/";
-
-    // Normalize strings: remove accents and replace spaces with %20
-    let parsed_city = normalize_string(city);
-    let parsed_street = normalize_string(street);
-
-    let url = base_api_url
-        .replace("{}", &federal_unit_upper)
-        .replacen("{}", &parsed_city, 1)
-        .replacen("{}", &parsed_street, 1);
-
-    match reqwest::blocking::get(&url) {
-        Ok(response) => {
-            let addresses: Vec<Address> = response.json()?;
-
-            if addresses.is_empty() {
-                if raise_exceptions {
-                    return Err(Box::new(CEPNotFound {
-                        message: format!("{} - {} - {}", federal_unit, city, street),
-                    }));
-                }
-                return Ok(None);
-            }
-
-            Ok(Some(addresses))
-        }
-        Err(_e) => {
-            if raise_exceptions {
-                return Err(Box::new(CEPNotFound {
-                    message: format!("{} - {} - {}", federal_unit, city, street),
-                }));
-            }
-            Ok(None)
-        }
+/// Maps the result of a lookup to the `raise_exceptions` convention of
+/// [`get_address_from_cep`] and [`get_cep_information_from_address`]: invalid
+/// input is reported as such, and every other failure as not found.
+fn raise_legacy<T>(
+    result: Result<T, CepError>,
+    searched: &str,
+    raise_exceptions: bool,
+) -> Result<Option<T>, Box<dyn Error + Send + Sync>> {
+    match result {
+        Ok(found) => Ok(Some(found)),
+        Err(_) if !raise_exceptions => Ok(None),
+        Err(CepError::InvalidCep(cep)) => Err(Box::new(InvalidCEP { cep })),
+        Err(error @ CepError::InvalidUf(_)) => Err(Box::new(error)),
+        Err(_) => Err(Box::new(CEPNotFound {
+            message: searched.to_string(),
+        })),
     }
 }
 
-// HELPER FUNCTIONS
-// ================
+/// Valid Brazilian state abbreviations accepted by the address search.
+const VALID_UFS: &[&str] = &[
+    "AC", "AL", "AP", "AM", "BA", "CE", "DF", "ES", "GO", "MA", "MT", "MS", "MG", "PA", "PB", "PR",
+    "PE", "PI", "RJ", "RN", "RS", "RO", "RR", "SC", "SP", "SE", "TO",
+];
 
 /// Normalizes a string by removing accents and replacing spaces with %20
 fn normalize_string(s: &str) -> String {
@@ -461,4 +599,57 @@ mod tests {
         // So these should fail format validation
         assert_eq!(format_cep("01310-200"), None); // contains dash
     }
+
+    #[test]
+    fn test_raise_legacy() {
+        let found: Result<u8, CepError> = Ok(1);
+        assert_eq!(raise_legacy(found, "x", true).unwrap(), Some(1));
+
+        let invalid: Result<u8, CepError> = Err(CepError::InvalidCep("123".to_string()));
+        assert_eq!(raise_legacy(invalid.clone(), "123", false).unwrap(), None);
+        let error = raise_legacy(invalid, "123", true).unwrap_err();
+        assert!(error.is::<InvalidCEP>());
+
+        let invalid_uf: Result<u8, CepError> = Err(CepError::InvalidUf("XX".to_string()));
+        let error = raise_legacy(invalid_uf, "XX", true).unwrap_err();
+        assert_eq!(error.to_string(), "Invalid UF: XX");
+
+        // Failed requests are reported as not found, as before
+        let timeout: Result<u8, CepError> = Err(CepError::Timeout);
+        let error = raise_legacy(timeout, "01310200", true).unwrap_err();
+        assert_eq!(error.to_string(), "01310200");
+        assert!(error.is::<CEPNotFound>());
+    }
+
+    #[test]
+    fn test_cep_error_display() {
+        assert_eq!(
+            CepError::InvalidCep("123".to_string()).to_string(),
+            "CEP '123' is invalid."
+        );
+        assert_eq!(
+            CepError::NotFound("99999999".to_string()).to_string(),
+            "not found: 99999999"
+        );
+        assert_eq!(
+            CepError::RateLimited.to_string(),
+            "CEP provider rate limit exceeded"
+        );
+    }
+
+    #[test]
+    fn test_default_provider() {
+        // The only test that changes the process-wide provider
+        let mock = MockProvider::new().with_address(Address {
+            cep: "01310-200".to_string(),
+            uf: "SP".to_string(),
+            ..Default::default()
+        });
+        set_default_provider(CachedProvider::new(mock));
+
+        assert_eq!(lookup_address("01310200").unwrap().uf, "SP");
+        assert!(get_address_from_cep("01310-200", true).unwrap().is_some());
+        assert!(get_address_from_cep("99999999", false).unwrap().is_none());
+        assert_eq!(default_provider().name(), "Mock");
+    }
 }
@@ -0,0 +1,534 @@
+//! Async CEP lookups, for services running on an async runtime such as tokio.
+
+use super::provider::{clean_cep, clean_federal_unit, found, searched_address, unsupported_search};
+use super::{
+    raise_legacy, Address, BrasilApi, CachedProvider, CepError, CepProvider, MockProvider, OpenCep,
+    ViaCep,
+};
+use std::error::Error;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+
+/// A boxed future that can be sent across threads, as returned by
+/// [`AsyncCepProvider`].
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+// TRAIT
+// =====
+
+/// A [`CepProvider`] that can also be queried without blocking the thread.
+///
+/// Lookups honour the timeout of the provider and are cancellation safe:
+/// dropping the future (for example when a `tokio::time::timeout` elapses or
+/// a `tokio::select!` branch loses) aborts the request, and the provider can
+/// be used again right away.
+///
+/// # Examples
+///
+/// ```no_run
+/// use brazilian_utils::cep::{AsyncCepProvider, ViaCep};
+/// use std::time::Duration;
+///
+/// # async fn run() {
+/// let provider = ViaCep::new().with_timeout(Duration::from_secs(2));
+/// if let Ok(address) = provider.get_address_async("01310-200").await {
+///     println!("{}", address.logradouro);
+/// }
+/// # }
+/// ```
+pub trait AsyncCepProvider: CepProvider {
+    /// Fetches the address of a clean, valid CEP.
+    ///
+    /// # Errors
+    ///
+    /// * `CepError::NotFound` - When the provider does not know the CEP.
+    /// * Any other `CepError` when the provider could not be queried.
+    fn fetch_address_async<'a>(&'a self, cep: &'a str) -> BoxFuture<'a, Result<Address, CepError>>;
+
+    /// Fetches the CEPs of an address. The UF is valid and uppercase.
+    ///
+    /// Providers that cannot search by address fail with
+    /// `CepError::Unsupported`.
+    fn fetch_addresses_async<'a>(
+        &'a self,
+        federal_unit: &'a str,
+        city: &'a str,
+        street: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<Address>, CepError>> {
+        let _ = (federal_unit, city, street);
+        Box::pin(async move { Err(unsupported_search(self.name())) })
+    }
+
+    /// Looks up the address of a CEP, with or without symbols.
+    ///
+    /// # Errors
+    ///
+    /// * `CepError::InvalidCep` - When the input CEP is invalid.
+    /// * `CepError::NotFound` - When the provider does not know the CEP.
+    /// * Any other `CepError` when the provider could not be queried.
+    fn get_address_async<'a>(&'a self, cep: &'a str) -> BoxFuture<'a, Result<Address, CepError>> {
+        Box::pin(async move {
+            let clean_cep = clean_cep(cep)?;
+            self.fetch_address_async(&clean_cep).await
+        })
+    }
+
+    /// Looks up the CEPs of an address.
+    ///
+    /// # Errors
+    ///
+    /// * `CepError::InvalidUf` - When the UF is invalid.
+    /// * `CepError::NotFound` - When no CEP matches the address.
+    /// * Any other `CepError` when the provider could not be queried.
+    fn get_addresses_async<'a>(
+        &'a self,
+        federal_unit: &'a str,
+        city: &'a str,
+        street: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<Address>, CepError>> {
+        Box::pin(async move {
+            let federal_unit = clean_federal_unit(federal_unit)?;
+            self.fetch_addresses_async(&federal_unit, city, street)
+                .await
+        })
+    }
+}
+
+impl<P: AsyncCepProvider + ?Sized> AsyncCepProvider for Box<P> {
+    fn fetch_address_async<'a>(&'a self, cep: &'a str) -> BoxFuture<'a, Result<Address, CepError>> {
+        (**self).fetch_address_async(cep)
+    }
+
+    fn fetch_addresses_async<'a>(
+        &'a self,
+        federal_unit: &'a str,
+        city: &'a str,
+        street: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<Address>, CepError>> {
+        (**self).fetch_addresses_async(federal_unit, city, street)
+    }
+}
+
+// PROVIDERS
+// =========
+
+impl AsyncCepProvider for ViaCep {
+    fn fetch_address_async<'a>(&'a self, cep: &'a str) -> BoxFuture<'a, Result<Address, CepError>> {
+        Box::pin(async move {
+            let (status, body) = self.http.get_async(&Self::address_path(cep)).await?;
+            found(Self::decode_address(status, &body)?, || cep.to_string())
+        })
+    }
+
+    fn fetch_addresses_async<'a>(
+        &'a self,
+        federal_unit: &'a str,
+        city: &'a str,
+        street: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<Address>, CepError>> {
+        Box::pin(async move {
+            let path = Self::addresses_path(federal_unit, city, street);
+            let (status, body) = self.http.get_async(&path).await?;
+            found(Self::decode_addresses(status, &body)?, || {
+                searched_address(federal_unit, city, street)
+            })
+        })
+    }
+}
+
+impl AsyncCepProvider for BrasilApi {
+    fn fetch_address_async<'a>(&'a self, cep: &'a str) -> BoxFuture<'a, Result<Address, CepError>> {
+        Box::pin(async move {
+            let (status, body) = self.http.get_async(&Self::address_path(cep)).await?;
+            found(Self::decode_address(status, &body)?, || cep.to_string())
+        })
+    }
+}
+
+impl AsyncCepProvider for OpenCep {
+    fn fetch_address_async<'a>(&'a self, cep: &'a str) -> BoxFuture<'a, Result<Address, CepError>> {
+        Box::pin(async move {
+            let (status, body) = self.http.get_async(&Self::address_path(cep)).await?;
+            found(Self::decode_address(status, &body)?, || cep.to_string())
+        })
+    }
+}
+
+impl AsyncCepProvider for MockProvider {
+    fn fetch_address_async<'a>(&'a self, cep: &'a str) -> BoxFuture<'a, Result<Address, CepError>> {
+        Box::pin(async move { self.fetch_address(cep) })
+    }
+
+    fn fetch_addresses_async<'a>(
+        &'a self,
+        federal_unit: &'a str,
+        city: &'a str,
+        street: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<Address>, CepError>> {
+        Box::pin(async move { self.fetch_addresses(federal_unit, city, street) })
+    }
+}
+
+impl<P: AsyncCepProvider> AsyncCepProvider for CachedProvider<P> {
+    fn fetch_address_async<'a>(&'a self, cep: &'a str) -> BoxFuture<'a, Result<Address, CepError>> {
+        Box::pin(async move {
+            if let Some(result) = self.cached(cep) {
+                return result;
+            }
+
+            let result = self.inner().fetch_address_async(cep).await;
+            self.store(cep, &result);
+            result
+        })
+    }
+
+    fn fetch_addresses_async<'a>(
+        &'a self,
+        federal_unit: &'a str,
+        city: &'a str,
+        street: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<Address>, CepError>> {
+        self.inner()
+            .fetch_addresses_async(federal_unit, city, street)
+    }
+}
+
+// API FUNCTIONS
+// =============
+
+/// The provider of the async lookup functions, ViaCEP once first used.
+static DEFAULT_ASYNC_PROVIDER: RwLock<Option<Arc<dyn AsyncCepProvider>>> = RwLock::new(None);
+
+/// Sets the provider used by [`lookup_address_async`],
+/// [`lookup_addresses_async`], [`get_address_from_cep_async`] and
+/// [`get_cep_information_from_address_async`] for the whole process. The
+/// default is [`ViaCep`].
+///
+/// The async functions do not use the provider set with
+/// [`set_default_provider`](super::set_default_provider), which does not have
+/// to support async lookups.
+///
+/// # Arguments
+///
+/// * `provider` - The provider, e.g. a [`CachedProvider`] so repeated
+///   lookups stop hitting the network.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::cep::{self, CachedProvider, ViaCep};
+///
+/// cep::set_default_async_provider(CachedProvider::new(ViaCep::new()));
+/// ```
+pub fn set_default_async_provider(provider: impl AsyncCepProvider + 'static) {
+    let mut default = DEFAULT_ASYNC_PROVIDER
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    *default = Some(Arc::new(provider));
+}
+
+/// Looks up the address of a CEP (Postal Code) using the default async
+/// provider, the ViaCEP API unless changed with
+/// [`set_default_async_provider`], without blocking the thread.
+///
+/// The async version of [`lookup_address`](super::lookup_address), with the
+/// same arguments and errors.
+///
+/// # Examples
+///
+/// ```no_run
+/// use brazilian_utils::cep::{lookup_address_async, CepError};
+///
+/// # async fn run() {
+/// match lookup_address_async("01310-200").await {
+///     Ok(address) => println!("{}", address.logradouro),
+///     Err(CepError::NotFound(_)) => println!("CEP not found"),
+///     Err(e) => eprintln!("Error: {}", e),
+/// }
+/// # }
+/// ```
+pub async fn lookup_address_async(cep: &str) -> Result<Address, CepError> {
+    default_async_provider().get_address_async(cep).await
+}
+
+/// Looks up the CEPs (Postal Codes) of an address using the default async
+/// provider, the ViaCEP API unless changed with
+/// [`set_default_async_provider`], without blocking the thread.
+///
+/// The async version of [`lookup_addresses`](super::lookup_addresses), with
+/// the same arguments and errors.
+pub async fn lookup_addresses_async(
+    federal_unit: &str,
+    city: &str,
+    street: &str,
+) -> Result<Vec<Address>, CepError> {
+    default_async_provider()
+        .get_addresses_async(federal_unit, city, street)
+        .await
+}
+
+/// Fetches address information from a given CEP (Postal Code) using the
+/// default async provider, without blocking the thread.
+///
+/// The async version of [`get_address_from_cep`](super::get_address_from_cep),
+/// with the same arguments and results.
+///
+/// # Examples
+///
+/// ```no_run
+/// use brazilian_utils::cep::get_address_from_cep_async;
+///
+/// # async fn run() {
+/// match get_address_from_cep_async("01310200", false).await {
+///     Ok(Some(address)) => println!("CEP: {}", address.cep),
+///     Ok(None) => println!("CEP not found"),
+///     Err(e) => eprintln!("Error: {}", e),
+/// }
+/// # }
+/// ```
+pub async fn get_address_from_cep_async(
+    cep: &str,
+    raise_exceptions: bool,
+) -> Result<Option<Address>, Box<dyn Error + Send + Sync>> {
+    raise_legacy(lookup_address_async(cep).await, cep, raise_exceptions)
+}
+
+/// Fetches CEP (Postal Code) options from a given address using the default
+/// async provider, without blocking the thread.
+///
+/// The async version of
+/// [`get_cep_information_from_address`](super::get_cep_information_from_address),
+/// with the same arguments and results.
+///
+/// # Examples
+///
+/// ```no_run
+/// use brazilian_utils::cep::get_cep_information_from_address_async;
+///
+/// # async fn run() {
+/// let addresses =
+///     get_cep_information_from_address_async("SP", "São Paulo", "Avenida Paulista", false)
+///         .await;
+/// if let Ok(Some(addresses)) = addresses {
+///     for addr in addresses {
+///         println!("CEP: {}", addr.cep);
+///     }
+/// }
+/// # }
+/// ```
+pub async fn get_cep_information_from_address_async(
+    federal_unit: &str,
+    city: &str,
+    street: &str,
+    raise_exceptions: bool,
+) -> Result<Option<Vec<Address>>, Box<dyn Error + Send + Sync>> {
+    let result = lookup_addresses_async(federal_unit, city, street).await;
+    let searched = format!("{} - {} - {}", federal_unit, city, street);
+    raise_legacy(result, &searched, raise_exceptions)
+}
+
+// HELPER FUNCTIONS
+// ================
+
+/// The provider set with [`set_default_async_provider`], or a ViaCEP client
+/// shared by all calls.
+fn default_async_provider() -> Arc<dyn AsyncCepProvider> {
+    if let Some(provider) = DEFAULT_ASYNC_PROVIDER
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .as_ref()
+    {
+        return Arc::clone(provider);
+    }
+
+    let mut default = DEFAULT_ASYNC_PROVIDER
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    Arc::clone(default.get_or_insert_with(|| Arc::new(ViaCep::new())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::provider::tests::{paulista, serve, VIACEP_PAULISTA};
+    use super::*;
+    use std::net::TcpListener;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_viacep_address() {
+        let (base_url, paths) = serve(vec![(200, VIACEP_PAULISTA), (200, r#"{"erro": true}"#)]);
+        let provider = ViaCep::with_base_url(&base_url);
+
+        let address = provider.get_address_async("01310-200").await;
+        assert_eq!(address, Ok(paulista()));
+        assert_eq!(
+            provider.get_address_async("99999999").await,
+            Err(CepError::NotFound("99999999".to_string()))
+        );
+        assert_eq!(
+            *paths.lock().unwrap(),
+            vec!["/ws/01310200/json/", "/ws/99999999/json/"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_viacep_search() {
+        let body = format!("[{}]", VIACEP_PAULISTA);
+        let (base_url, paths) = serve(vec![(200, body), (200, "[]".to_string())]);
+        let provider = ViaCep::with_base_url(&base_url);
+
+        let addresses = provider
+            .get_addresses_async("sp", "São Paulo", "Avenida Paulista")
+            .await;
+        assert_eq!(addresses, Ok(vec![paulista()]));
+        assert!(matches!(
+            provider
+                .get_addresses_async("SP", "São Paulo", "Rua Inexistente")
+                .await,
+            Err(CepError::NotFound(_))
+        ));
+        assert_eq!(
+            paths.lock().unwrap()[0],
+            "/ws/SP/Sao%20Paulo/Avenida%20Paulista/json/"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_brasilapi_and_opencep() {
+        let brasilapi = r#"{"cep": "01310200", "state": "SP", "city": "São Paulo",
+            "neighborhood": "Bela Vista", "street": "Avenida Paulista"}"#;
+        let (base_url, _) = serve(vec![(200, brasilapi), (404, "{}")]);
+        let provider = BrasilApi::with_base_url(&base_url);
+        let address = provider.get_address_async("01310200").await.unwrap();
+        assert_eq!(address.cep, "01310-200");
+        assert!(matches!(
+            provider.get_address_async("99999999").await,
+            Err(CepError::NotFound(_))
+        ));
+        assert!(matches!(
+            provider
+                .get_addresses_async("SP", "São Paulo", "Paulista")
+                .await,
+            Err(CepError::Unsupported(_))
+        ));
+
+        let (base_url, _) = serve(vec![(200, VIACEP_PAULISTA), (429, "")]);
+        let provider = OpenCep::with_base_url(&base_url);
+        let address = provider.get_address_async("01310200").await;
+        assert_eq!(address, Ok(paulista()));
+        assert_eq!(
+            provider.get_address_async("01310200").await,
+            Err(CepError::RateLimited)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_input() {
+        let provider = MockProvider::new();
+        assert_eq!(
+            provider.get_address_async("123").await,
+            Err(CepError::InvalidCep("123".to_string()))
+        );
+        assert_eq!(
+            provider
+                .get_addresses_async("XX", "São Paulo", "Paulista")
+                .await,
+            Err(CepError::InvalidUf("XX".to_string()))
+        );
+        assert_eq!(provider.calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_mock_provider() {
+        let provider = MockProvider::new().with_address(paulista());
+        assert_eq!(provider.get_address_async("01310200").await, Ok(paulista()));
+        assert_eq!(
+            provider
+                .get_addresses_async("SP", "sao paulo", "paulista")
+                .await,
+            Ok(vec![paulista()])
+        );
+        assert!(matches!(
+            MockProvider::unavailable()
+                .get_address_async("01310200")
+                .await,
+            Err(CepError::Network(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_timeout() {
+        // A server that accepts the connection but never answers
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let provider = ViaCep::with_base_url(&base_url).with_timeout(Duration::from_millis(200));
+
+        assert_eq!(
+            provider.get_address_async("01310200").await,
+            Err(CepError::Timeout)
+        );
+        drop(listener);
+    }
+
+    #[tokio::test]
+    async fn test_cancellation() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let provider = ViaCep::with_base_url(&base_url).with_timeout(Duration::from_millis(300));
+
+        // Dropping the pending lookup cancels it
+        let lookup = provider.get_address_async("01310200");
+        let cancelled = tokio::time::timeout(Duration::from_millis(50), lookup).await;
+        assert!(cancelled.is_err());
+
+        // The provider is still usable afterwards, with the same timeout
+        assert_eq!(
+            provider.get_address_async("01310200").await,
+            Err(CepError::Timeout)
+        );
+        drop(listener);
+    }
+
+    #[tokio::test]
+    async fn test_cached_provider() {
+        let provider = CachedProvider::new(MockProvider::new().with_address(paulista()));
+
+        assert_eq!(provider.get_address_async("01310200").await, Ok(paulista()));
+        assert_eq!(provider.get_address("01310-200"), Ok(paulista()));
+        assert!(provider.get_address_async("99999999").await.is_err());
+        assert!(provider.get_address_async("99999999").await.is_err());
+
+        assert_eq!(provider.inner().calls(), 2);
+        assert_eq!(provider.stats().hits, 2);
+    }
+
+    #[tokio::test]
+    async fn test_default_async_provider() {
+        // The only test that changes the process-wide async provider
+        set_default_async_provider(CachedProvider::new(
+            MockProvider::new().with_address(paulista()),
+        ));
+
+        assert_eq!(lookup_address_async("01310-200").await, Ok(paulista()));
+        assert_eq!(
+            lookup_addresses_async("SP", "São Paulo", "Paulista").await,
+            Ok(vec![paulista()])
+        );
+        assert_eq!(
+            lookup_address_async("99999999").await,
+            Err(CepError::NotFound("99999999".to_string()))
+        );
+        assert!(get_address_from_cep_async("01310200", true)
+            .await
+            .unwrap()
+            .is_some());
+        assert!(
+            get_cep_information_from_address_async("SP", "São Paulo", "Paulista", true)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert_eq!(default_async_provider().name(), "Mock");
+    }
+}
@@ -0,0 +1,431 @@
+//! Bulk CEP lookups with bounded concurrency, rate limiting and retries.
+
+use super::{remove_symbols, Address, CepError, CepProvider};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Default number of lookups running at the same time.
+const DEFAULT_CONCURRENCY: usize = 8;
+
+/// Default number of retries of a transient failure.
+const DEFAULT_RETRIES: u32 = 3;
+
+/// Default wait before the first retry. Doubles on each retry.
+const DEFAULT_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Longest wait between retries.
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+// TYPES
+// =====
+
+/// The result of one CEP of a [`BatchLookup`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchResult {
+    /// The CEP without symbols.
+    pub cep: String,
+    /// The address, or the error of the last attempt.
+    pub result: Result<Address, CepError>,
+    /// The number of lookups made, 0 when the CEP is invalid.
+    pub attempts: u32,
+}
+
+// RATE LIMITING
+// =============
+
+/// A [`CepProvider`] that spaces the lookups of another provider to stay
+/// within a rate limit.
+///
+/// Lookups are spread evenly: a limit of 10 requests per second lets one
+/// lookup start every 100 milliseconds, blocking the calling thread until
+/// its turn. Wrap each provider of a [`FallbackProvider`](super::FallbackProvider)
+/// to give each service its own limit.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::cep::{CepProvider, MockProvider, RateLimitedProvider};
+/// use std::time::Duration;
+///
+/// let provider = RateLimitedProvider::new(MockProvider::new(), 100, Duration::from_secs(1));
+/// assert!(provider.get_address("01310200").is_err());
+/// ```
+#[derive(Debug)]
+pub struct RateLimitedProvider<P> {
+    provider: P,
+    interval: Duration,
+    next_slot: Mutex<Option<Instant>>,
+}
+
+impl<P> RateLimitedProvider<P> {
+    /// Allows at most `requests` lookups per `period`.
+    ///
+    /// # Panics
+    ///
+    /// When `requests` is zero.
+    pub fn new(provider: P, requests: u32, period: Duration) -> Self {
+        assert!(requests > 0, "the rate limit must allow some requests");
+        RateLimitedProvider {
+            provider,
+            interval: period / requests,
+            next_slot: Mutex::new(None),
+        }
+    }
+
+    /// The wrapped provider.
+    pub fn inner(&self) -> &P {
+        &self.provider
+    }
+
+    /// Blocks until the next lookup may start.
+    fn wait_turn(&self) {
+        let wait = {
+            let mut next_slot = self
+                .next_slot
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let now = Instant::now();
+            let slot = next_slot.map_or(now, |slot| slot.max(now));
+            *next_slot = Some(slot + self.interval);
+            slot - now
+        };
+
+        if !wait.is_zero() {
+            thread::sleep(wait);
+        }
+    }
+}
+
+impl<P: CepProvider> CepProvider for RateLimitedProvider<P> {
+    fn name(&self) -> &str {
+        self.provider.name()
+    }
+
+    fn fetch_address(&self, cep: &str) -> Result<Address, CepError> {
+        self.wait_turn();
+        self.provider.fetch_address(cep)
+    }
+
+    fn fetch_addresses(
+        &self,
+        federal_unit: &str,
+        city: &str,
+        street: &str,
+    ) -> Result<Vec<Address>, CepError> {
+        self.wait_turn();
+        self.provider.fetch_addresses(federal_unit, city, street)
+    }
+}
+
+// BATCH LOOKUP
+// ============
+
+/// Looks up many CEPs at once.
+///
+/// The input is deduplicated after removing symbols, so "01310-200" and
+/// "01310200" are looked up once. Lookups run on up to 8 threads by default,
+/// and transient failures ([`CepError::is_transient`]) are retried up to 3
+/// times, waiting 0.5, 1 and 2 seconds. Wrap the provider in a
+/// [`RateLimitedProvider`] to respect the limits of the service, and in a
+/// [`CachedProvider`](super::CachedProvider) to skip CEPs already known.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::cep::{Address, BatchLookup, CepError, MockProvider};
+///
+/// let provider = MockProvider::new().with_address(Address {
+///     cep: "01310-200".to_string(),
+///     ..Default::default()
+/// });
+///
+/// let results = BatchLookup::new(&provider)
+///     .with_concurrency(4)
+///     .run(["01310-200", "01310200", "99999999", "123"]);
+///
+/// assert_eq!(results.len(), 3);
+/// assert!(results[0].result.is_ok());
+/// assert!(matches!(results[1].result, Err(CepError::NotFound(_))));
+/// assert!(matches!(results[2].result, Err(CepError::InvalidCep(_))));
+/// assert_eq!(provider.calls(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BatchLookup<P> {
+    provider: P,
+    concurrency: usize,
+    retries: u32,
+    backoff: Duration,
+}
+
+impl<P: CepProvider> BatchLookup<P> {
+    /// Creates a batch lookup over a provider, which may be a reference.
+    pub fn new(provider: P) -> Self {
+        BatchLookup {
+            provider,
+            concurrency: DEFAULT_CONCURRENCY,
+            retries: DEFAULT_RETRIES,
+            backoff: DEFAULT_BACKOFF,
+        }
+    }
+
+    /// Sets the number of lookups running at the same time (at least 1).
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Sets the number of retries of a transient failure.
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets the wait before the first retry, doubled on each retry up to 30
+    /// seconds.
+    pub fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Looks up the CEPs.
+    ///
+    /// # Arguments
+    ///
+    /// * `ceps` - The CEPs, with or without symbols. Repeated CEPs are
+    ///   looked up once.
+    ///
+    /// # Returns
+    ///
+    /// One result per distinct CEP, in the order of first appearance.
+    /// Invalid CEPs fail with `CepError::InvalidCep` without a lookup.
+    pub fn run<I, S>(&self, ceps: I) -> Vec<BatchResult>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut seen = HashSet::new();
+        let inputs: Vec<String> = ceps
+            .into_iter()
+            .map(|cep| cep.as_ref().to_string())
+            .filter(|cep| seen.insert(remove_symbols(cep)))
+            .collect();
+
+        let next = AtomicUsize::new(0);
+        let workers = self.concurrency.min(inputs.len());
+        let mut results: Vec<(usize, BatchResult)> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut done = Vec::new();
+                        loop {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(cep) = inputs.get(index) else {
+                                return done;
+                            };
+                            done.push((index, self.lookup(cep)));
+                        }
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().expect("CEP lookup thread panicked"))
+                .collect()
+        });
+
+        results.sort_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
+    /// Looks up one CEP, retrying transient failures.
+    fn lookup(&self, cep: &str) -> BatchResult {
+        let mut attempts = 0;
+        let result = loop {
+            let result = self.provider.get_address(cep);
+            match &result {
+                Err(CepError::InvalidCep(_)) => break result,
+                Err(error) if error.is_transient() && attempts < self.retries => {
+                    thread::sleep(self.backoff_for(attempts));
+                    attempts += 1;
+                }
+                _ => {
+                    attempts += 1;
+                    break result;
+                }
+            }
+        };
+
+        BatchResult {
+            cep: remove_symbols(cep),
+            result,
+            attempts,
+        }
+    }
+
+    /// The wait before retry number `retry`, counting from 0.
+    fn backoff_for(&self, retry: u32) -> Duration {
+        self.backoff
+            .checked_mul(1 << retry.min(16))
+            .map_or(MAX_BACKOFF, |backoff| backoff.min(MAX_BACKOFF))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::provider::tests::paulista;
+    use super::super::MockProvider;
+    use super::*;
+
+    /// A provider that fails a number of times before answering, and tracks
+    /// how many lookups run at the same time.
+    #[derive(Default)]
+    struct FlakyProvider {
+        failures: AtomicUsize,
+        error: Option<CepError>,
+        running: AtomicUsize,
+        max_running: AtomicUsize,
+        delay: Duration,
+    }
+
+    impl CepProvider for FlakyProvider {
+        fn name(&self) -> &str {
+            "Flaky"
+        }
+
+        fn fetch_address(&self, cep: &str) -> Result<Address, CepError> {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_running.fetch_max(running, Ordering::SeqCst);
+            thread::sleep(self.delay);
+            self.running.fetch_sub(1, Ordering::SeqCst);
+
+            let failing = self
+                .failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok();
+            match &self.error {
+                Some(error) if failing => Err(error.clone()),
+                _ => Ok(Address {
+                    cep: cep.to_string(),
+                    ..Default::default()
+                }),
+            }
+        }
+    }
+
+    #[test]
+    fn test_deduplicates_input() {
+        let provider = MockProvider::new().with_address(paulista());
+        let results = BatchLookup::new(&provider).run(vec![
+            "01310-200",
+            "99999999",
+            "01310200",
+            "01.310-200",
+            "abc",
+            "99999-999",
+        ]);
+
+        let ceps: Vec<&str> = results.iter().map(|r| r.cep.as_str()).collect();
+        assert_eq!(ceps, vec!["01310200", "99999999", "abc"]);
+        assert_eq!(results[0].result, Ok(paulista()));
+        assert_eq!(
+            results[1].result,
+            Err(CepError::NotFound("99999999".to_string()))
+        );
+        assert_eq!(
+            results[2].result,
+            Err(CepError::InvalidCep("abc".to_string()))
+        );
+        assert_eq!(results[2].attempts, 0);
+        assert_eq!(provider.calls(), 2);
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let results = BatchLookup::new(MockProvider::new()).run(Vec::<String>::new());
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_bounded_concurrency() {
+        let provider = FlakyProvider {
+            delay: Duration::from_millis(20),
+            ..Default::default()
+        };
+        let ceps: Vec<String> = (0..12).map(|n| format!("{:08}", n)).collect();
+
+        let results = BatchLookup::new(&provider).with_concurrency(3).run(&ceps);
+
+        assert_eq!(results.len(), 12);
+        assert!(results.iter().all(|r| r.result.is_ok()));
+        assert_eq!(results[5].cep, "00000005");
+        assert!(provider.max_running.load(Ordering::SeqCst) <= 3);
+        assert!(provider.max_running.load(Ordering::SeqCst) >= 2);
+    }
+
+    #[test]
+    fn test_retries_transient_failures() {
+        let provider = FlakyProvider {
+            failures: AtomicUsize::new(2),
+            error: Some(CepError::Timeout),
+            ..Default::default()
+        };
+        let batch = BatchLookup::new(&provider)
+            .with_concurrency(1)
+            .with_backoff(Duration::from_millis(1));
+
+        let results = batch.run(["01310200"]);
+        assert!(results[0].result.is_ok());
+        assert_eq!(results[0].attempts, 3);
+
+        // Retries exhausted: the last error is returned
+        provider.failures.store(5, Ordering::SeqCst);
+        let results = batch.with_retries(1).run(["01310200"]);
+        assert_eq!(results[0].result, Err(CepError::Timeout));
+        assert_eq!(results[0].attempts, 2);
+    }
+
+    #[test]
+    fn test_does_not_retry_permanent_failures() {
+        let provider = FlakyProvider {
+            failures: AtomicUsize::new(1),
+            error: Some(CepError::Decode("bad JSON".to_string())),
+            ..Default::default()
+        };
+
+        let results = BatchLookup::new(&provider).run(["01310200"]);
+        assert!(matches!(results[0].result, Err(CepError::Decode(_))));
+        assert_eq!(results[0].attempts, 1);
+    }
+
+    #[test]
+    fn test_backoff() {
+        let batch = BatchLookup::new(MockProvider::new()).with_backoff(Duration::from_secs(1));
+        assert_eq!(batch.backoff_for(0), Duration::from_secs(1));
+        assert_eq!(batch.backoff_for(2), Duration::from_secs(4));
+        assert_eq!(batch.backoff_for(10), MAX_BACKOFF);
+        assert_eq!(batch.backoff_for(u32::MAX), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let provider = RateLimitedProvider::new(
+            MockProvider::new().with_address(paulista()),
+            20,
+            Duration::from_secs(1),
+        );
+        let ceps = ["01310200", "01310201", "01310202", "01310203"];
+
+        let start = Instant::now();
+        let results = BatchLookup::new(&provider).with_concurrency(4).run(ceps);
+
+        // One lookup every 50 ms, even with 4 threads
+        assert!(start.elapsed() >= Duration::from_millis(150));
+        assert_eq!(results.len(), 4);
+        assert_eq!(provider.inner().calls(), 4);
+        assert_eq!(provider.name(), "Mock");
+    }
+}
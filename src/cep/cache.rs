@@ -0,0 +1,575 @@
+//! A caching wrapper around CEP providers, with an in-memory LRU and an
+//! optional on-disk store.
+
+use super::{is_valid, remove_symbols, Address, CepError, CepProvider};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Default number of CEPs kept in memory.
+const DEFAULT_CAPACITY: usize = 10_000;
+
+/// Default time to live of an address. CEPs rarely change.
+const DEFAULT_POSITIVE_TTL: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Default time to live of a CEP that was not found. New CEPs are created
+/// from time to time, so misses are kept for less time.
+const DEFAULT_NEGATIVE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+// TYPES
+// =====
+
+/// Statistics of a [`CachedProvider`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Lookups answered by the cache, from memory or disk.
+    pub hits: u64,
+    /// Hits that answered that the CEP does not exist.
+    pub negative_hits: u64,
+    /// Hits read from the on-disk store.
+    pub disk_hits: u64,
+    /// Lookups forwarded to the provider.
+    pub misses: u64,
+    /// Entries dropped from memory to stay within the capacity.
+    pub evictions: u64,
+    /// Entries currently in memory.
+    pub entries: usize,
+}
+
+impl CacheStats {
+    /// The share of lookups answered by the cache, from 0.0 to 1.0.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// A cached lookup result: the address, or `None` if the CEP was not found.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Entry {
+    /// When the result was stored, in milliseconds since the Unix epoch.
+    stored_at: u64,
+    address: Option<Address>,
+}
+
+/// A least recently used map of CEPs to entries.
+#[derive(Debug, Default)]
+struct Lru {
+    entries: HashMap<String, (Entry, u64)>,
+    order: BTreeMap<u64, String>,
+    tick: u64,
+}
+
+impl Lru {
+    fn get(&mut self, cep: &str) -> Option<Entry> {
+        let (entry, used) = self.entries.get_mut(cep)?;
+        self.order.remove(used);
+        self.tick += 1;
+        *used = self.tick;
+        self.order.insert(self.tick, cep.to_string());
+        Some(entry.clone())
+    }
+
+    /// Inserts an entry and returns the number of entries evicted.
+    fn insert(&mut self, cep: &str, entry: Entry, capacity: usize) -> u64 {
+        self.remove(cep);
+        self.tick += 1;
+        self.entries.insert(cep.to_string(), (entry, self.tick));
+        self.order.insert(self.tick, cep.to_string());
+
+        let mut evicted = 0;
+        while self.entries.len() > capacity {
+            let Some((_, oldest)) = self.order.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+            evicted += 1;
+        }
+        evicted
+    }
+
+    fn remove(&mut self, cep: &str) {
+        if let Some((_, used)) = self.entries.remove(cep) {
+            self.order.remove(&used);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+}
+
+/// A directory with one JSON file per cached CEP.
+#[derive(Debug)]
+struct DiskStore {
+    dir: PathBuf,
+}
+
+impl DiskStore {
+    /// The file of a CEP, or `None` if it is not 8 ASCII digits, so that no
+    /// input can name a path outside the cache directory.
+    fn path(&self, cep: &str) -> Option<PathBuf> {
+        if !is_valid(cep) {
+            return None;
+        }
+        Some(self.dir.join(format!("{}.json", cep)))
+    }
+
+    fn get(&self, cep: &str) -> Option<Entry> {
+        let content = fs::read(self.path(cep)?).ok()?;
+        serde_json::from_slice(&content).ok()
+    }
+
+    /// Writes to a temporary file first, so readers never see a partial
+    /// entry.
+    fn insert(&self, cep: &str, entry: &Entry) -> io::Result<()> {
+        let path = self
+            .path(cep)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid CEP"))?;
+        let temporary = self
+            .dir
+            .join(format!("{}.json.{}.tmp", cep, std::process::id()));
+        fs::write(&temporary, serde_json::to_vec(entry)?)?;
+        fs::rename(temporary, path)
+    }
+
+    fn remove(&self, cep: &str) {
+        if let Some(path) = self.path(cep) {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+// PROVIDER
+// ========
+
+/// A [`CepProvider`] that caches the CEP lookups of another provider.
+///
+/// Addresses are kept for the positive TTL (30 days by default) and CEPs that
+/// were not found for the negative TTL (1 day by default); other errors, such
+/// as timeouts, are never cached. The most recently used CEPs are kept in
+/// memory, and optionally in a directory so the cache survives restarts and
+/// can be shared between processes. The cache is best effort: a store that
+/// cannot be read or written is skipped. Address searches are not cached.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::cep::{Address, CachedProvider, CepProvider, MockProvider};
+///
+/// let provider = CachedProvider::new(MockProvider::new().with_address(Address {
+///     cep: "01310-200".to_string(),
+///     ..Default::default()
+/// }));
+///
+/// assert!(provider.get_address("01310-200").is_ok());
+/// assert!(provider.get_address("01310200").is_ok());
+///
+/// let stats = provider.stats();
+/// assert_eq!((stats.hits, stats.misses), (1, 1));
+/// assert_eq!(provider.inner().calls(), 1);
+/// ```
+#[derive(Debug)]
+pub struct CachedProvider<P> {
+    provider: P,
+    memory: Mutex<Lru>,
+    store: Option<DiskStore>,
+    capacity: usize,
+    positive_ttl: Duration,
+    negative_ttl: Duration,
+    hits: AtomicU64,
+    negative_hits: AtomicU64,
+    disk_hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+}
+
+impl<P> CachedProvider<P> {
+    /// Wraps a provider with an in-memory cache of 10,000 CEPs.
+    pub fn new(provider: P) -> Self {
+        CachedProvider {
+            provider,
+            memory: Mutex::new(Lru::default()),
+            store: None,
+            capacity: DEFAULT_CAPACITY,
+            positive_ttl: DEFAULT_POSITIVE_TTL,
+            negative_ttl: DEFAULT_NEGATIVE_TTL,
+            hits: AtomicU64::new(0),
+            negative_hits: AtomicU64::new(0),
+            disk_hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+        }
+    }
+
+    /// Sets the number of CEPs kept in memory. A capacity of zero keeps
+    /// nothing in memory, leaving only the on-disk store.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Sets how long addresses are kept.
+    pub fn with_positive_ttl(mut self, ttl: Duration) -> Self {
+        self.positive_ttl = ttl;
+        self
+    }
+
+    /// Sets how long CEPs that were not found are kept. A zero TTL disables
+    /// negative caching.
+    pub fn with_negative_ttl(mut self, ttl: Duration) -> Self {
+        self.negative_ttl = ttl;
+        self
+    }
+
+    /// Also stores the results in a directory, created if missing.
+    ///
+    /// # Errors
+    ///
+    /// When the directory cannot be created.
+    pub fn with_disk_store(mut self, dir: impl AsRef<Path>) -> io::Result<Self> {
+        fs::create_dir_all(dir.as_ref())?;
+        self.store = Some(DiskStore {
+            dir: dir.as_ref().to_path_buf(),
+        });
+        Ok(self)
+    }
+
+    /// The wrapped provider.
+    pub fn inner(&self) -> &P {
+        &self.provider
+    }
+
+    /// The statistics of the cache since it was created.
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            negative_hits: self.negative_hits.load(Ordering::Relaxed),
+            disk_hits: self.disk_hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            entries: self.memory().entries.len(),
+        }
+    }
+
+    /// Forgets a CEP, in memory and on disk.
+    pub fn invalidate(&self, cep: &str) {
+        let cep = remove_symbols(cep);
+        if !is_valid(&cep) {
+            return;
+        }
+        self.memory().remove(&cep);
+        if let Some(store) = &self.store {
+            store.remove(&cep);
+        }
+    }
+
+    /// Empties the in-memory cache. The on-disk store is kept.
+    pub fn clear(&self) {
+        self.memory().clear();
+    }
+
+    fn memory(&self) -> std::sync::MutexGuard<'_, Lru> {
+        // The LRU is always consistent between operations, so a panic of
+        // another thread does not corrupt it.
+        self.memory
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Whether an entry is still within its TTL.
+    fn is_fresh(&self, entry: &Entry, now: u64) -> bool {
+        let ttl = match entry.address {
+            Some(_) => self.positive_ttl,
+            None => self.negative_ttl,
+        };
+        now.saturating_sub(entry.stored_at) < ttl.as_millis() as u64
+    }
+
+    /// Looks up a clean CEP in the cache, counting the hit or miss.
+    pub(super) fn cached(&self, cep: &str) -> Option<Result<Address, CepError>> {
+        let now = now_millis();
+        let mut entry = self.memory().get(cep).filter(|e| self.is_fresh(e, now));
+
+        if entry.is_none() {
+            if let Some(store) = &self.store {
+                entry = store.get(cep).filter(|e| self.is_fresh(e, now));
+                if let Some(found) = &entry {
+                    self.disk_hits.fetch_add(1, Ordering::Relaxed);
+                    self.remember(cep, found.clone());
+                }
+            }
+        }
+
+        let Some(entry) = entry else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        };
+
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some(entry.address.ok_or_else(|| {
+            self.negative_hits.fetch_add(1, Ordering::Relaxed);
+            CepError::NotFound(cep.to_string())
+        }))
+    }
+
+    /// Stores the result of a lookup, if it can be cached.
+    pub(super) fn store(&self, cep: &str, result: &Result<Address, CepError>) {
+        let address = match result {
+            Ok(address) => Some(address.clone()),
+            Err(CepError::NotFound(_)) if !self.negative_ttl.is_zero() => None,
+            Err(_) => return,
+        };
+
+        let entry = Entry {
+            stored_at: now_millis(),
+            address,
+        };
+        if let Some(store) = &self.store {
+            let _ = store.insert(cep, &entry);
+        }
+        self.remember(cep, entry);
+    }
+
+    fn remember(&self, cep: &str, entry: Entry) {
+        let evicted = self.memory().insert(cep, entry, self.capacity);
+        self.evictions.fetch_add(evicted, Ordering::Relaxed);
+    }
+}
+
+impl<P: CepProvider> CepProvider for CachedProvider<P> {
+    fn name(&self) -> &str {
+        self.provider.name()
+    }
+
+    fn fetch_address(&self, cep: &str) -> Result<Address, CepError> {
+        if let Some(result) = self.cached(cep) {
+            return result;
+        }
+
+        let result = self.provider.fetch_address(cep);
+        self.store(cep, &result);
+        result
+    }
+
+    fn fetch_addresses(
+        &self,
+        federal_unit: &str,
+        city: &str,
+        street: &str,
+    ) -> Result<Vec<Address>, CepError> {
+        self.provider.fetch_addresses(federal_unit, city, street)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::provider::tests::paulista;
+    use super::super::MockProvider;
+    use super::*;
+    use std::thread;
+
+    /// A fresh directory under the system temporary directory.
+    fn temporary_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "brazilian_utils_cep_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_positive_and_negative_hits() {
+        let provider = CachedProvider::new(MockProvider::new().with_address(paulista()));
+
+        for _ in 0..3 {
+            assert_eq!(provider.get_address("01310-200"), Ok(paulista()));
+            assert_eq!(
+                provider.get_address("99999999"),
+                Err(CepError::NotFound("99999999".to_string()))
+            );
+        }
+
+        assert_eq!(provider.inner().calls(), 2);
+        let stats = provider.stats();
+        assert_eq!(stats.hits, 4);
+        assert_eq!(stats.negative_hits, 2);
+        assert_eq!(stats.misses, 2);
+        assert_eq!(stats.entries, 2);
+        assert!((stats.hit_rate() - 4.0 / 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_errors_are_not_cached() {
+        let provider = CachedProvider::new(MockProvider::unavailable());
+
+        assert!(provider.get_address("01310200").is_err());
+        assert!(provider.get_address("01310200").is_err());
+        assert_eq!(provider.inner().calls(), 2);
+        assert_eq!(provider.stats().entries, 0);
+
+        // Invalid input never reaches the cache
+        assert!(provider.get_address("123").is_err());
+        assert_eq!(provider.stats().misses, 2);
+    }
+
+    #[test]
+    fn test_ttl() {
+        let provider = CachedProvider::new(MockProvider::new().with_address(paulista()))
+            .with_positive_ttl(Duration::from_millis(50))
+            .with_negative_ttl(Duration::ZERO);
+
+        provider.get_address("01310200").unwrap();
+        provider.get_address("01310200").unwrap();
+        assert_eq!(provider.inner().calls(), 1);
+
+        thread::sleep(Duration::from_millis(80));
+        provider.get_address("01310200").unwrap();
+        assert_eq!(provider.inner().calls(), 2);
+
+        // Negative caching is disabled
+        assert!(provider.get_address("99999999").is_err());
+        assert!(provider.get_address("99999999").is_err());
+        assert_eq!(provider.inner().calls(), 4);
+    }
+
+    #[test]
+    fn test_lru_eviction() {
+        let second = Address {
+            cep: "20040-020".to_string(),
+            ..Default::default()
+        };
+        let third = Address {
+            cep: "30130-010".to_string(),
+            ..Default::default()
+        };
+        let mock = MockProvider::new()
+            .with_address(paulista())
+            .with_address(second)
+            .with_address(third);
+        let provider = CachedProvider::new(mock).with_capacity(2);
+
+        provider.get_address("01310200").unwrap();
+        provider.get_address("20040020").unwrap();
+        provider.get_address("01310200").unwrap(); // most recently used
+        provider.get_address("30130010").unwrap(); // evicts 20040020
+
+        assert_eq!(provider.stats().evictions, 1);
+        provider.get_address("01310200").unwrap();
+        assert_eq!(provider.inner().calls(), 3);
+        provider.get_address("20040020").unwrap();
+        assert_eq!(provider.inner().calls(), 4);
+    }
+
+    #[test]
+    fn test_disk_store() {
+        let dir = temporary_dir("disk_store");
+        let mock = || MockProvider::new().with_address(paulista());
+
+        let first = CachedProvider::new(mock()).with_disk_store(&dir).unwrap();
+        first.get_address("01310200").unwrap();
+        assert!(first.get_address("99999999").is_err());
+        assert!(dir.join("01310200.json").exists());
+
+        // A new cache over the same directory answers without the provider
+        let second = CachedProvider::new(mock()).with_disk_store(&dir).unwrap();
+        assert_eq!(second.get_address("01310-200"), Ok(paulista()));
+        assert!(second.get_address("99999999").is_err());
+        assert_eq!(second.inner().calls(), 0);
+        assert_eq!(second.stats().disk_hits, 2);
+
+        // Later hits come from memory
+        second.get_address("01310200").unwrap();
+        assert_eq!(second.stats().disk_hits, 2);
+
+        second.invalidate("01310-200");
+        assert!(!dir.join("01310200.json").exists());
+        second.get_address("01310200").unwrap();
+        assert_eq!(second.inner().calls(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_disk_store_rejects_invalid_ceps() {
+        let dir = temporary_dir("invalid");
+        let outside = temporary_dir("invalid_outside");
+        fs::create_dir_all(&outside).unwrap();
+        let victim = outside.join("victim.json");
+        fs::write(&victim, "{}").unwrap();
+
+        let provider = CachedProvider::new(MockProvider::new())
+            .with_disk_store(&dir)
+            .unwrap();
+        let store = provider.store.as_ref().unwrap();
+        let entry = Entry {
+            stored_at: 0,
+            address: None,
+        };
+
+        let escape = outside.join("victim");
+        let escape = escape.to_str().unwrap();
+        provider.invalidate(escape);
+        store.remove(escape);
+        assert!(victim.exists());
+        assert!(store.get(escape).is_none());
+        assert!(store.insert("../12345678", &entry).is_err());
+        assert!(store.insert("1234567", &entry).is_err());
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+
+        fs::remove_dir_all(&dir).unwrap();
+        fs::remove_dir_all(&outside).unwrap();
+    }
+
+    #[test]
+    fn test_clear_keeps_disk_store() {
+        let dir = temporary_dir("clear");
+        let provider = CachedProvider::new(MockProvider::new().with_address(paulista()))
+            .with_disk_store(&dir)
+            .unwrap();
+
+        provider.get_address("01310200").unwrap();
+        provider.clear();
+        assert_eq!(provider.stats().entries, 0);
+        provider.get_address("01310200").unwrap();
+        assert_eq!(provider.inner().calls(), 1);
+        assert_eq!(provider.stats().disk_hits, 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_search_is_not_cached() {
+        let provider = CachedProvider::new(MockProvider::new().with_address(paulista()));
+
+        provider
+            .get_addresses("SP", "São Paulo", "Paulista")
+            .unwrap();
+        provider
+            .get_addresses("SP", "São Paulo", "Paulista")
+            .unwrap();
+        assert_eq!(provider.inner().calls(), 2);
+        assert_eq!(provider.name(), "Mock");
+    }
+}
@@ -0,0 +1,1009 @@
+//! CEP lookup providers: ViaCEP, BrasilAPI, OpenCEP, an in-memory mock and a
+//! fallback chain.
+
+use super::{is_valid, normalize_string, remove_symbols, Address, CepError, VALID_UFS};
+use reqwest::blocking::Client;
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Default timeout of the HTTP providers.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+// TRAIT
+// =====
+
+/// A source of CEP (Postal Code) data.
+///
+/// Implementors only answer for CEPs that are already clean and valid (8
+/// digits, no symbols); [`get_address`](Self::get_address) and
+/// [`get_addresses`](Self::get_addresses) validate the input first.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::cep::{Address, CepError, CepProvider, MockProvider};
+///
+/// let provider = MockProvider::new().with_address(Address {
+///     cep: "01310-200".to_string(),
+///     localidade: "São Paulo".to_string(),
+///     uf: "SP".to_string(),
+///     ..Default::default()
+/// });
+///
+/// let address = provider.get_address("01310-200").unwrap();
+/// assert_eq!(address.localidade, "São Paulo");
+/// assert!(matches!(provider.get_address("99999999"), Err(CepError::NotFound(_))));
+/// ```
+pub trait CepProvider: Send + Sync {
+    /// The name of the provider, e.g. "ViaCEP".
+    fn name(&self) -> &str;
+
+    /// Fetches the address of a clean, valid CEP.
+    ///
+    /// # Errors
+    ///
+    /// * `CepError::NotFound` - When the provider does not know the CEP.
+    /// * Any other `CepError` when the provider could not be queried.
+    fn fetch_address(&self, cep: &str) -> Result<Address, CepError>;
+
+    /// Fetches the CEPs of an address. The UF is valid and uppercase.
+    ///
+    /// Providers that cannot search by address fail with
+    /// `CepError::Unsupported`.
+    fn fetch_addresses(
+        &self,
+        federal_unit: &str,
+        city: &str,
+        street: &str,
+    ) -> Result<Vec<Address>, CepError> {
+        let _ = (federal_unit, city, street);
+        Err(unsupported_search(self.name()))
+    }
+
+    /// Looks up the address of a CEP, with or without symbols.
+    ///
+    /// # Errors
+    ///
+    /// * `CepError::InvalidCep` - When the input CEP is invalid.
+    /// * `CepError::NotFound` - When the provider does not know the CEP.
+    /// * Any other `CepError` when the provider could not be queried.
+    fn get_address(&self, cep: &str) -> Result<Address, CepError> {
+        self.fetch_address(&clean_cep(cep)?)
+    }
+
+    /// Looks up the CEPs of an address.
+    ///
+    /// # Errors
+    ///
+    /// * `CepError::InvalidUf` - When the UF is invalid.
+    /// * `CepError::NotFound` - When no CEP matches the address.
+    /// * Any other `CepError` when the provider could not be queried.
+    fn get_addresses(
+        &self,
+        federal_unit: &str,
+        city: &str,
+        street: &str,
+    ) -> Result<Vec<Address>, CepError> {
+        self.fetch_addresses(&clean_federal_unit(federal_unit)?, city, street)
+    }
+}
+
+impl<P: CepProvider + ?Sized> CepProvider for Box<P> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn fetch_address(&self, cep: &str) -> Result<Address, CepError> {
+        (**self).fetch_address(cep)
+    }
+
+    fn fetch_addresses(
+        &self,
+        federal_unit: &str,
+        city: &str,
+        street: &str,
+    ) -> Result<Vec<Address>, CepError> {
+        (**self).fetch_addresses(federal_unit, city, street)
+    }
+}
+
+impl<P: CepProvider + ?Sized> CepProvider for &P {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn fetch_address(&self, cep: &str) -> Result<Address, CepError> {
+        (**self).fetch_address(cep)
+    }
+
+    fn fetch_addresses(
+        &self,
+        federal_unit: &str,
+        city: &str,
+        street: &str,
+    ) -> Result<Vec<Address>, CepError> {
+        (**self).fetch_addresses(federal_unit, city, street)
+    }
+}
+
+/// Removes the symbols of a CEP and checks that it is valid.
+pub(super) fn clean_cep(cep: &str) -> Result<String, CepError> {
+    let clean_cep = remove_symbols(cep);
+    if !is_valid(&clean_cep) {
+        return Err(CepError::InvalidCep(cep.to_string()));
+    }
+    Ok(clean_cep)
+}
+
+/// Uppercases a UF and checks that it is valid.
+pub(super) fn clean_federal_unit(federal_unit: &str) -> Result<String, CepError> {
+    let federal_unit_upper = federal_unit.to_uppercase();
+    if !VALID_UFS.contains(&federal_unit_upper.as_str()) {
+        return Err(CepError::InvalidUf(federal_unit.to_string()));
+    }
+    Ok(federal_unit_upper)
+}
+
+/// Describes a searched address, as held by `CepError::NotFound`.
+pub(super) fn searched_address(federal_unit: &str, city: &str, street: &str) -> String {
+    format!("{} - {} - {}", federal_unit, city, street)
+}
+
+/// Fails with `NotFound` when the provider has no result.
+pub(super) fn found<T>(
+    result: Option<T>,
+    searched: impl FnOnce() -> String,
+) -> Result<T, CepError> {
+    result.ok_or_else(|| CepError::NotFound(searched()))
+}
+
+/// The error of a provider without address search.
+pub(super) fn unsupported_search(provider: &str) -> CepError {
+    CepError::Unsupported(format!("{} does not support address search", provider))
+}
+
+// HTTP PROVIDERS
+// ==============
+
+/// The base URL and the HTTP clients shared by the web providers.
+///
+/// Clients are built on first use, so a provider used only asynchronously
+/// never builds the blocking client (which cannot be created inside an async
+/// runtime), and the other way around.
+#[derive(Debug, Clone)]
+pub(super) struct HttpSource {
+    base_url: String,
+    timeout: Duration,
+    client: OnceLock<Client>,
+    #[cfg(feature = "async")]
+    async_client: OnceLock<reqwest::Client>,
+}
+
+impl HttpSource {
+    fn new(base_url: &str, timeout: Duration) -> Self {
+        HttpSource {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            timeout,
+            client: OnceLock::new(),
+            #[cfg(feature = "async")]
+            async_client: OnceLock::new(),
+        }
+    }
+
+    fn with_timeout(self, timeout: Duration) -> Self {
+        Self::new(&self.base_url, timeout)
+    }
+
+    /// Sends a GET request and returns the status and body of the response.
+    fn get(&self, path: &str) -> Result<(StatusCode, Vec<u8>), CepError> {
+        let client = self.client.get_or_init(|| {
+            Client::builder()
+                .timeout(self.timeout)
+                .build()
+                .expect("the HTTP client can be built")
+        });
+
+        let response = client
+            .get(format!("{}{}", self.base_url, path))
+            .send()
+            .map_err(transport_error)?;
+        let status = response.status();
+        Ok((status, response.bytes().map_err(transport_error)?.to_vec()))
+    }
+
+    /// Sends a GET request without blocking and returns the status and body
+    /// of the response.
+    #[cfg(feature = "async")]
+    pub(super) async fn get_async(&self, path: &str) -> Result<(StatusCode, Vec<u8>), CepError> {
+        let client = self.async_client.get_or_init(|| {
+            reqwest::Client::builder()
+                .timeout(self.timeout)
+                .build()
+                .expect("the HTTP client can be built")
+        });
+
+        let response = client
+            .get(format!("{}{}", self.base_url, path))
+            .send()
+            .await
+            .map_err(transport_error)?;
+        let status = response.status();
+        let body = response.bytes().await.map_err(transport_error)?;
+        Ok((status, body.to_vec()))
+    }
+}
+
+/// Classifies an error of the HTTP client.
+fn transport_error(error: reqwest::Error) -> CepError {
+    if error.is_timeout() {
+        CepError::Timeout
+    } else {
+        CepError::Network(error.to_string())
+    }
+}
+
+/// Fails with the HTTP status of an unexpected response.
+fn unexpected_status(provider: &str, status: StatusCode) -> CepError {
+    match status {
+        StatusCode::TOO_MANY_REQUESTS => CepError::RateLimited,
+        status => CepError::Network(format!("{} returned HTTP {}", provider, status)),
+    }
+}
+
+/// Decodes a JSON response body.
+fn decode<T: DeserializeOwned>(body: &[u8]) -> Result<T, CepError> {
+    serde_json::from_slice(body).map_err(|error| CepError::Decode(error.to_string()))
+}
+
+/// The ViaCEP web service (<https://viacep.com.br/>), the default provider.
+///
+/// Supports CEP lookups and address search.
+///
+/// # Examples
+///
+/// ```no_run
+/// use brazilian_utils::cep::{CepProvider, ViaCep};
+/// use std::time::Duration;
+///
+/// let provider = ViaCep::new().with_timeout(Duration::from_secs(2));
+/// if let Ok(address) = provider.get_address("01310-200") {
+///     println!("{}", address.logradouro);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ViaCep {
+    pub(super) http: HttpSource,
+}
+
+impl ViaCep {
+    /// The public ViaCEP endpoint.
+    pub const BASE_URL: &'static str = "https://viacep.com.br";
+
+    /// Creates a provider querying the public ViaCEP endpoint.
+    pub fn new() -> Self {
+        Self::with_base_url(Self::BASE_URL)
+    }
+
+    /// Creates a provider querying another server with the ViaCEP API, such
+    /// as a local stand-in used in tests.
+    pub fn with_base_url(base_url: &str) -> Self {
+        ViaCep {
+            http: HttpSource::new(base_url, DEFAULT_TIMEOUT),
+        }
+    }
+
+    /// Sets the timeout of each request (10 seconds by default).
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        ViaCep {
+            http: self.http.with_timeout(timeout),
+        }
+    }
+
+    pub(super) fn address_path(cep: &str) -> String {
+        format!("/ws/{}/json/", cep)
+    }
+
+    pub(super) fn addresses_path(federal_unit: &str, city: &str, street: &str) -> String {
+        format!(
+            "/ws/{}/{}/{}/json/",
+            federal_unit,
+            normalize_string(city),
+            normalize_string(street)
+        )
+    }
+
+    pub(super) fn decode_address(
+        status: StatusCode,
+        body: &[u8],
+    ) -> Result<Option<Address>, CepError> {
+        if !status.is_success() {
+            return Err(unexpected_status("ViaCEP", status));
+        }
+
+        let json: serde_json::Value = decode(body)?;
+        if json.get("erro").is_some() {
+            return Ok(None);
+        }
+
+        serde_json::from_value(json)
+            .map(Some)
+            .map_err(|error| CepError::Decode(error.to_string()))
+    }
+
+    pub(super) fn decode_addresses(
+        status: StatusCode,
+        body: &[u8],
+    ) -> Result<Option<Vec<Address>>, CepError> {
+        if !status.is_success() {
+            return Err(unexpected_status("ViaCEP", status));
+        }
+
+        let addresses: Vec<Address> = decode(body)?;
+        Ok((!addresses.is_empty()).then_some(addresses))
+    }
+}
+
+impl Default for ViaCep {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CepProvider for ViaCep {
+    fn name(&self) -> &str {
+        "ViaCEP"
+    }
+
+    fn fetch_address(&self, cep: &str) -> Result<Address, CepError> {
+        let (status, body) = self.http.get(&Self::address_path(cep))?;
+        found(Self::decode_address(status, &body)?, || cep.to_string())
+    }
+
+    fn fetch_addresses(
+        &self,
+        federal_unit: &str,
+        city: &str,
+        street: &str,
+    ) -> Result<Vec<Address>, CepError> {
+        let path = Self::addresses_path(federal_unit, city, street);
+        let (status, body) = self.http.get(&path)?;
+        found(Self::decode_addresses(status, &body)?, || {
+            searched_address(federal_unit, city, street)
+        })
+    }
+}
+
+/// The BrasilAPI CEP service (<https://brasilapi.com.br/>).
+///
+/// BrasilAPI does not return the IBGE, GIA, DDD and SIAFI codes, which are
+/// left empty, and does not support address search.
+#[derive(Debug, Clone)]
+pub struct BrasilApi {
+    pub(super) http: HttpSource,
+}
+
+impl BrasilApi {
+    /// The public BrasilAPI endpoint.
+    pub const BASE_URL: &'static str = "https://brasilapi.com.br";
+
+    /// Creates a provider querying the public BrasilAPI endpoint.
+    pub fn new() -> Self {
+        Self::with_base_url(Self::BASE_URL)
+    }
+
+    /// Creates a provider querying another server with the BrasilAPI API.
+    pub fn with_base_url(base_url: &str) -> Self {
+        BrasilApi {
+            http: HttpSource::new(base_url, DEFAULT_TIMEOUT),
+        }
+    }
+
+    /// Sets the timeout of each request (10 seconds by default).
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        BrasilApi {
+            http: self.http.with_timeout(timeout),
+        }
+    }
+
+    pub(super) fn address_path(cep: &str) -> String {
+        format!("/api/cep/v1/{}", cep)
+    }
+
+    pub(super) fn decode_address(
+        status: StatusCode,
+        body: &[u8],
+    ) -> Result<Option<Address>, CepError> {
+        match status {
+            StatusCode::NOT_FOUND => Ok(None),
+            status if status.is_success() => {
+                let address: BrasilApiAddress = decode(body)?;
+                Ok(Some(address.into()))
+            }
+            status => Err(unexpected_status("BrasilAPI", status)),
+        }
+    }
+}
+
+impl Default for BrasilApi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An address as returned by BrasilAPI.
+#[derive(Debug, Deserialize)]
+struct BrasilApiAddress {
+    cep: String,
+    #[serde(default)]
+    state: String,
+    #[serde(default)]
+    city: String,
+    #[serde(default)]
+    neighborhood: String,
+    #[serde(default)]
+    street: String,
+}
+
+impl From<BrasilApiAddress> for Address {
+    fn from(address: BrasilApiAddress) -> Self {
+        let cep = remove_symbols(&address.cep);
+        Address {
+            cep: super::format_cep(&cep).unwrap_or(cep),
+            logradouro: address.street,
+            bairro: address.neighborhood,
+            localidade: address.city,
+            uf: address.state,
+            ..Default::default()
+        }
+    }
+}
+
+impl CepProvider for BrasilApi {
+    fn name(&self) -> &str {
+        "BrasilAPI"
+    }
+
+    fn fetch_address(&self, cep: &str) -> Result<Address, CepError> {
+        let (status, body) = self.http.get(&Self::address_path(cep))?;
+        found(Self::decode_address(status, &body)?, || cep.to_string())
+    }
+}
+
+/// The OpenCEP service (<https://opencep.com/>), with ViaCEP-compatible data.
+///
+/// Does not support address search.
+#[derive(Debug, Clone)]
+pub struct OpenCep {
+    pub(super) http: HttpSource,
+}
+
+impl OpenCep {
+    /// The public OpenCEP endpoint.
+    pub const BASE_URL: &'static str = "https://opencep.com";
+
+    /// Creates a provider querying the public OpenCEP endpoint.
+    pub fn new() -> Self {
+        Self::with_base_url(Self::BASE_URL)
+    }
+
+    /// Creates a provider querying another server with the OpenCEP API.
+    pub fn with_base_url(base_url: &str) -> Self {
+        OpenCep {
+            http: HttpSource::new(base_url, DEFAULT_TIMEOUT),
+        }
+    }
+
+    /// Sets the timeout of each request (10 seconds by default).
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        OpenCep {
+            http: self.http.with_timeout(timeout),
+        }
+    }
+
+    pub(super) fn address_path(cep: &str) -> String {
+        format!("/v1/{}", cep)
+    }
+
+    pub(super) fn decode_address(
+        status: StatusCode,
+        body: &[u8],
+    ) -> Result<Option<Address>, CepError> {
+        match status {
+            StatusCode::NOT_FOUND => Ok(None),
+            status if status.is_success() => decode(body).map(Some),
+            status => Err(unexpected_status("OpenCEP", status)),
+        }
+    }
+}
+
+impl Default for OpenCep {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CepProvider for OpenCep {
+    fn name(&self) -> &str {
+        "OpenCEP"
+    }
+
+    fn fetch_address(&self, cep: &str) -> Result<Address, CepError> {
+        let (status, body) = self.http.get(&Self::address_path(cep))?;
+        found(Self::decode_address(status, &body)?, || cep.to_string())
+    }
+}
+
+// OTHER PROVIDERS
+// ===============
+
+/// An in-memory provider, for tests and for fixed data sets.
+///
+/// The address search matches the UF exactly and the city and street as
+/// case- and accent-insensitive substrings. A provider created with
+/// [`unavailable`](Self::unavailable) fails every lookup with
+/// `CepError::Network`, to simulate an outage.
+#[derive(Debug, Default)]
+pub struct MockProvider {
+    addresses: HashMap<String, Address>,
+    unavailable: bool,
+    calls: AtomicUsize,
+}
+
+impl MockProvider {
+    /// Creates an empty provider.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a provider that fails every lookup.
+    pub fn unavailable() -> Self {
+        MockProvider {
+            unavailable: true,
+            ..Self::default()
+        }
+    }
+
+    /// Adds an address, keyed by its CEP.
+    pub fn with_address(mut self, address: Address) -> Self {
+        self.addresses.insert(remove_symbols(&address.cep), address);
+        self
+    }
+
+    /// The number of lookups made so far.
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::Relaxed)
+    }
+
+    /// Counts a lookup and fails if the provider is unavailable.
+    fn call(&self) -> Result<(), CepError> {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        if self.unavailable {
+            return Err(CepError::Network(
+                "mock provider is unavailable".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl CepProvider for MockProvider {
+    fn name(&self) -> &str {
+        "Mock"
+    }
+
+    fn fetch_address(&self, cep: &str) -> Result<Address, CepError> {
+        self.call()?;
+        found(self.addresses.get(cep).cloned(), || cep.to_string())
+    }
+
+    fn fetch_addresses(
+        &self,
+        federal_unit: &str,
+        city: &str,
+        street: &str,
+    ) -> Result<Vec<Address>, CepError> {
+        self.call()?;
+
+        let fold = |text: &str| normalize_string(text).to_lowercase();
+        let (folded_city, folded_street) = (fold(city), fold(street));
+        let mut addresses: Vec<Address> = self
+            .addresses
+            .values()
+            .filter(|address| {
+                address.uf == federal_unit
+                    && fold(&address.localidade).contains(&folded_city)
+                    && fold(&address.logradouro).contains(&folded_street)
+            })
+            .cloned()
+            .collect();
+        addresses.sort_by(|a, b| a.cep.cmp(&b.cep));
+
+        found((!addresses.is_empty()).then_some(addresses), || {
+            searched_address(federal_unit, city, street)
+        })
+    }
+}
+
+/// A chain of providers tried in order.
+///
+/// A provider that fails is skipped, and so is one that does not know the
+/// CEP, since the data sets of the services differ. The lookup fails with
+/// `CepError::NotFound` if no provider has the CEP and at least one
+/// answered; if every provider failed, the last error is returned.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::cep::{Address, CepProvider, FallbackProvider, MockProvider};
+///
+/// let provider = FallbackProvider::new()
+///     .with(MockProvider::unavailable())
+///     .with(MockProvider::new().with_address(Address {
+///         cep: "01310-200".to_string(),
+///         ..Default::default()
+///     }));
+///
+/// assert!(provider.get_address("01310200").is_ok());
+/// ```
+#[derive(Default)]
+pub struct FallbackProvider {
+    providers: Vec<Box<dyn CepProvider>>,
+}
+
+impl FallbackProvider {
+    /// Creates an empty chain. Lookups on an empty chain fail with
+    /// `CepError::Unsupported`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a provider to the chain.
+    pub fn with(mut self, provider: impl CepProvider + 'static) -> Self {
+        self.providers.push(Box::new(provider));
+        self
+    }
+
+    /// The providers of the chain, in order.
+    pub fn providers(&self) -> &[Box<dyn CepProvider>] {
+        &self.providers
+    }
+
+    /// Tries every provider until one returns a result.
+    fn first_found<T>(
+        &self,
+        lookup: impl Fn(&dyn CepProvider) -> Result<T, CepError>,
+    ) -> Result<T, CepError> {
+        let mut not_found = None;
+        let mut last_error = None;
+
+        for provider in &self.providers {
+            match lookup(provider.as_ref()) {
+                Ok(found) => return Ok(found),
+                Err(error @ CepError::NotFound(_)) => not_found = Some(error),
+                Err(error) => last_error = Some(error),
+            }
+        }
+
+        Err(not_found
+            .or(last_error)
+            .unwrap_or_else(|| CepError::Unsupported("no CEP provider configured".to_string())))
+    }
+}
+
+impl std::fmt::Debug for FallbackProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let names: Vec<&str> = self.providers.iter().map(|p| p.name()).collect();
+        f.debug_struct("FallbackProvider")
+            .field("providers", &names)
+            .finish()
+    }
+}
+
+impl CepProvider for FallbackProvider {
+    fn name(&self) -> &str {
+        "Fallback"
+    }
+
+    fn fetch_address(&self, cep: &str) -> Result<Address, CepError> {
+        self.first_found(|provider| provider.fetch_address(cep))
+    }
+
+    fn fetch_addresses(
+        &self,
+        federal_unit: &str,
+        city: &str,
+        street: &str,
+    ) -> Result<Vec<Address>, CepError> {
+        self.first_found(|provider| provider.fetch_addresses(federal_unit, city, street))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    /// Serves canned `(status, body)` responses, one per connection, from a
+    /// local stand-in server. Returns its base URL and the requested paths.
+    pub(crate) fn serve<B>(responses: Vec<(u16, B)>) -> (String, Arc<Mutex<Vec<String>>>)
+    where
+        B: Into<String> + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let paths = Arc::new(Mutex::new(Vec::new()));
+        let requested = Arc::clone(&paths);
+
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let body = body.into();
+                let Ok((mut stream, _)) = listener.accept() else {
+                    return;
+                };
+                let mut buffer = [0; 4096];
+                let read = stream.read(&mut buffer).unwrap_or(0);
+                let request = String::from_utf8_lossy(&buffer[..read]);
+                let path = request.split_whitespace().nth(1).unwrap_or_default();
+                requested.lock().unwrap().push(path.to_string());
+
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {} Stand-in\r\nContent-Type: application/json\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+            }
+        });
+
+        (base_url, paths)
+    }
+
+    pub(crate) const VIACEP_PAULISTA: &str = r#"{
+        "cep": "01310-200",
+        "logradouro": "Avenida Paulista",
+        "complemento": "de 1047 a 1865 - lado ímpar",
+        "unidade": "",
+        "bairro": "Bela Vista",
+        "localidade": "São Paulo",
+        "uf": "SP",
+        "estado": "São Paulo",
+        "regiao": "Sudeste",
+        "ibge": "3550308",
+        "gia": "1004",
+        "ddd": "11",
+        "siafi": "7107"
+    }"#;
+
+    pub(crate) fn paulista() -> Address {
+        serde_json::from_str(VIACEP_PAULISTA).unwrap()
+    }
+
+    #[test]
+    fn test_viacep_address() {
+        let (base_url, paths) = serve(vec![(200, VIACEP_PAULISTA)]);
+        let provider = ViaCep::with_base_url(&base_url);
+
+        let address = provider.get_address("01310-200").unwrap();
+        assert_eq!(address, paulista());
+        assert_eq!(address.ibge, "3550308");
+        assert_eq!(*paths.lock().unwrap(), vec!["/ws/01310200/json/"]);
+    }
+
+    #[test]
+    fn test_viacep_not_found() {
+        let (base_url, _) = serve(vec![(200, r#"{"erro": "true"}"#)]);
+        let provider = ViaCep::with_base_url(&base_url);
+
+        assert_eq!(
+            provider.get_address("99999999"),
+            Err(CepError::NotFound("99999999".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_viacep_server_error() {
+        let (base_url, _) = serve(vec![(500, "")]);
+        let provider = ViaCep::with_base_url(&base_url);
+
+        let error = provider.get_address("01310200").unwrap_err();
+        assert!(matches!(error, CepError::Network(message) if message.contains("500")));
+    }
+
+    #[test]
+    fn test_error_kinds() {
+        let (base_url, _) = serve(vec![(429, ""), (200, "<html>")]);
+        let provider = ViaCep::with_base_url(&base_url);
+
+        assert_eq!(provider.get_address("01310200"), Err(CepError::RateLimited));
+        assert!(matches!(
+            provider.get_address("01310200"),
+            Err(CepError::Decode(_))
+        ));
+    }
+
+    #[test]
+    fn test_viacep_search() {
+        let body = format!("[{}]", VIACEP_PAULISTA);
+        let (base_url, paths) = serve(vec![(200, body), (200, "[]".to_string())]);
+        let provider = ViaCep::with_base_url(&format!("{}/", base_url));
+
+        let addresses = provider
+            .get_addresses("sp", "São Paulo", "Avenida Paulista")
+            .unwrap();
+        assert_eq!(addresses, vec![paulista()]);
+        assert_eq!(
+            provider.get_addresses("SP", "São Paulo", "Nenhuma"),
+            Err(CepError::NotFound("SP - São Paulo - Nenhuma".to_string()))
+        );
+        assert_eq!(
+            paths.lock().unwrap()[0],
+            "/ws/SP/Sao%20Paulo/Avenida%20Paulista/json/"
+        );
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let provider = MockProvider::new();
+
+        assert_eq!(
+            provider.get_address("1234"),
+            Err(CepError::InvalidCep("1234".to_string()))
+        );
+        assert_eq!(
+            provider.get_addresses("xx", "City", "Street"),
+            Err(CepError::InvalidUf("xx".to_string()))
+        );
+        assert_eq!(provider.calls(), 0);
+    }
+
+    #[test]
+    fn test_brasilapi_address() {
+        let body = r#"{
+            "cep": "01310200",
+            "state": "SP",
+            "city": "São Paulo",
+            "neighborhood": "Bela Vista",
+            "street": "Avenida Paulista",
+            "service": "open-cep"
+        }"#;
+        let (base_url, paths) = serve(vec![(200, body), (404, r#"{"name": "CepPromiseError"}"#)]);
+        let provider = BrasilApi::with_base_url(&base_url);
+
+        let address = provider.get_address("01310200").unwrap();
+        assert_eq!(address.cep, "01310-200");
+        assert_eq!(address.logradouro, "Avenida Paulista");
+        assert_eq!(address.localidade, "São Paulo");
+        assert_eq!(address.ibge, "");
+        assert!(matches!(
+            provider.get_address("99999999"),
+            Err(CepError::NotFound(_))
+        ));
+        assert_eq!(paths.lock().unwrap()[0], "/api/cep/v1/01310200");
+
+        assert!(matches!(
+            provider.get_addresses("SP", "São Paulo", "Paulista"),
+            Err(CepError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_opencep_address() {
+        let body = r#"{
+            "cep": "01310-200",
+            "logradouro": "Avenida Paulista",
+            "complemento": "de 1047 a 1865 - lado ímpar",
+            "bairro": "Bela Vista",
+            "localidade": "São Paulo",
+            "uf": "SP",
+            "ibge": "3550308"
+        }"#;
+        let (base_url, paths) = serve(vec![(200, body), (404, "{}")]);
+        let provider = OpenCep::with_base_url(&base_url);
+
+        let address = provider.get_address("01310200").unwrap();
+        assert_eq!(address.ibge, "3550308");
+        assert_eq!(address.ddd, "");
+        assert!(matches!(
+            provider.get_address("99999999"),
+            Err(CepError::NotFound(_))
+        ));
+        assert_eq!(paths.lock().unwrap()[0], "/v1/01310200");
+    }
+
+    #[test]
+    fn test_timeout() {
+        // A server that accepts the connection but never answers
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let provider = ViaCep::with_base_url(&base_url).with_timeout(Duration::from_millis(200));
+
+        assert_eq!(provider.get_address("01310200"), Err(CepError::Timeout));
+        drop(listener);
+    }
+
+    #[test]
+    fn test_mock_provider() {
+        let provider = MockProvider::new().with_address(paulista());
+
+        assert_eq!(provider.get_address("01310200"), Ok(paulista()));
+        assert!(provider.get_address("01310-201").is_err());
+        assert_eq!(
+            provider.get_addresses("SP", "sao paulo", "PAULISTA"),
+            Ok(vec![paulista()])
+        );
+        assert!(provider.get_addresses("RJ", "", "").is_err());
+        assert_eq!(provider.calls(), 4);
+
+        let provider = MockProvider::unavailable();
+        assert!(matches!(
+            provider.get_address("01310200"),
+            Err(CepError::Network(_))
+        ));
+        assert_eq!(provider.calls(), 1);
+    }
+
+    #[test]
+    fn test_fallback_order() {
+        let provider = FallbackProvider::new()
+            .with(MockProvider::unavailable())
+            .with(MockProvider::new())
+            .with(MockProvider::new().with_address(paulista()));
+
+        assert_eq!(provider.get_address("01310200"), Ok(paulista()));
+        assert_eq!(
+            provider.get_address("99999999"),
+            Err(CepError::NotFound("99999999".to_string()))
+        );
+        assert_eq!(provider.providers().len(), 3);
+    }
+
+    #[test]
+    fn test_fallback_stops_at_first_found() {
+        let first = MockProvider::new().with_address(paulista());
+        let provider = FallbackProvider::new()
+            .with(first)
+            .with(MockProvider::unavailable());
+
+        assert!(provider.get_address("01310200").is_ok());
+        assert_eq!(
+            format!("{:?}", provider),
+            r#"FallbackProvider { providers: ["Mock", "Mock"] }"#
+        );
+    }
+
+    #[test]
+    fn test_fallback_all_failed() {
+        let provider = FallbackProvider::new()
+            .with(MockProvider::unavailable())
+            .with(MockProvider::unavailable());
+        assert!(matches!(
+            provider.get_address("01310200"),
+            Err(CepError::Network(_))
+        ));
+
+        assert!(matches!(
+            FallbackProvider::new().get_address("01310200"),
+            Err(CepError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_fallback_over_http() {
+        let (base_url, _) = serve(vec![(503, "")]);
+        let provider = FallbackProvider::new()
+            .with(ViaCep::with_base_url(&base_url))
+            .with(MockProvider::new().with_address(paulista()));
+
+        assert_eq!(provider.get_address("01310-200"), Ok(paulista()));
+    }
+}
@@ -0,0 +1,430 @@
+//! Procedural deadline (prazo processual) calculation under the Code of Civil Procedure (CPC).
+
+use crate::business_days::BusinessCalendar;
+use crate::holiday_calendar::HolidayCalendar;
+use crate::legal_process::parse;
+use crate::time_zone::{from_local, Tz};
+use chrono::{DateTime, Days, NaiveDate, Utc};
+
+// TYPES
+// =====
+
+/// Calculator of procedural deadlines for a court.
+///
+/// Deadlines are counted in business days (CPC, art. 219), excluding the day
+/// of the start and including the last day (art. 224). Weekends, the
+/// holidays of the court's state, the forensic recess from December 20 to
+/// January 20 (art. 220) and any suspension declared by the court do not
+/// count.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::court_deadline::DeadlineCalculator;
+/// use chrono::NaiveDate;
+///
+/// // TJSP, 15 business days from an intimation on Friday, June 28, 2024
+/// let calculator = DeadlineCalculator::for_legal_process("1234567-21.2024.8.26.0000").unwrap();
+/// let intimation = NaiveDate::from_ymd_opt(2024, 6, 28).unwrap();
+///
+/// // July 9 is a holiday in São Paulo
+/// assert_eq!(
+///     calculator.deadline(intimation, 15),
+///     NaiveDate::from_ymd_opt(2024, 7, 22)
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct DeadlineCalculator {
+    calendar: BusinessCalendar,
+}
+
+impl DeadlineCalculator {
+    /// Creates a calculator for a court in the given state (UF).
+    ///
+    /// # Arguments
+    ///
+    /// * `uf` - The state whose holidays are observed by the court. If not
+    ///   provided, only national holidays are considered.
+    ///
+    /// # Returns
+    ///
+    /// The calculator, or `None` if the UF is invalid.
+    pub fn new(uf: Option<&str>) -> Option<Self> {
+        Some(DeadlineCalculator {
+            calendar: BusinessCalendar::new(uf)?.with_forensic_recess(),
+        })
+    }
+
+    /// Creates a calculator for the court of a legal process.
+    ///
+    /// The court is identified by the J and TR segments of the CNJ number
+    /// (see [`LegalProcess::uf`](crate::legal_process::LegalProcess::uf)).
+    ///
+    /// # Arguments
+    ///
+    /// * `legal_process_id` - The legal process ID (with or without symbols).
+    ///
+    /// # Returns
+    ///
+    /// The calculator, or `None` if the legal process ID is invalid or its
+    /// court is unknown.
+    pub fn for_legal_process(legal_process_id: &str) -> Option<Self> {
+        let uf = parse(legal_process_id)?.uf()?;
+        Self::new(Some(uf))
+    }
+
+    /// Creates a calculator for a court with its own holiday calendar, e.g.
+    /// one loaded from the court's yearly act on holidays.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::court_deadline::DeadlineCalculator;
+    /// use brazilian_utils::holiday_calendar::{HolidayCalendar, HolidayRule};
+    /// use chrono::NaiveDate;
+    ///
+    /// // Dia da Justiça (December 8) in the federal courts
+    /// let holidays = HolidayCalendar::new(None)
+    ///     .unwrap()
+    ///     .with_rule(HolidayRule::fixed("Dia da Justiça", 12, 8).legal_basis("Lei nº 5.010/1966"));
+    /// let calculator = DeadlineCalculator::from_holidays(holidays);
+    ///
+    /// assert_eq!(
+    ///     calculator.deadline(NaiveDate::from_ymd_opt(2025, 12, 5).unwrap(), 1),
+    ///     NaiveDate::from_ymd_opt(2025, 12, 9)
+    /// );
+    /// ```
+    pub fn from_holidays(holidays: HolidayCalendar) -> Self {
+        DeadlineCalculator {
+            calendar: BusinessCalendar::from_holidays(holidays).with_forensic_recess(),
+        }
+    }
+
+    /// Adds a suspension of deadlines declared by the court, from `from` to
+    /// `to` (inclusive).
+    pub fn with_suspension(self, from: NaiveDate, to: NaiveDate) -> Self {
+        DeadlineCalculator {
+            calendar: self.calendar.with_suspension(from, to),
+        }
+    }
+
+    /// Counts the deadlines in the given time zone instead of the one of
+    /// the court's state.
+    pub fn with_time_zone(self, time_zone: Tz) -> Self {
+        DeadlineCalculator {
+            calendar: self.calendar.with_time_zone(time_zone),
+        }
+    }
+
+    /// The business-day calendar of the court.
+    pub fn calendar(&self) -> &BusinessCalendar {
+        &self.calendar
+    }
+
+    /// Computes the date of publication of an act made available in the
+    /// electronic journal (DJe): the first business day after the
+    /// availability (Lei nº 11.419/2006, art. 4º, § 3º).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::court_deadline::DeadlineCalculator;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calculator = DeadlineCalculator::new(None).unwrap();
+    /// let availability = NaiveDate::from_ymd_opt(2024, 3, 8).unwrap();
+    ///
+    /// assert_eq!(
+    ///     calculator.publication_date(availability),
+    ///     NaiveDate::from_ymd_opt(2024, 3, 11)
+    /// );
+    /// ```
+    pub fn publication_date(&self, availability: NaiveDate) -> Option<NaiveDate> {
+        self.calendar.next_business_day(availability)
+    }
+
+    /// Computes the last day of a deadline.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The date of the publication or intimation. It is not
+    ///   counted: counting starts on the first business day after it. An
+    ///   intimation on a weekend, holiday or during the recess is moved to
+    ///   the next business day, and counting starts on the day after that
+    ///   (CPC, art. 224, § 1º).
+    /// * `days` - The length of the deadline in business days.
+    ///
+    /// # Returns
+    ///
+    /// The last day to perform the act, or `None` if `days` is zero or the
+    /// date is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::court_deadline::DeadlineCalculator;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calculator = DeadlineCalculator::new(None).unwrap();
+    ///
+    /// // Intimation on Saturday, June 8, 2024: moved to Monday, so the
+    /// // first day counted is Tuesday
+    /// let intimation = NaiveDate::from_ymd_opt(2024, 6, 8).unwrap();
+    /// assert_eq!(
+    ///     calculator.deadline(intimation, 1),
+    ///     NaiveDate::from_ymd_opt(2024, 6, 11)
+    /// );
+    /// ```
+    pub fn deadline(&self, start: NaiveDate, days: u32) -> Option<NaiveDate> {
+        if days == 0 {
+            return None;
+        }
+
+        let start = if self.calendar.is_business_day(start) {
+            start
+        } else {
+            self.calendar.next_business_day(start)?
+        };
+
+        self.calendar.add_business_days(start, days as i64)
+    }
+
+    /// Computes the instant a deadline expires: electronic filings are
+    /// timely until 24:00 of the last day in the court's local time (Lei nº
+    /// 11.419/2006, art. 3º, parágrafo único).
+    ///
+    /// # Returns
+    ///
+    /// The first instant after the deadline, or `None` if `days` is zero or
+    /// the date is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::court_deadline::DeadlineCalculator;
+    /// use chrono::{NaiveDate, TimeZone, Utc};
+    ///
+    /// let calculator = DeadlineCalculator::new(Some("SP")).unwrap();
+    /// let intimation = NaiveDate::from_ymd_opt(2024, 6, 28).unwrap();
+    ///
+    /// // The deadline ends on July 22, at midnight in Brasília time
+    /// assert_eq!(
+    ///     calculator.deadline_end(intimation, 15),
+    ///     Some(Utc.with_ymd_and_hms(2024, 7, 23, 3, 0, 0).unwrap())
+    /// );
+    /// ```
+    pub fn deadline_end(&self, start: NaiveDate, days: u32) -> Option<DateTime<Utc>> {
+        let next_day = self.deadline(start, days)?.checked_add_days(Days::new(1))?;
+        let end = from_local(next_day.and_hms_opt(0, 0, 0)?, self.calendar.time_zone())?;
+        Some(end.with_timezone(&Utc))
+    }
+
+    /// Checks whether a filing made at the given instant meets a deadline,
+    /// comparing the local date of the filing in the court's time zone with
+    /// the last day of the deadline.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The date of the publication or intimation.
+    /// * `days` - The length of the deadline in business days.
+    /// * `filed_at` - The instant the filing was received.
+    ///
+    /// # Returns
+    ///
+    /// `Some(true)` if the filing is timely, `Some(false)` if it is late, or
+    /// `None` if `days` is zero or the date is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::court_deadline::DeadlineCalculator;
+    /// use chrono::{NaiveDate, TimeZone, Utc};
+    ///
+    /// let intimation = NaiveDate::from_ymd_opt(2024, 6, 28).unwrap();
+    /// let filed_at = Utc.with_ymd_and_hms(2024, 7, 23, 2, 30, 0).unwrap();
+    ///
+    /// // 23:30 of July 22 in São Paulo
+    /// let calculator = DeadlineCalculator::new(Some("SP")).unwrap();
+    /// assert_eq!(calculator.is_timely(intimation, 15, filed_at), Some(true));
+    ///
+    /// // 00:30 of July 23 in Fernando de Noronha
+    /// let calculator = DeadlineCalculator::new(Some("PE"))
+    ///     .unwrap()
+    ///     .with_time_zone(brazilian_utils::time_zone::Tz::America__Noronha);
+    /// assert_eq!(calculator.is_timely(intimation, 15, filed_at), Some(false));
+    /// ```
+    pub fn is_timely(&self, start: NaiveDate, days: u32, filed_at: DateTime<Utc>) -> Option<bool> {
+        let deadline = self.deadline(start, days)?;
+        Some(self.calendar.local_date(filed_at) <= deadline)
+    }
+}
+
+// OPERATIONS
+// ==========
+
+/// Computes the last day of a procedural deadline for a legal process.
+///
+/// See [`DeadlineCalculator`] for the counting rules. Use the calculator
+/// directly to add court suspensions.
+///
+/// # Arguments
+///
+/// * `start` - The date of the publication or intimation.
+/// * `days` - The length of the deadline in business days.
+/// * `legal_process_id` - The legal process ID, used to find the court's state.
+///
+/// # Returns
+///
+/// The last day to perform the act, or `None` if the legal process ID is
+/// invalid, its court is unknown or `days` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::court_deadline::deadline;
+/// use chrono::NaiveDate;
+///
+/// // Intimation on December 18: the recess suspends the count until January 21
+/// let start = NaiveDate::from_ymd_opt(2024, 12, 18).unwrap();
+/// assert_eq!(
+///     deadline(start, 5, "1234567-21.2024.8.26.0000"),
+///     NaiveDate::from_ymd_opt(2025, 1, 24)
+/// );
+/// ```
+pub fn deadline(start: NaiveDate, days: u32, legal_process_id: &str) -> Option<NaiveDate> {
+    DeadlineCalculator::for_legal_process(legal_process_id)?.deadline(start, days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TJSP: &str = "1234567-21.2024.8.26.0000";
+    const TRF2: &str = "4553234-69.2023.4.02.5107";
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_deadline_business_days() {
+        let calculator = DeadlineCalculator::new(None).unwrap();
+
+        // Intimation on Monday: days 1-5 are Tuesday to the next Monday
+        assert_eq!(
+            calculator.deadline(date(2024, 6, 3), 5),
+            Some(date(2024, 6, 10))
+        );
+        // Intimation on Friday: counting starts on Monday
+        assert_eq!(
+            calculator.deadline(date(2024, 6, 7), 1),
+            Some(date(2024, 6, 10))
+        );
+        // Intimation on a Saturday: moved to Monday, counting starts on Tuesday
+        assert_eq!(
+            calculator.deadline(date(2024, 6, 8), 1),
+            Some(date(2024, 6, 11))
+        );
+        // Good Friday is skipped
+        assert_eq!(
+            calculator.deadline(date(2024, 3, 27), 2),
+            Some(date(2024, 4, 1))
+        );
+        assert_eq!(calculator.deadline(date(2024, 6, 3), 0), None);
+    }
+
+    #[test]
+    fn test_deadline_forensic_recess() {
+        let calculator = DeadlineCalculator::new(None).unwrap();
+
+        // Count stops on December 20 and resumes on January 21
+        assert_eq!(
+            calculator.deadline(date(2024, 12, 16), 5),
+            Some(date(2025, 1, 22))
+        );
+        // Intimation during the recess: moved to January 21, counting starts
+        // on January 22
+        assert_eq!(
+            calculator.deadline(date(2025, 1, 8), 15),
+            Some(date(2025, 2, 11))
+        );
+    }
+
+    #[test]
+    fn test_deadline_court_state_holidays() {
+        // July 9 is a holiday in São Paulo, not in Rio de Janeiro
+        assert_eq!(deadline(date(2024, 7, 8), 1, TJSP), Some(date(2024, 7, 10)));
+        assert_eq!(deadline(date(2024, 7, 8), 1, TRF2), Some(date(2024, 7, 9)));
+
+        // April 23 (Saint George) is a holiday in Rio de Janeiro
+        assert_eq!(
+            deadline(date(2024, 4, 22), 1, TRF2),
+            Some(date(2024, 4, 24))
+        );
+
+        assert_eq!(deadline(date(2024, 7, 8), 1, "123"), None);
+    }
+
+    #[test]
+    fn test_deadline_suspension() {
+        let calculator = DeadlineCalculator::for_legal_process(TJSP)
+            .unwrap()
+            .with_suspension(date(2024, 6, 4), date(2024, 6, 6));
+
+        assert_eq!(
+            calculator.deadline(date(2024, 6, 3), 3),
+            Some(date(2024, 6, 11))
+        );
+        assert_eq!(calculator.calendar().uf(), Some("SP"));
+    }
+
+    #[test]
+    fn test_publication_date() {
+        let calculator = DeadlineCalculator::new(Some("SP")).unwrap();
+
+        assert_eq!(
+            calculator.publication_date(date(2024, 7, 8)),
+            Some(date(2024, 7, 10))
+        );
+        assert_eq!(
+            calculator.publication_date(date(2024, 12, 19)),
+            Some(date(2025, 1, 21))
+        );
+        assert!(DeadlineCalculator::new(Some("XX")).is_none());
+    }
+
+    #[test]
+    fn test_deadline_end_local_time() {
+        use chrono::TimeZone;
+
+        let calculator = DeadlineCalculator::new(Some("AC")).unwrap();
+        let last_day = calculator.deadline(date(2024, 6, 3), 5).unwrap();
+        assert_eq!(last_day, date(2024, 6, 10));
+
+        // Midnight in Acre (UTC-5)
+        let end = calculator.deadline_end(date(2024, 6, 3), 5).unwrap();
+        assert_eq!(end, Utc.with_ymd_and_hms(2024, 6, 11, 5, 0, 0).unwrap());
+
+        let just_before = Utc.with_ymd_and_hms(2024, 6, 11, 4, 59, 59).unwrap();
+        assert_eq!(
+            calculator.is_timely(date(2024, 6, 3), 5, just_before),
+            Some(true)
+        );
+        assert_eq!(calculator.is_timely(date(2024, 6, 3), 5, end), Some(false));
+
+        assert_eq!(calculator.deadline_end(date(2024, 6, 3), 0), None);
+        assert_eq!(calculator.is_timely(date(2024, 6, 3), 0, end), None);
+    }
+
+    #[test]
+    fn test_deadline_end_daylight_saving() {
+        use chrono::TimeZone;
+
+        // Summer time in 2018-2019: the deadline ends at 02:00 UTC
+        let calculator = DeadlineCalculator::new(Some("SP")).unwrap();
+        assert_eq!(
+            calculator.deadline_end(date(2019, 2, 4), 1),
+            Some(Utc.with_ymd_and_hms(2019, 2, 6, 2, 0, 0).unwrap())
+        );
+    }
+}
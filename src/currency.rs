@@ -1,3 +1,9 @@
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::error::Error;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Neg, Sub, SubAssign};
+
 /// Currency formatting utilities for Brazilian Real (BRL).
 const ONES: &[&str] = &[
     "", "um", "dois", "três", "quatro", "cinco", "seis", "sete", "oito", "nove",
@@ -42,15 +48,116 @@ const HUNDREDS: &[&str] = &[
     "novecentos",
 ];
 
+/// Feminine forms of the hundreds, used with [`Gender::Feminine`].
+const HUNDREDS_FEMININE: &[&str] = &[
+    "",
+    "cento",
+    "duzentas",
+    "trezentas",
+    "quatrocentas",
+    "quinhentas",
+    "seiscentas",
+    "setecentas",
+    "oitocentas",
+    "novecentas",
+];
+
+/// Scales from one million up, as (value, singular, plural).
+const SCALES: &[(u128, &str, &str)] = &[
+    (
+        1_000_000_000_000_000_000_000_000_000_000_000_000,
+        "undecilhão",
+        "undecilhões",
+    ),
+    (
+        1_000_000_000_000_000_000_000_000_000_000_000,
+        "decilhão",
+        "decilhões",
+    ),
+    (
+        1_000_000_000_000_000_000_000_000_000_000,
+        "nonilhão",
+        "nonilhões",
+    ),
+    (
+        1_000_000_000_000_000_000_000_000_000,
+        "octilhão",
+        "octilhões",
+    ),
+    (1_000_000_000_000_000_000_000_000, "septilhão", "septilhões"),
+    (1_000_000_000_000_000_000_000, "sextilhão", "sextilhões"),
+    (1_000_000_000_000_000_000, "quintilhão", "quintilhões"),
+    (1_000_000_000_000_000, "quatrilhão", "quatrilhões"),
+    (1_000_000_000_000, "trilhão", "trilhões"),
+    (1_000_000_000, "bilhão", "bilhões"),
+    (1_000_000, "milhão", "milhões"),
+];
+
+const ORDINAL_ONES: &[&str] = &[
+    "", "primeiro", "segundo", "terceiro", "quarto", "quinto", "sexto", "sétimo", "oitavo", "nono",
+];
+
+const ORDINAL_TENS: &[&str] = &[
+    "",
+    "décimo",
+    "vigésimo",
+    "trigésimo",
+    "quadragésimo",
+    "quinquagésimo",
+    "sexagésimo",
+    "septuagésimo",
+    "octogésimo",
+    "nonagésimo",
+];
+
+const ORDINAL_HUNDREDS: &[&str] = &[
+    "",
+    "centésimo",
+    "ducentésimo",
+    "trecentésimo",
+    "quadringentésimo",
+    "quingentésimo",
+    "sexcentésimo",
+    "septingentésimo",
+    "octingentésimo",
+    "nongentésimo",
+];
+
+/// Ordinal scales from one thousand up, as (value, ordinal).
+const ORDINAL_SCALES: &[(u64, &str)] = &[
+    (1_000_000_000_000_000_000, "quintilionésimo"),
+    (1_000_000_000_000_000, "quatrilionésimo"),
+    (1_000_000_000_000, "trilionésimo"),
+    (1_000_000_000, "bilionésimo"),
+    (1_000_000, "milionésimo"),
+    (1_000, "milésimo"),
+];
+
+/// Grammatical gender of the noun a number refers to.
+///
+/// In Portuguese "um", "dois" and the hundreds from "duzentos" on agree in
+/// gender with the counted noun ("duzentas e duas parcelas"), as do all the
+/// ordinals ("vigésima primeira parcela").
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Gender {
+    /// Masculine nouns, such as "real" or "dia".
+    #[default]
+    Masculine,
+    /// Feminine nouns, such as "parcela" or "pessoa".
+    Feminine,
+}
+
 /// Convert an integer to its Portuguese (Brazilian) word representation.
 ///
 /// # Arguments
 ///
-/// * `n` - The number to convert (0-999,999,999,999,999,999)
+/// * `n` - The number to convert.
 ///
 /// # Returns
 ///
-/// The number written in words in Brazilian Portuguese.
+/// The number written in words in Brazilian Portuguese, in the masculine gender.
+/// Use [`number_to_words_with_gender`] for feminine nouns and for values
+/// beyond the `i64` range.
 ///
 /// # Examples
 ///
@@ -59,109 +166,82 @@ const HUNDREDS: &[&str] = &[
 ///
 /// assert_eq!(number_to_words(123), "cento e vinte e três");
 /// assert_eq!(number_to_words(1000), "mil");
+/// assert_eq!(number_to_words(5_000_000_000_000_000_000), "cinco quintilhões");
 /// ```
 pub fn number_to_words(n: i64) -> String {
+    number_to_words_with_gender(n as i128, Gender::Masculine)
+}
+
+/// Convert an integer to its Portuguese (Brazilian) word representation in the given gender.
+///
+/// The gender applies to the units, the hundreds and the thousands, while the
+/// counts of "milhões", "bilhões" and larger scales are always masculine
+/// because those words are masculine nouns. Scales go up to "undecilhões",
+/// covering the whole `i128` range (and therefore `u64`).
+///
+/// # Arguments
+///
+/// * `n` - The number to convert.
+/// * `gender` - The gender of the noun being counted.
+///
+/// # Returns
+///
+/// The number written in words in Brazilian Portuguese.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::currency::{number_to_words_with_gender, Gender};
+///
+/// assert_eq!(number_to_words_with_gender(2, Gender::Feminine), "duas");
+/// assert_eq!(number_to_words_with_gender(200, Gender::Feminine), "duzentas");
+/// assert_eq!(number_to_words_with_gender(2_201, Gender::Feminine), "duas mil, duzentas e uma");
+/// assert_eq!(number_to_words_with_gender(2_000_000, Gender::Feminine), "dois milhões");
+/// assert_eq!(
+///     number_to_words_with_gender(u64::MAX.into(), Gender::Masculine).split(',').next(),
+///     Some("dezoito quintilhões")
+/// );
+/// ```
+pub fn number_to_words_with_gender(n: i128, gender: Gender) -> String {
     if n == 0 {
         return "zero".to_string();
     }
 
     if n < 0 {
-        return format!("menos {}", number_to_words(-n));
-    }
-
-    // Handle quadrillions (10^15)
-    if n >= 1_000_000_000_000_000 {
-        let quadrillions = n / 1_000_000_000_000_000;
-        let remainder = n % 1_000_000_000_000_000;
-
-        let quadrillion_text = if quadrillions == 1 {
-            "um quatrilhão".to_string()
-        } else {
-            format!("{} quatrilhões", number_to_words(quadrillions))
-        };
-
-        if remainder == 0 {
-            return quadrillion_text;
-        }
-
-        let connector = if remainder < 100 { " e " } else { ", " };
-        return format!(
-            "{}{}{}",
-            quadrillion_text,
-            connector,
-            number_to_words(remainder)
-        );
-    }
-
-    // Handle trillions (10^12)
-    if n >= 1_000_000_000_000 {
-        let trillions = n / 1_000_000_000_000;
-        let remainder = n % 1_000_000_000_000;
-
-        let trillion_text = if trillions == 1 {
-            "um trilhão".to_string()
-        } else {
-            format!("{} trilhões", number_to_words(trillions))
-        };
-
-        if remainder == 0 {
-            return trillion_text;
-        }
-
-        let connector = if remainder < 100 { " e " } else { ", " };
-        return format!(
-            "{}{}{}",
-            trillion_text,
-            connector,
-            number_to_words(remainder)
-        );
+        return format!("menos {}", cardinal_to_words(n.unsigned_abs(), gender));
     }
 
-    // Handle billions (10^9)
-    if n >= 1_000_000_000 {
-        let billions = n / 1_000_000_000;
-        let remainder = n % 1_000_000_000;
-
-        let billion_text = if billions == 1 {
-            "um bilhão".to_string()
-        } else {
-            format!("{} bilhões", number_to_words(billions))
-        };
+    cardinal_to_words(n as u128, gender)
+}
 
-        if remainder == 0 {
-            return billion_text;
+/// Writes a positive number in words.
+fn cardinal_to_words(n: u128, gender: Gender) -> String {
+    // Handle millions and larger scales
+    for &(scale, singular, plural) in SCALES {
+        if n < scale {
+            continue;
         }
 
-        let connector = if remainder < 100 { " e " } else { ", " };
-        return format!(
-            "{}{}{}",
-            billion_text,
-            connector,
-            number_to_words(remainder)
-        );
-    }
-
-    // Handle millions (10^6)
-    if n >= 1_000_000 {
-        let millions = n / 1_000_000;
-        let remainder = n % 1_000_000;
+        let count = n / scale;
+        let remainder = n % scale;
 
-        let million_text = if millions == 1 {
-            "um milhão".to_string()
+        // The scale names are masculine nouns: "dois milhões", "um bilhão"
+        let scale_text = if count == 1 {
+            format!("um {}", singular)
         } else {
-            format!("{} milhões", number_to_words(millions))
+            format!("{} {}", cardinal_to_words(count, Gender::Masculine), plural)
         };
 
         if remainder == 0 {
-            return million_text;
+            return scale_text;
         }
 
         let connector = if remainder < 100 { " e " } else { ", " };
         return format!(
             "{}{}{}",
-            million_text,
+            scale_text,
             connector,
-            number_to_words(remainder)
+            cardinal_to_words(remainder, gender)
         );
     }
 
@@ -173,7 +253,7 @@ pub fn number_to_words(n: i64) -> String {
         let thousand_text = if thousands == 1 {
             "mil".to_string()
         } else {
-            format!("{} mil", number_to_words(thousands))
+            format!("{} mil", cardinal_to_words(thousands, gender))
         };
 
         if remainder == 0 {
@@ -181,7 +261,7 @@ pub fn number_to_words(n: i64) -> String {
         }
 
         // Use "e" for round hundreds (100, 200, 300, etc.), otherwise use ","
-        let connector = if remainder % 100 == 0 || remainder < 100 {
+        let connector = if remainder.is_multiple_of(100) || remainder < 100 {
             " e "
         } else {
             ", "
@@ -190,7 +270,7 @@ pub fn number_to_words(n: i64) -> String {
             "{}{}{}",
             thousand_text,
             connector,
-            number_to_words(remainder)
+            cardinal_to_words(remainder, gender)
         );
     }
 
@@ -200,28 +280,38 @@ pub fn number_to_words(n: i64) -> String {
         let remainder = n % 100;
 
         let hundred_text = if n == 100 {
-            "cem".to_string()
+            "cem"
+        } else if gender == Gender::Feminine {
+            HUNDREDS_FEMININE[hundreds_digit]
         } else {
-            HUNDREDS[hundreds_digit].to_string()
+            HUNDREDS[hundreds_digit]
         };
 
         if remainder == 0 {
-            return hundred_text;
+            return hundred_text.to_string();
         }
 
-        return format!("{} e {}", hundred_text, number_to_words(remainder));
+        return format!(
+            "{} e {}",
+            hundred_text,
+            cardinal_to_words(remainder, gender)
+        );
     }
 
     // Handle numbers from 20-99
     if n >= 20 {
         let tens_digit = (n / 10) as usize;
-        let ones_digit = (n % 10) as usize;
+        let ones_digit = n % 10;
 
         if ones_digit == 0 {
             return TENS[tens_digit].to_string();
         }
 
-        return format!("{} e {}", TENS[tens_digit], ONES[ones_digit]);
+        return format!(
+            "{} e {}",
+            TENS[tens_digit],
+            cardinal_to_words(ones_digit, gender)
+        );
     }
 
     // Handle teens (11-19)
@@ -231,11 +321,117 @@ pub fn number_to_words(n: i64) -> String {
     }
 
     // Handle 1-10
-    if n == 10 {
-        return "dez".to_string();
+    match (n, gender) {
+        (10, _) => "dez".to_string(),
+        (1, Gender::Feminine) => "uma".to_string(),
+        (2, Gender::Feminine) => "duas".to_string(),
+        _ => ONES[n as usize].to_string(),
+    }
+}
+
+/// Convert a positive integer to its Portuguese (Brazilian) ordinal numeral.
+///
+/// The parts of the ordinal are written without connectors ("vigésimo
+/// primeiro") and every part agrees with the given gender. Multiples of the
+/// scales use the ordinal of the multiplier ("segundo milésimo" for 2000º).
+///
+/// # Arguments
+///
+/// * `n` - The number to convert.
+/// * `gender` - The gender of the noun being ordered.
+///
+/// # Returns
+///
+/// The ordinal written in words, or `None` if `n` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::currency::{ordinal_to_words, Gender};
+///
+/// assert_eq!(ordinal_to_words(1, Gender::Masculine), Some("primeiro".to_string()));
+/// assert_eq!(ordinal_to_words(21, Gender::Feminine), Some("vigésima primeira".to_string()));
+/// assert_eq!(ordinal_to_words(1_000, Gender::Masculine), Some("milésimo".to_string()));
+/// assert_eq!(ordinal_to_words(0, Gender::Masculine), None);
+/// ```
+pub fn ordinal_to_words(n: u64, gender: Gender) -> Option<String> {
+    if n == 0 {
+        return None;
+    }
+
+    let mut words: Vec<&str> = Vec::new();
+    let mut remainder = n;
+
+    for &(scale, name) in ORDINAL_SCALES {
+        let count = remainder / scale;
+        if count == 0 {
+            continue;
+        }
+
+        if count > 1 {
+            push_ordinal_below_thousand(count, &mut words);
+        }
+        words.push(name);
+        remainder %= scale;
+    }
+
+    push_ordinal_below_thousand(remainder, &mut words);
+
+    let words: Vec<String> = words
+        .into_iter()
+        .map(|word| match gender {
+            Gender::Masculine => word.to_string(),
+            // Every ordinal ends in "o"; the feminine replaces it with "a"
+            Gender::Feminine => format!("{}a", &word[..word.len() - 1]),
+        })
+        .collect();
+
+    Some(words.join(" "))
+}
+
+/// Appends the ordinal words for a number below one thousand.
+fn push_ordinal_below_thousand(n: u64, words: &mut Vec<&'static str>) {
+    let hundreds = (n / 100) as usize;
+    let tens = (n / 10 % 10) as usize;
+    let ones = (n % 10) as usize;
+
+    for word in [
+        ORDINAL_HUNDREDS[hundreds],
+        ORDINAL_TENS[tens],
+        ORDINAL_ONES[ones],
+    ] {
+        if !word.is_empty() {
+            words.push(word);
+        }
     }
+}
+
+/// Writes an ordinal number in its abbreviated form, such as "1º" or "1ª".
+///
+/// # Arguments
+///
+/// * `n` - The number to abbreviate.
+/// * `gender` - The gender of the noun being ordered.
+///
+/// # Returns
+///
+/// The number followed by the masculine (º) or feminine (ª) ordinal indicator.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::currency::{ordinal_abbreviation, Gender};
+///
+/// assert_eq!(ordinal_abbreviation(1, Gender::Masculine), "1º");
+/// assert_eq!(ordinal_abbreviation(21, Gender::Feminine), "21ª");
+/// ```
+pub fn ordinal_abbreviation(n: u64, gender: Gender) -> String {
+    let indicator = match gender {
+        Gender::Masculine => 'º',
+        Gender::Feminine => 'ª',
+    };
 
-    ONES[n as usize].to_string()
+    format!("{}{}", n, indicator)
 }
 
 /// Format a numeric value as Brazilian currency (R$).
@@ -247,9 +443,13 @@ pub fn number_to_words(n: i64) -> String {
 /// - Uses period (.) as thousands separator
 /// - Always shows 2 decimal places
 ///
+/// Both `f64` values and exact [`Brl`] amounts are accepted. Floating point
+/// values are rounded half away from zero using their shortest decimal
+/// representation, so `1.005` is formatted as `R$ 1,01`.
+///
 /// # Arguments
 ///
-/// * `value` - The numeric value to format (can be positive, negative, or zero).
+/// * `value` - The value to format (can be positive, negative, or zero).
 ///
 /// # Returns
 ///
@@ -258,39 +458,24 @@ pub fn number_to_words(n: i64) -> String {
 /// # Examples
 ///
 /// ```
-/// use brazilian_utils::currency::format_currency;
+/// use brazilian_utils::currency::{format_currency, Brl};
 ///
 /// assert_eq!(format_currency(1234.56), Some("R$ 1.234,56".to_string()));
 /// assert_eq!(format_currency(0.0), Some("R$ 0,00".to_string()));
 /// assert_eq!(format_currency(-9876.54), Some("R$ -9.876,54".to_string()));
+/// assert_eq!(format_currency(Brl::from_centavos(100)), Some("R$ 1,00".to_string()));
 /// ```
-pub fn format_currency(value: f64) -> Option<String> {
-    if !value.is_finite() {
-        return None;
-    }
-
-    // Round to 2 decimal places
-    let rounded = (value * 100.0).round() / 100.0;
-
-    // Separate integer and decimal parts
-    let abs_value = rounded.abs();
-    let integer_part = abs_value.floor() as i64;
-    let decimal_part = ((abs_value - abs_value.floor()) * 100.0).round() as i64;
-
-    // Check if the value is effectively zero after rounding
-    let is_zero = integer_part == 0 && decimal_part == 0;
-
-    // Handle negative sign (but treat -0.00 as 0.00)
-    let negative = rounded < 0.0 && !is_zero;
+pub fn format_currency<T: IntoBrl>(value: T) -> Option<String> {
+    let value = value.into_brl()?;
 
     // Format integer part with thousands separator
-    let integer_str = format_with_thousands_separator(integer_part);
+    let integer_str = format_with_thousands_separator(value.reais().abs());
 
     // Format with 2 decimal places
-    let formatted = format!("{},{:02}", integer_str, decimal_part);
+    let formatted = format!("{},{:02}", integer_str, value.centavos_part());
 
     // Add currency symbol and negative sign if needed
-    if negative {
+    if value.is_negative() {
         Some(format!("R$ -{}", formatted))
     } else {
         Some(format!("R$ {}", formatted))
@@ -322,258 +507,2347 @@ fn format_with_thousands_separator(mut num: i64) -> String {
 
 /// Converts a Real (BRL) value to its written text representation in Brazilian Portuguese.
 ///
+/// Both `f64` values and exact [`Brl`] amounts are accepted. See
+/// [`convert_amount_to_text`] for other currencies and units.
+///
 /// # Arguments
 ///
 /// * `value` - The monetary value in Brazilian Reais to convert
 ///
 /// # Returns
 ///
-/// The value written in full in Brazilian Portuguese.
+/// The value written in full in Brazilian Portuguese, or an empty string if
+/// the value is not a finite number.
 ///
 /// # Examples
 ///
 /// ```
-/// use brazilian_utils::currency::convert_real_to_text;
+/// use brazilian_utils::currency::{convert_real_to_text, Brl};
 ///
 /// assert_eq!(convert_real_to_text(1.00), "um real");
 /// assert_eq!(convert_real_to_text(2.50), "dois reais e cinquenta centavos");
 /// assert_eq!(convert_real_to_text(1000000.00), "um milhão de reais");
+/// assert_eq!(convert_real_to_text(Brl::from_centavos(101)), "um real e um centavo");
 /// ```
-pub fn convert_real_to_text(value: f64) -> String {
-    if value == 0.0 || value.abs() < 0.005 {
-        return "zero real".to_string();
+pub fn convert_real_to_text<T: IntoBrl>(value: T) -> String {
+    match value.into_brl() {
+        Some(value) => convert_amount_to_text(value.centavos() as i128, &Unit::BRL),
+        None => String::new(),
     }
+}
 
-    let is_negative = value < 0.0;
-    let abs_value = value.abs();
-
-    // Split into integer and decimal parts
-    let reais = abs_value.floor() as i64;
-    let centavos = ((abs_value - reais as f64) * 100.0).round() as i64;
+/// Name of a currency or unit of measure, used to write amounts in full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UnitName<'a> {
+    /// Name used for exactly one unit, e.g. "real".
+    pub singular: &'a str,
+    /// Name used for any other quantity, e.g. "reais".
+    pub plural: &'a str,
+    /// Gender of the name, which the numbers agree with ("duas libras").
+    pub gender: Gender,
+    /// Whether "de" goes before the name from a million on ("um milhão de
+    /// reais", "um milhão e um de reais").
+    pub de_from_million: bool,
+}
 
-    let mut result = String::new();
+impl<'a> UnitName<'a> {
+    /// Creates a unit name, with "de" before it from a million on.
+    pub const fn new(singular: &'a str, plural: &'a str, gender: Gender) -> Self {
+        UnitName {
+            singular,
+            plural,
+            gender,
+            de_from_million: true,
+        }
+    }
 
-    if is_negative {
-        result.push_str("menos ");
+    /// Never places "de" before the name ("um milhão vezes").
+    pub const fn without_de(self) -> Self {
+        UnitName {
+            de_from_million: false,
+            ..self
+        }
     }
+}
 
-    if reais > 0 {
-        let reais_text = number_to_words(reais);
+/// Describes a currency or unit of measure for [`convert_amount_to_text`].
+///
+/// A unit has a major name and, optionally, a minor name with the number of
+/// minor units in one major unit (100 centavos in a real, 1000 gramas in a
+/// quilo).
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::currency::{convert_amount_to_text, Gender, Unit, UnitName};
+///
+/// let square_meters = Unit::new(UnitName::new("metro quadrado", "metros quadrados", Gender::Masculine));
+/// assert_eq!(convert_amount_to_text(2, &square_meters), "dois metros quadrados");
+///
+/// let tons = Unit::new(UnitName::new("tonelada", "toneladas", Gender::Feminine))
+///     .with_minor(UnitName::new("quilo", "quilos", Gender::Masculine), 1000);
+/// assert_eq!(convert_amount_to_text(2_200, &tons), "duas toneladas e duzentos quilos");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Unit<'a> {
+    /// Name of the main unit.
+    pub major: UnitName<'a>,
+    /// Name of the fractional unit, if the amount has one.
+    pub minor: Option<UnitName<'a>>,
+    /// Number of minor units in one major unit (1 when there is no minor unit).
+    pub minor_per_major: u32,
+}
 
-        // Add "de" before "reais" for large numbers (million and above)
-        let currency_name = if reais == 1 {
-            "real".to_string()
-        } else if reais >= 1_000_000 {
-            "de reais".to_string()
-        } else {
-            "reais".to_string()
-        };
+impl Unit<'static> {
+    /// Brazilian Real: "real"/"reais" and "centavo"/"centavos".
+    pub const BRL: Unit<'static> =
+        Unit::new(UnitName::new("real", "reais", Gender::Masculine)).with_minor(CENTAVO, 100);
 
-        result.push_str(&format!("{} {}", reais_text, currency_name));
-    }
+    /// United States Dollar: "dólar"/"dólares" and "centavo"/"centavos".
+    pub const USD: Unit<'static> =
+        Unit::new(UnitName::new("dólar", "dólares", Gender::Masculine)).with_minor(CENTAVO, 100);
 
-    if centavos > 0 {
-        if reais > 0 {
-            result.push_str(" e ");
-        }
+    /// Euro: "euro"/"euros" and "centavo"/"centavos".
+    pub const EUR: Unit<'static> =
+        Unit::new(UnitName::new("euro", "euros", Gender::Masculine)).with_minor(CENTAVO, 100);
 
-        let centavos_text = number_to_words(centavos);
-        let centavo_text = if centavos == 1 { "centavo" } else { "centavos" };
+    /// Pound sterling: "libra"/"libras" and "pêni"/"pence".
+    pub const GBP: Unit<'static> = Unit::new(UnitName::new("libra", "libras", Gender::Feminine))
+        .with_minor(UnitName::new("pêni", "pence", Gender::Masculine), 100);
+}
 
-        result.push_str(&format!("{} {}", centavos_text, centavo_text));
+impl<'a> Unit<'a> {
+    /// Creates a unit without a minor unit.
+    pub const fn new(major: UnitName<'a>) -> Self {
+        Unit {
+            major,
+            minor: None,
+            minor_per_major: 1,
+        }
     }
 
-    result
+    /// Adds a minor unit, `minor_per_major` of which make one major unit.
+    pub const fn with_minor(self, minor: UnitName<'a>, minor_per_major: u32) -> Self {
+        Unit {
+            major: self.major,
+            minor: Some(minor),
+            minor_per_major,
+        }
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_format_currency_positive_values() {
-        assert_eq!(format_currency(1234.56), Some("R$ 1.234,56".to_string()));
-        assert_eq!(
-            format_currency(123236.70),
-            Some("R$ 123.236,70".to_string())
-        );
-        assert_eq!(format_currency(1259.03), Some("R$ 1.259,03".to_string()));
-    }
+/// Minor unit shared by the currencies that Brazilians count in "centavos".
+const CENTAVO: UnitName<'static> = UnitName::new("centavo", "centavos", Gender::Masculine);
 
-    #[test]
-    fn test_format_currency_zero() {
-        assert_eq!(format_currency(0.0), Some("R$ 0,00".to_string()));
+/// Writes an amount of any currency or unit in full in Brazilian Portuguese.
+///
+/// This is the general form of [`convert_real_to_text`]: the numbers agree in
+/// gender with the unit names, the plural is used for anything but exactly
+/// one unit, and "de" is placed before the unit from a million on ("um
+/// milhão de dólares", "um milhão e um de dólares"), unless the unit name
+/// opts out with [`UnitName::without_de`].
+///
+/// # Arguments
+///
+/// * `amount` - The amount in minor units (e.g. cents), or in whole units when
+///   `unit` has no minor unit.
+/// * `unit` - The currency or unit of measure.
+///
+/// # Returns
+///
+/// The amount written in full in Brazilian Portuguese.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::currency::{convert_amount_to_text, Unit};
+///
+/// assert_eq!(convert_amount_to_text(100_010, &Unit::USD), "mil dólares e dez centavos");
+/// assert_eq!(convert_amount_to_text(100, &Unit::EUR), "um euro");
+/// assert_eq!(convert_amount_to_text(200_000_000, &Unit::USD), "dois milhões de dólares");
+/// assert_eq!(convert_amount_to_text(201, &Unit::GBP), "duas libras e um pêni");
+/// ```
+pub fn convert_amount_to_text(amount: i128, unit: &Unit) -> String {
+    if amount == 0 {
+        return format!("zero {}", unit.major.singular);
     }
 
-    #[test]
-    fn test_format_currency_negative_values() {
-        assert_eq!(
-            format_currency(-123236.70),
-            Some("R$ -123.236,70".to_string())
-        );
-        assert_eq!(format_currency(-9876.54), Some("R$ -9.876,54".to_string()));
-    }
+    let magnitude = amount.unsigned_abs();
+    let (major, minor) = match unit.minor {
+        Some(_) if unit.minor_per_major > 1 => {
+            let per_major = unit.minor_per_major as u128;
+            (magnitude / per_major, magnitude % per_major)
+        }
+        _ => (magnitude, 0),
+    };
 
-    #[test]
-    fn test_format_currency_rounding() {
-        // Test decimal rounding
-        assert_eq!(
-            format_currency(123236.7676),
-            Some("R$ 123.236,77".to_string())
-        );
-        assert_eq!(format_currency(10.555), Some("R$ 10,56".to_string()));
-    }
+    let mut parts = Vec::new();
 
-    #[test]
-    fn test_format_currency_small_values() {
-        assert_eq!(format_currency(0.01), Some("R$ 0,01".to_string()));
-        assert_eq!(format_currency(0.99), Some("R$ 0,99".to_string()));
-        assert_eq!(format_currency(5.50), Some("R$ 5,50".to_string()));
+    if major > 0 {
+        parts.push(quantity_to_text(major, &unit.major));
     }
 
-    #[test]
-    fn test_format_currency_large_values() {
-        assert_eq!(
-            format_currency(1_000_000.00),
-            Some("R$ 1.000.000,00".to_string())
-        );
-        assert_eq!(
-            format_currency(999_999_999.99),
-            Some("R$ 999.999.999,99".to_string())
-        );
+    if let (Some(minor_name), true) = (&unit.minor, minor > 0) {
+        parts.push(quantity_to_text(minor, minor_name));
     }
 
-    #[test]
-    fn test_format_currency_invalid_values() {
-        assert_eq!(format_currency(f64::INFINITY), None);
-        assert_eq!(format_currency(f64::NEG_INFINITY), None);
-        assert_eq!(format_currency(f64::NAN), None);
-    }
+    let text = parts.join(" e ");
 
-    #[test]
-    fn test_format_with_thousands_separator() {
-        assert_eq!(format_with_thousands_separator(0), "0");
-        assert_eq!(format_with_thousands_separator(123), "123");
-        assert_eq!(format_with_thousands_separator(1234), "1.234");
-        assert_eq!(format_with_thousands_separator(123456), "123.456");
-        assert_eq!(format_with_thousands_separator(1234567), "1.234.567");
-        assert_eq!(format_with_thousands_separator(999999999), "999.999.999");
+    if amount < 0 {
+        format!("menos {}", text)
+    } else {
+        text
     }
+}
 
-    #[test]
-    fn test_format_currency_edge_cases() {
-        // Very small positive value
-        assert_eq!(format_currency(0.001), Some("R$ 0,00".to_string()));
+/// Writes a positive quantity followed by the unit name.
+fn quantity_to_text(quantity: u128, name: &UnitName) -> String {
+    let words = cardinal_to_words(quantity, name.gender);
 
-        // Very small negative value - rounds to 0 but we treat as positive zero
-        assert_eq!(format_currency(-0.001), Some("R$ 0,00".to_string()));
+    if quantity == 1 {
+        return format!("{} {}", words, name.singular);
+    }
 
-        // Values that need rounding
-        assert_eq!(format_currency(1.234), Some("R$ 1,23".to_string()));
-        assert_eq!(format_currency(1.235), Some("R$ 1,24".to_string()));
+    // "um milhão de reais", "dois bilhões de quilos"
+    if name.de_from_million && quantity >= 1_000_000 {
+        format!("{} de {}", words, name.plural)
+    } else {
+        format!("{} {}", words, name.plural)
     }
+}
 
-    #[test]
-    fn test_number_to_words_basic() {
-        assert_eq!(number_to_words(0), "zero");
-        assert_eq!(number_to_words(1), "um");
-        assert_eq!(number_to_words(5), "cinco");
-        assert_eq!(number_to_words(10), "dez");
-        assert_eq!(number_to_words(15), "quinze");
+/// Parses a number written in Brazilian Portuguese words, the inverse of [`number_to_words`].
+///
+/// Accents are optional, case is ignored, and the connectors "e" and "," may
+/// be used or omitted freely. Common variants such as "quatorze" and feminine
+/// forms ("uma", "duas", "duzentas") are accepted.
+///
+/// # Arguments
+///
+/// * `text` - The number written in words.
+///
+/// # Returns
+///
+/// The parsed number, or `None` if the text is not a well-formed number.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::currency::{number_to_words, words_to_number};
+///
+/// assert_eq!(words_to_number("cento e vinte e três"), Some(123));
+/// assert_eq!(words_to_number("Dois Milhoes, Trezentos Mil e Quinze"), Some(2_300_015));
+/// assert_eq!(words_to_number("menos mil"), Some(-1000));
+/// assert_eq!(words_to_number(&number_to_words(987_654_321)), Some(987_654_321));
+/// assert_eq!(words_to_number("vinte cem"), None);
+/// ```
+pub fn words_to_number(text: &str) -> Option<i64> {
+    let normalized = normalize_words(text);
+    let tokens: Vec<&str> = normalized.split_whitespace().collect();
+    parse_number_tokens(&tokens).and_then(|value| i64::try_from(value).ok())
+}
+
+/// Parses a Real (BRL) amount written in words, the inverse of [`convert_real_to_text`].
+///
+/// Accepts the integer part followed by "real"/"reais" (optionally preceded
+/// by "de", as in "um milhão de reais"), the centavos followed by
+/// "centavo"/"centavos", or both joined by "e". A leading "menos" makes the
+/// amount negative. Accents are optional and case is ignored.
+///
+/// # Arguments
+///
+/// * `text` - The amount written in words.
+///
+/// # Returns
+///
+/// The exact amount, or `None` if the text is not a well-formed amount.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::currency::{convert_real_to_text, convert_text_to_real, Brl};
+///
+/// assert_eq!(convert_text_to_real("um real e cinquenta centavos"), Some(Brl::from_centavos(150)));
+/// assert_eq!(convert_text_to_real("um milhão de reais"), Some(Brl::from_centavos(100_000_000)));
+/// assert_eq!(convert_text_to_real("noventa e nove centavos"), Some(Brl::from_centavos(99)));
+///
+/// let amount = Brl::from_centavos(12_345_678);
+/// assert_eq!(convert_text_to_real(&convert_real_to_text(amount)), Some(amount));
+/// ```
+pub fn convert_text_to_real(text: &str) -> Option<Brl> {
+    let normalized = normalize_words(text);
+    let mut tokens: Vec<&str> = normalized.split_whitespace().collect();
+
+    let negative = tokens.first() == Some(&"menos");
+    if negative {
+        tokens.remove(0);
+    }
+
+    // "cinquenta centavos de real"
+    if tokens.ends_with(&["de", "real"]) {
+        tokens.truncate(tokens.len() - 2);
+    }
+
+    let major_end = tokens
+        .iter()
+        .position(|token| *token == "real" || *token == "reais");
+
+    let (major_tokens, minor_tokens) = match major_end {
+        Some(position) => {
+            let mut major = &tokens[..position];
+            if major.last() == Some(&"de") {
+                major = &major[..major.len() - 1];
+            }
+            (Some(major), &tokens[position + 1..])
+        }
+        None => (None, &tokens[..]),
+    };
+
+    let reais = match major_tokens {
+        Some(major) => parse_number_tokens(major).filter(|value| *value >= 0)?,
+        None => 0,
+    };
+
+    let centavos = match minor_tokens.split_last() {
+        None if major_tokens.is_some() => 0,
+        Some((last, minor)) if *last == "centavo" || *last == "centavos" => {
+            parse_number_tokens(minor).filter(|value| (0..100).contains(value))?
+        }
+        _ => return None,
+    };
+
+    let total = reais.checked_mul(100)?.checked_add(centavos)?;
+    let total = if negative { -total } else { total };
+    i64::try_from(total).ok().map(Brl::from_centavos)
+}
+
+/// Lowercases the text, removes accents and drops the "e"/"," connectors.
+fn normalize_words(text: &str) -> String {
+    use unicode_normalization::UnicodeNormalization;
+
+    let stripped: String = text
+        .nfd()
+        .filter(|c| !unicode_normalization::char::is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .map(|c| if c == ',' || c == ';' { ' ' } else { c })
+        .collect();
+
+    stripped
+        .split_whitespace()
+        .filter(|word| *word != "e")
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A single word of a number written in Portuguese.
+enum NumberWord {
+    /// A value below one thousand, with the slot it occupies in its group
+    /// (3 = hundreds, 2 = tens, 1 = units) and the highest slot that may
+    /// follow it.
+    Part { value: i128, slot: u8, next: u8 },
+    /// A scale word such as "mil" or "milhões".
+    Scale(i128),
+}
+
+/// Looks up an accent-free, lowercase number word.
+fn lookup_number_word(word: &str) -> Option<NumberWord> {
+    let part = |value, slot, next| Some(NumberWord::Part { value, slot, next });
+
+    match word {
+        "um" | "uma" => part(1, 1, 0),
+        "dois" | "duas" => part(2, 1, 0),
+        "tres" => part(3, 1, 0),
+        "quatro" => part(4, 1, 0),
+        "cinco" => part(5, 1, 0),
+        "seis" => part(6, 1, 0),
+        "sete" => part(7, 1, 0),
+        "oito" => part(8, 1, 0),
+        "nove" => part(9, 1, 0),
+        "dez" => part(10, 2, 0),
+        "onze" => part(11, 2, 0),
+        "doze" => part(12, 2, 0),
+        "treze" => part(13, 2, 0),
+        "catorze" | "quatorze" => part(14, 2, 0),
+        "quinze" => part(15, 2, 0),
+        "dezesseis" | "dezasseis" => part(16, 2, 0),
+        "dezessete" | "dezassete" => part(17, 2, 0),
+        "dezoito" => part(18, 2, 0),
+        "dezenove" | "dezanove" => part(19, 2, 0),
+        "vinte" => part(20, 2, 1),
+        "trinta" => part(30, 2, 1),
+        "quarenta" => part(40, 2, 1),
+        "cinquenta" | "cincoenta" => part(50, 2, 1),
+        "sessenta" => part(60, 2, 1),
+        "setenta" => part(70, 2, 1),
+        "oitenta" => part(80, 2, 1),
+        "noventa" => part(90, 2, 1),
+        "cem" => part(100, 3, 0),
+        "cento" => part(100, 3, 2),
+        "duzentos" | "duzentas" => part(200, 3, 2),
+        "trezentos" | "trezentas" => part(300, 3, 2),
+        "quatrocentos" | "quatrocentas" => part(400, 3, 2),
+        "quinhentos" | "quinhentas" => part(500, 3, 2),
+        "seiscentos" | "seiscentas" => part(600, 3, 2),
+        "setecentos" | "setecentas" => part(700, 3, 2),
+        "oitocentos" | "oitocentas" => part(800, 3, 2),
+        "novecentos" | "novecentas" => part(900, 3, 2),
+        "mil" => Some(NumberWord::Scale(1_000)),
+        "milhao" | "milhoes" => Some(NumberWord::Scale(10i128.pow(6))),
+        "bilhao" | "bilhoes" => Some(NumberWord::Scale(10i128.pow(9))),
+        "trilhao" | "trilhoes" => Some(NumberWord::Scale(10i128.pow(12))),
+        "quatrilhao" | "quatrilhoes" => Some(NumberWord::Scale(10i128.pow(15))),
+        "quintilhao" | "quintilhoes" => Some(NumberWord::Scale(10i128.pow(18))),
+        "sextilhao" | "sextilhoes" => Some(NumberWord::Scale(10i128.pow(21))),
+        "septilhao" | "septilhoes" => Some(NumberWord::Scale(10i128.pow(24))),
+        "octilhao" | "octilhoes" => Some(NumberWord::Scale(10i128.pow(27))),
+        "nonilhao" | "nonilhoes" => Some(NumberWord::Scale(10i128.pow(30))),
+        "decilhao" | "decilhoes" => Some(NumberWord::Scale(10i128.pow(33))),
+        "undecilhao" | "undecilhoes" => Some(NumberWord::Scale(10i128.pow(36))),
+        _ => None,
+    }
+}
+
+/// Parses normalized number words into a value.
+fn parse_number_tokens(tokens: &[&str]) -> Option<i128> {
+    let (negative, tokens) = match tokens.split_first() {
+        Some((&"menos", rest)) => (true, rest),
+        _ => (false, tokens),
+    };
+
+    if tokens == ["zero"] {
+        return Some(0);
+    }
+
+    if tokens.is_empty() {
+        return None;
+    }
+
+    let mut total: i128 = 0;
+    let mut group: i128 = 0;
+    let mut allowed_slot: u8 = 3;
+    let mut last_scale = i128::MAX;
+
+    for token in tokens {
+        match lookup_number_word(token)? {
+            NumberWord::Part { value, slot, next } => {
+                if slot > allowed_slot {
+                    return None;
+                }
+                group += value;
+                allowed_slot = next;
+            }
+            NumberWord::Scale(scale) => {
+                if scale >= last_scale {
+                    return None;
+                }
+
+                // "mil" may stand alone, the other scales need a count
+                let count = match group {
+                    0 if scale == 1_000 => 1,
+                    0 => return None,
+                    count => count,
+                };
+
+                total = total.checked_add(count.checked_mul(scale)?)?;
+                group = 0;
+                allowed_slot = 3;
+                last_scale = scale;
+            }
+        }
+    }
+
+    let total = total.checked_add(group)?;
+    Some(if negative { -total } else { total })
+}
+
+/// Error returned when a formatted currency string cannot be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseCurrencyError {
+    /// The input is empty or contains only whitespace.
+    Empty,
+    /// The input is not a currency amount in a supported format.
+    InvalidFormat,
+    /// The input has a single separator followed by exactly three digits, such
+    /// as `"1.234"`, which could be either a thousands separator or a decimal
+    /// separator.
+    Ambiguous,
+    /// The amount does not fit in a [`Brl`] value.
+    Overflow,
+}
+
+impl fmt::Display for ParseCurrencyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseCurrencyError::Empty => write!(f, "currency string is empty"),
+            ParseCurrencyError::InvalidFormat => write!(f, "currency string is invalid"),
+            ParseCurrencyError::Ambiguous => write!(
+                f,
+                "currency string is ambiguous: separator may be thousands or decimal"
+            ),
+            ParseCurrencyError::Overflow => write!(f, "currency amount is out of range"),
+        }
+    }
+}
+
+impl Error for ParseCurrencyError {}
+
+/// Parses a currency string in the exact format produced by [`format_currency`].
+///
+/// The input must use `.` as thousands separator with correctly sized groups,
+/// `,` followed by exactly two decimal places, and may be prefixed by `"R$ "`.
+/// A negative amount has its `-` sign right before the digits.
+///
+/// # Arguments
+///
+/// * `value` - The currency string to parse.
+///
+/// # Returns
+///
+/// The exact amount, or an error describing why the input was rejected.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::currency::{format_currency, parse_currency, Brl, ParseCurrencyError};
+///
+/// assert_eq!(parse_currency("R$ 1.234,56"), Ok(Brl::from_centavos(123_456)));
+/// assert_eq!(parse_currency("R$ -9.876,54"), Ok(Brl::from_centavos(-987_654)));
+/// assert_eq!(parse_currency("1.234,56"), Ok(Brl::from_centavos(123_456)));
+/// assert_eq!(parse_currency("1234,56"), Err(ParseCurrencyError::InvalidFormat));
+///
+/// let formatted = format_currency(Brl::from_centavos(100_000_001)).unwrap();
+/// assert_eq!(parse_currency(&formatted), Ok(Brl::from_centavos(100_000_001)));
+/// ```
+pub fn parse_currency(value: &str) -> Result<Brl, ParseCurrencyError> {
+    if value.trim().is_empty() {
+        return Err(ParseCurrencyError::Empty);
+    }
+
+    let body = value.strip_prefix("R$ ").unwrap_or(value);
+    let (negative, body) = match body.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, body),
+    };
+
+    let (integer, fraction) = body
+        .split_once(',')
+        .ok_or(ParseCurrencyError::InvalidFormat)?;
+
+    if fraction.len() != 2 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ParseCurrencyError::InvalidFormat);
+    }
+
+    let integer = remove_thousands_separators(integer, '.')
+        .filter(|digits| digits.len() == 1 || !digits.starts_with('0'))
+        .ok_or(ParseCurrencyError::InvalidFormat)?;
+
+    build_amount(negative, &integer, fraction)
+}
+
+/// Parses a currency string written in any common Brazilian notation.
+///
+/// This is the forgiving counterpart of [`parse_currency`], meant for
+/// spreadsheets, OCR output and user input. It accepts:
+///
+/// - an optional `R$` symbol, in upper or lower case, followed or not by
+///   spaces (including non-breaking spaces);
+/// - a sign before or after the symbol (`"R$-9.876,54"`, `"-R$ 9.876,54"`),
+///   a trailing minus (`"9.876,54-"`) or accounting parentheses (`"(1.234,56)"`);
+/// - amounts with or without thousands separators (`"1234,56"`);
+/// - one or two decimal places (`"1234,5"`) or none at all (`"1.234.567"`);
+/// - `.` as decimal separator when it cannot be a thousands separator
+///   (`"12.5"`, `"1,234.56"`).
+///
+/// Whitespace around the amount is ignored, but not inside it (`"12 34,56"`
+/// is invalid). A single separator followed by exactly three digits, such as
+/// `"1.234"` or `"1,234"`, is reported as [`ParseCurrencyError::Ambiguous`]
+/// instead of guessing, unless the digits before it are too many for a
+/// thousands group (`"1234.567"` is invalid).
+///
+/// # Arguments
+///
+/// * `value` - The currency string to parse.
+///
+/// # Returns
+///
+/// The exact amount, or an error describing why the input was rejected.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::currency::{parse_currency_lenient, Brl, ParseCurrencyError};
+///
+/// assert_eq!(parse_currency_lenient("R$ 1.234,56"), Ok(Brl::from_centavos(123_456)));
+/// assert_eq!(parse_currency_lenient("R$-9.876,54"), Ok(Brl::from_centavos(-987_654)));
+/// assert_eq!(parse_currency_lenient("(1.234,56)"), Ok(Brl::from_centavos(-123_456)));
+/// assert_eq!(parse_currency_lenient("1234,5"), Ok(Brl::from_centavos(123_450)));
+/// assert_eq!(parse_currency_lenient("1.234"), Err(ParseCurrencyError::Ambiguous));
+/// ```
+pub fn parse_currency_lenient(value: &str) -> Result<Brl, ParseCurrencyError> {
+    let mut body = value.trim();
+    if body.is_empty() {
+        return Err(ParseCurrencyError::Empty);
+    }
+
+    let mut negative = false;
+    let mut sign_seen = false;
+    let mut symbol_seen = false;
+
+    // Accounting notation: (1.234,56)
+    if let Some(inner) = body
+        .strip_prefix('(')
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        body = inner;
+        negative = true;
+        sign_seen = true;
+    }
+
+    // Sign and currency symbol may appear in either order
+    loop {
+        if !symbol_seen {
+            if let Some(rest) = strip_currency_symbol(body) {
+                body = rest.trim_start();
+                symbol_seen = true;
+                continue;
+            }
+        }
+
+        if !sign_seen {
+            if let Some(rest) = body.strip_prefix(['-', '\u{2212}']) {
+                body = rest;
+                negative = true;
+                sign_seen = true;
+                continue;
+            }
+            if let Some(rest) = body.strip_prefix('+') {
+                body = rest;
+                sign_seen = true;
+                continue;
+            }
+        }
+
+        break;
+    }
+
+    if !sign_seen {
+        if let Some(rest) = body.strip_suffix(['-', '\u{2212}']) {
+            body = rest;
+            negative = true;
+        }
+    }
+
+    if body.is_empty()
+        || !body
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '.' || c == ',')
+    {
+        return Err(ParseCurrencyError::InvalidFormat);
+    }
+
+    let last_dot = body.rfind('.');
+    let last_comma = body.rfind(',');
+    let dots = body.matches('.').count();
+    let commas = body.matches(',').count();
+
+    // Figure out which separator (if any) marks the decimal places
+    let decimal_separator = match (last_dot, last_comma) {
+        (None, None) => None,
+        (Some(dot), Some(comma)) => Some(if dot > comma { '.' } else { ',' }),
+        (Some(_), None) if dots > 1 => None,
+        (None, Some(_)) if commas > 1 => None,
+        (Some(position), None) | (None, Some(position)) => {
+            // Only a first group of 1 to 3 digits can precede a thousands separator
+            let decimals = body.len() - position - 1;
+            if decimals == 3 && (1..=3).contains(&position) {
+                return Err(ParseCurrencyError::Ambiguous);
+            }
+            Some(body.as_bytes()[position] as char)
+        }
+    };
+
+    let (integer, fraction) = match decimal_separator {
+        Some(separator) => body
+            .rsplit_once(separator)
+            .ok_or(ParseCurrencyError::InvalidFormat)?,
+        None => (body, ""),
+    };
+
+    if decimal_separator.is_some() && (fraction.is_empty() || fraction.len() > 2) {
+        return Err(ParseCurrencyError::InvalidFormat);
+    }
+
+    let thousands_separator = match decimal_separator {
+        Some('.') => ',',
+        Some(_) => '.',
+        None if dots > 0 => '.',
+        None => ',',
+    };
+
+    let integer = if integer.contains(thousands_separator) {
+        remove_thousands_separators(integer, thousands_separator)
+            .ok_or(ParseCurrencyError::InvalidFormat)?
+    } else if integer.is_empty() || !integer.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ParseCurrencyError::InvalidFormat);
+    } else {
+        integer.to_string()
+    };
+
+    build_amount(negative, &integer, fraction)
+}
+
+/// Strips a leading `R$` currency symbol, ignoring case (`r$` is accepted too).
+fn strip_currency_symbol(value: &str) -> Option<&str> {
+    let symbol = value.get(..2)?;
+    symbol.eq_ignore_ascii_case("R$").then(|| &value[2..])
+}
+
+/// Removes thousands separators from an integer, validating the digit groups.
+///
+/// The first group must have between 1 and 3 digits and every following
+/// group exactly 3 digits.
+fn remove_thousands_separators(value: &str, separator: char) -> Option<String> {
+    let mut digits = String::with_capacity(value.len());
+
+    for (index, group) in value.split(separator).enumerate() {
+        let valid_length = if index == 0 {
+            (1..=3).contains(&group.len())
+        } else {
+            group.len() == 3
+        };
+
+        if !valid_length || !group.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+
+        digits.push_str(group);
+    }
+
+    Some(digits)
+}
+
+/// Builds an amount from already validated integer and fraction digits.
+fn build_amount(negative: bool, integer: &str, fraction: &str) -> Result<Brl, ParseCurrencyError> {
+    let sign = if negative { "-" } else { "" };
+    let decimal = if fraction.is_empty() {
+        format!("{}{}", sign, integer)
+    } else {
+        format!("{}{}.{}", sign, integer, fraction)
+    };
+
+    parse_decimal(&decimal, None).ok_or(ParseCurrencyError::Overflow)
+}
+
+/// Rounding strategy applied when a value carries more precision than centavos.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::currency::{Brl, Rounding};
+///
+/// assert_eq!(Brl::from_decimal_str("0.125", Rounding::HalfEven), Some(Brl::from_centavos(12)));
+/// assert_eq!(Brl::from_decimal_str("0.125", Rounding::HalfUp), Some(Brl::from_centavos(13)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Round to the nearest centavo, ties to the even centavo (banker's rounding).
+    HalfEven,
+    /// Round to the nearest centavo, ties away from zero.
+    HalfUp,
+    /// Round to the nearest centavo, ties toward zero.
+    HalfDown,
+    /// Truncate toward zero.
+    Down,
+    /// Round away from zero.
+    Up,
+}
+
+/// Exact amount of Brazilian Reais, stored as an integer number of centavos.
+///
+/// All arithmetic is exact. The `checked_*` methods return `None` on overflow,
+/// while the `+`, `-` and unary `-` operators panic on overflow like the
+/// primitive integer types do. Amounts are serialized with serde as a decimal
+/// string such as `"1234.56"`.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::currency::{format_currency, Brl};
+///
+/// let price = Brl::from_centavos(123_456);
+/// assert_eq!(price.reais(), 1234);
+/// assert_eq!(price.centavos_part(), 56);
+/// assert_eq!(price.to_string(), "1234.56");
+/// assert_eq!(format_currency(price), Some("R$ 1.234,56".to_string()));
+///
+/// let total = price + Brl::from_centavos(44);
+/// assert_eq!(total, Brl::from_reais(1235).unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Brl {
+    centavos: i64,
+}
+
+impl Brl {
+    /// The zero amount.
+    pub const ZERO: Brl = Brl { centavos: 0 };
+
+    /// The largest representable amount.
+    pub const MAX: Brl = Brl { centavos: i64::MAX };
+
+    /// The smallest representable amount.
+    pub const MIN: Brl = Brl { centavos: i64::MIN };
+
+    /// Creates an amount from a number of centavos.
+    pub const fn from_centavos(centavos: i64) -> Self {
+        Brl { centavos }
+    }
+
+    /// Creates an amount from a whole number of reais.
+    ///
+    /// Returns `None` if the amount does not fit in the centavo range.
+    pub fn from_reais(reais: i64) -> Option<Self> {
+        reais.checked_mul(100).map(Brl::from_centavos)
+    }
+
+    /// Creates an amount from a floating point value.
+    ///
+    /// The value is read through its shortest decimal representation (the one
+    /// printed by `{}`), so `1.005` is treated as exactly `1.005` and not as
+    /// `1.00499999999999989...`, and then rounded to centavos.
+    ///
+    /// Returns `None` for NaN, infinities and values out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::currency::{Brl, Rounding};
+    ///
+    /// assert_eq!(Brl::from_f64(1.005, Rounding::HalfUp), Some(Brl::from_centavos(101)));
+    /// assert_eq!(Brl::from_f64(1.005, Rounding::HalfEven), Some(Brl::from_centavos(100)));
+    /// assert_eq!(Brl::from_f64(f64::NAN, Rounding::HalfUp), None);
+    /// ```
+    pub fn from_f64(value: f64, rounding: Rounding) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+
+        parse_decimal(&value.to_string(), Some(rounding))
+    }
+
+    /// Creates an amount from a plain decimal string such as `"1234.56"` or `"-0.5"`.
+    ///
+    /// The string may have an optional sign, must not contain thousands
+    /// separators and uses `.` as decimal separator. Extra decimal places are
+    /// rounded to centavos.
+    ///
+    /// Returns `None` if the string is not a decimal number or is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::currency::{Brl, Rounding};
+    ///
+    /// assert_eq!(Brl::from_decimal_str("10.5", Rounding::HalfEven), Some(Brl::from_centavos(1050)));
+    /// assert_eq!(Brl::from_decimal_str("2.675", Rounding::HalfEven), Some(Brl::from_centavos(268)));
+    /// assert_eq!(Brl::from_decimal_str("1.234,56", Rounding::HalfEven), None);
+    /// ```
+    pub fn from_decimal_str(value: &str, rounding: Rounding) -> Option<Self> {
+        parse_decimal(value, Some(rounding))
+    }
+
+    /// Returns the amount as a number of centavos.
+    pub const fn centavos(self) -> i64 {
+        self.centavos
+    }
+
+    /// Returns the whole reais of the amount, truncated toward zero.
+    ///
+    /// `Brl::from_centavos(-150).reais()` is `-1`.
+    pub const fn reais(self) -> i64 {
+        self.centavos / 100
+    }
+
+    /// Returns the centavos below one real, always between 0 and 99.
+    ///
+    /// `Brl::from_centavos(-150).centavos_part()` is `50`.
+    pub const fn centavos_part(self) -> i64 {
+        (self.centavos % 100).abs()
+    }
+
+    /// Returns `true` if the amount is zero.
+    pub const fn is_zero(self) -> bool {
+        self.centavos == 0
+    }
+
+    /// Returns `true` if the amount is lower than zero.
+    pub const fn is_negative(self) -> bool {
+        self.centavos < 0
+    }
+
+    /// Returns `true` if the amount is greater than zero.
+    pub const fn is_positive(self) -> bool {
+        self.centavos > 0
+    }
+
+    /// Converts the amount to a floating point number of reais.
+    ///
+    /// The result may lose precision for very large amounts.
+    pub fn to_f64(self) -> f64 {
+        self.centavos as f64 / 100.0
+    }
+
+    /// Adds two amounts, returning `None` on overflow.
+    pub fn checked_add(self, other: Brl) -> Option<Brl> {
+        self.centavos
+            .checked_add(other.centavos)
+            .map(Brl::from_centavos)
+    }
+
+    /// Subtracts two amounts, returning `None` on overflow.
+    pub fn checked_sub(self, other: Brl) -> Option<Brl> {
+        self.centavos
+            .checked_sub(other.centavos)
+            .map(Brl::from_centavos)
+    }
+
+    /// Negates the amount, returning `None` on overflow.
+    pub fn checked_neg(self) -> Option<Brl> {
+        self.centavos.checked_neg().map(Brl::from_centavos)
+    }
+
+    /// Returns the absolute value of the amount, or `None` on overflow.
+    pub fn checked_abs(self) -> Option<Brl> {
+        self.centavos.checked_abs().map(Brl::from_centavos)
+    }
+
+    /// Multiplies the amount by an integer, returning `None` on overflow.
+    pub fn checked_mul(self, factor: i64) -> Option<Brl> {
+        self.centavos.checked_mul(factor).map(Brl::from_centavos)
+    }
+
+    /// Multiplies the amount by the exact fraction `numerator / denominator`.
+    ///
+    /// Returns `None` if the denominator is zero or the result is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::currency::{Brl, Rounding};
+    ///
+    /// let amount = Brl::from_centavos(1000);
+    ///
+    /// // One third of R$ 10,00
+    /// assert_eq!(amount.checked_mul_ratio(1, 3, Rounding::HalfEven), Some(Brl::from_centavos(333)));
+    /// assert_eq!(amount.checked_mul_ratio(1, 3, Rounding::Up), Some(Brl::from_centavos(334)));
+    /// ```
+    pub fn checked_mul_ratio(
+        self,
+        numerator: i64,
+        denominator: i64,
+        rounding: Rounding,
+    ) -> Option<Brl> {
+        if denominator == 0 {
+            return None;
+        }
+
+        let product = self.centavos as i128 * numerator as i128;
+        let result = divide_rounded(product, denominator as i128, rounding);
+        i64::try_from(result).ok().map(Brl::from_centavos)
+    }
+
+    /// Multiplies the amount by a floating point factor, such as an interest rate.
+    ///
+    /// Returns `None` if the factor is not finite or the result is out of range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::currency::{Brl, Rounding};
+    ///
+    /// // 1,5% of R$ 1.000,00
+    /// let amount = Brl::from_reais(1000).unwrap();
+    /// assert_eq!(amount.checked_mul_f64(0.015, Rounding::HalfEven), Some(Brl::from_centavos(1500)));
+    /// ```
+    pub fn checked_mul_f64(self, factor: f64, rounding: Rounding) -> Option<Brl> {
+        let product = self.centavos as f64 * factor;
+        if !product.is_finite() {
+            return None;
+        }
+
+        let rounded = round_f64(product, rounding);
+        if rounded < i64::MIN as f64 || rounded >= i64::MAX as f64 {
+            return None;
+        }
+
+        Some(Brl::from_centavos(rounded as i64))
+    }
+
+    /// Splits the amount into `parts` amounts that add up exactly to the original.
+    ///
+    /// Leftover centavos are given one by one to the first parts, so no
+    /// centavo is lost. Returns `None` if `parts` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::currency::Brl;
+    ///
+    /// let parts = Brl::from_centavos(1000).allocate(3).unwrap();
+    /// assert_eq!(
+    ///     parts,
+    ///     vec![Brl::from_centavos(334), Brl::from_centavos(333), Brl::from_centavos(333)]
+    /// );
+    /// ```
+    pub fn allocate(self, parts: usize) -> Option<Vec<Brl>> {
+        if parts == 0 {
+            return None;
+        }
+
+        let parts_count = parts as i128;
+        let total = self.centavos as i128;
+        let base = total / parts_count;
+        let leftover = (total % parts_count).unsigned_abs() as usize;
+        let step = total.signum();
+
+        Some(
+            (0..parts)
+                .map(|index| {
+                    let extra = if index < leftover { step } else { 0 };
+                    Brl::from_centavos((base + extra) as i64)
+                })
+                .collect(),
+        )
+    }
+
+    /// Splits the amount proportionally to the given weights.
+    ///
+    /// The parts add up exactly to the original amount; leftover centavos go
+    /// to the parts with the largest rounding remainders (largest remainder
+    /// method). Returns `None` if there are no weights or they are all zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::currency::Brl;
+    ///
+    /// // R$ 100,00 split 50% / 30% / 20%
+    /// let parts = Brl::from_reais(100).unwrap().allocate_by(&[50, 30, 20]).unwrap();
+    /// assert_eq!(
+    ///     parts,
+    ///     vec![Brl::from_reais(50).unwrap(), Brl::from_reais(30).unwrap(), Brl::from_reais(20).unwrap()]
+    /// );
+    ///
+    /// // R$ 0,05 split in halves
+    /// let parts = Brl::from_centavos(5).allocate_by(&[1, 1]).unwrap();
+    /// assert_eq!(parts, vec![Brl::from_centavos(3), Brl::from_centavos(2)]);
+    /// ```
+    pub fn allocate_by(self, weights: &[u64]) -> Option<Vec<Brl>> {
+        let total_weight: u128 = weights.iter().map(|&weight| weight as u128).sum();
+        if total_weight == 0 {
+            return None;
+        }
+
+        let total = self.centavos as i128;
+        let magnitude = total.unsigned_abs();
+        let step = total.signum();
+
+        let mut shares = Vec::with_capacity(weights.len());
+        let mut remainders = Vec::with_capacity(weights.len());
+        let mut allocated: u128 = 0;
+
+        for (index, &weight) in weights.iter().enumerate() {
+            // magnitude < 2^63 and weight < 2^64, so the product fits in u128
+            let product = magnitude * weight as u128;
+            let share = product / total_weight;
+            allocated += share;
+            shares.push(share);
+            remainders.push((product % total_weight, index));
+        }
+
+        // Largest remainders first, ties broken by position
+        remainders.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        let leftover = (magnitude - allocated) as usize;
+        for &(_, index) in remainders.iter().take(leftover) {
+            shares[index] += 1;
+        }
+
+        Some(
+            shares
+                .into_iter()
+                .map(|share| Brl::from_centavos((share as i128 * step) as i64))
+                .collect(),
+        )
+    }
+}
+
+impl fmt::Display for Brl {
+    /// Writes the amount as a plain decimal string, e.g. `-1234.56`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.is_negative() { "-" } else { "" };
+        write!(
+            f,
+            "{}{}.{:02}",
+            sign,
+            self.reais().unsigned_abs(),
+            self.centavos_part()
+        )
+    }
+}
+
+impl Add for Brl {
+    type Output = Brl;
+
+    fn add(self, other: Brl) -> Brl {
+        self.checked_add(other)
+            .expect("attempt to add with overflow")
+    }
+}
+
+impl Sub for Brl {
+    type Output = Brl;
+
+    fn sub(self, other: Brl) -> Brl {
+        self.checked_sub(other)
+            .expect("attempt to subtract with overflow")
+    }
+}
+
+impl Neg for Brl {
+    type Output = Brl;
+
+    fn neg(self) -> Brl {
+        self.checked_neg().expect("attempt to negate with overflow")
+    }
+}
+
+impl AddAssign for Brl {
+    fn add_assign(&mut self, other: Brl) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Brl {
+    fn sub_assign(&mut self, other: Brl) {
+        *self = *self - other;
+    }
+}
+
+impl Sum for Brl {
+    fn sum<I: Iterator<Item = Brl>>(iter: I) -> Brl {
+        iter.fold(Brl::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Brl> for Brl {
+    fn sum<I: Iterator<Item = &'a Brl>>(iter: I) -> Brl {
+        iter.copied().sum()
+    }
+}
+
+impl Serialize for Brl {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Brl {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Brl, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        parse_decimal(&value, None)
+            .ok_or_else(|| de::Error::custom(format!("invalid BRL amount '{}'", value)))
+    }
+}
+
+/// Values that can be converted into an exact [`Brl`] amount.
+///
+/// This is implemented for `f64` (rounded half away from zero) and for
+/// [`Brl`] itself, so the formatting functions in this module accept both.
+pub trait IntoBrl {
+    /// Converts the value, returning `None` if it cannot be represented.
+    fn into_brl(self) -> Option<Brl>;
+}
+
+impl IntoBrl for Brl {
+    fn into_brl(self) -> Option<Brl> {
+        Some(self)
+    }
+}
+
+impl IntoBrl for &Brl {
+    fn into_brl(self) -> Option<Brl> {
+        Some(*self)
+    }
+}
+
+impl IntoBrl for f64 {
+    fn into_brl(self) -> Option<Brl> {
+        Brl::from_f64(self, Rounding::HalfUp)
+    }
+}
+
+/// Parses a plain decimal string into centavos.
+///
+/// When `rounding` is `None` the value must be exactly representable in
+/// centavos (extra decimal places must be zeros).
+fn parse_decimal(value: &str, rounding: Option<Rounding>) -> Option<Brl> {
+    let (negative, unsigned) = match value.as_bytes().first()? {
+        b'-' => (true, &value[1..]),
+        b'+' => (false, &value[1..]),
+        _ => (false, value),
+    };
+
+    let (integer_digits, fraction_digits) = match unsigned.split_once('.') {
+        Some((integer, fraction)) if !fraction.is_empty() => (integer, fraction),
+        Some(_) => return None,
+        None => (unsigned, ""),
+    };
+
+    if integer_digits.is_empty()
+        || !integer_digits.bytes().all(|b| b.is_ascii_digit())
+        || !fraction_digits.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let mut centavos: i128 = 0;
+    for digit in integer_digits.bytes() {
+        centavos = centavos
+            .checked_mul(10)?
+            .checked_add((digit - b'0') as i128)?;
+        if centavos > i64::MAX as i128 {
+            return None;
+        }
+    }
+
+    let (kept, rest) = fraction_digits.split_at(fraction_digits.len().min(2));
+    let kept_value = kept
+        .bytes()
+        .fold(0, |acc, digit| acc * 10 + (digit - b'0') as i128);
+    let kept_value = if kept.len() == 1 {
+        kept_value * 10
+    } else {
+        kept_value
+    };
+    centavos = centavos * 100 + kept_value;
+
+    if rest.bytes().any(|digit| digit != b'0') {
+        let rounding = rounding?;
+        let first = rest.as_bytes()[0];
+        let tail_is_zero = rest[1..].bytes().all(|digit| digit == b'0');
+        let round_away = match rounding {
+            Rounding::Down => false,
+            Rounding::Up => true,
+            Rounding::HalfUp => first >= b'5',
+            Rounding::HalfDown => first > b'5' || (first == b'5' && !tail_is_zero),
+            Rounding::HalfEven => {
+                first > b'5' || (first == b'5' && (!tail_is_zero || centavos % 2 == 1))
+            }
+        };
+        if round_away {
+            centavos += 1;
+        }
+    }
+
+    let centavos = if negative { -centavos } else { centavos };
+    i64::try_from(centavos).ok().map(Brl::from_centavos)
+}
+
+/// Divides two integers, rounding the quotient with the given strategy.
+fn divide_rounded(numerator: i128, denominator: i128, rounding: Rounding) -> i128 {
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+
+    if remainder == 0 {
+        return quotient;
+    }
+
+    // Direction away from zero for this quotient
+    let step = if (numerator < 0) == (denominator < 0) {
+        1
+    } else {
+        -1
+    };
+    let twice_remainder = remainder.unsigned_abs() * 2;
+    let divisor = denominator.unsigned_abs();
+
+    let round_away = match rounding {
+        Rounding::Down => false,
+        Rounding::Up => true,
+        Rounding::HalfUp => twice_remainder >= divisor,
+        Rounding::HalfDown => twice_remainder > divisor,
+        Rounding::HalfEven => {
+            twice_remainder > divisor || (twice_remainder == divisor && quotient % 2 != 0)
+        }
+    };
+
+    if round_away {
+        quotient + step
+    } else {
+        quotient
+    }
+}
+
+/// Rounds a floating point value to an integer with the given strategy.
+fn round_f64(value: f64, rounding: Rounding) -> f64 {
+    match rounding {
+        Rounding::Down => value.trunc(),
+        Rounding::Up => {
+            if value.fract() == 0.0 {
+                value
+            } else {
+                value.trunc() + value.signum()
+            }
+        }
+        Rounding::HalfUp => value.round(),
+        Rounding::HalfDown => {
+            if value.fract().abs() == 0.5 {
+                value.trunc()
+            } else {
+                value.round()
+            }
+        }
+        Rounding::HalfEven => value.round_ties_even(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_currency_positive_values() {
+        assert_eq!(format_currency(1234.56), Some("R$ 1.234,56".to_string()));
+        assert_eq!(
+            format_currency(123236.70),
+            Some("R$ 123.236,70".to_string())
+        );
+        assert_eq!(format_currency(1259.03), Some("R$ 1.259,03".to_string()));
+    }
+
+    #[test]
+    fn test_format_currency_zero() {
+        assert_eq!(format_currency(0.0), Some("R$ 0,00".to_string()));
+    }
+
+    #[test]
+    fn test_format_currency_negative_values() {
+        assert_eq!(
+            format_currency(-123236.70),
+            Some("R$ -123.236,70".to_string())
+        );
+        assert_eq!(format_currency(-9876.54), Some("R$ -9.876,54".to_string()));
+    }
+
+    #[test]
+    fn test_format_currency_rounding() {
+        // Test decimal rounding
+        assert_eq!(
+            format_currency(123236.7676),
+            Some("R$ 123.236,77".to_string())
+        );
+        assert_eq!(format_currency(10.555), Some("R$ 10,56".to_string()));
+    }
+
+    #[test]
+    fn test_format_currency_small_values() {
+        assert_eq!(format_currency(0.01), Some("R$ 0,01".to_string()));
+        assert_eq!(format_currency(0.99), Some("R$ 0,99".to_string()));
+        assert_eq!(format_currency(5.50), Some("R$ 5,50".to_string()));
+    }
+
+    #[test]
+    fn test_format_currency_large_values() {
+        assert_eq!(
+            format_currency(1_000_000.00),
+            Some("R$ 1.000.000,00".to_string())
+        );
+        assert_eq!(
+            format_currency(999_999_999.99),
+            Some("R$ 999.999.999,99".to_string())
+        );
+    }
+
+    #[test]
+    fn test_format_currency_invalid_values() {
+        assert_eq!(format_currency(f64::INFINITY), None);
+        assert_eq!(format_currency(f64::NEG_INFINITY), None);
+        assert_eq!(format_currency(f64::NAN), None);
+    }
+
+    #[test]
+    fn test_format_with_thousands_separator() {
+        assert_eq!(format_with_thousands_separator(0), "0");
+        assert_eq!(format_with_thousands_separator(123), "123");
+        assert_eq!(format_with_thousands_separator(1234), "1.234");
+        assert_eq!(format_with_thousands_separator(123456), "123.456");
+        assert_eq!(format_with_thousands_separator(1234567), "1.234.567");
+        assert_eq!(format_with_thousands_separator(999999999), "999.999.999");
+    }
+
+    #[test]
+    fn test_format_currency_edge_cases() {
+        // Very small positive value
+        assert_eq!(format_currency(0.001), Some("R$ 0,00".to_string()));
+
+        // Very small negative value - rounds to 0 but we treat as positive zero
+        assert_eq!(format_currency(-0.001), Some("R$ 0,00".to_string()));
+
+        // Values that need rounding
+        assert_eq!(format_currency(1.234), Some("R$ 1,23".to_string()));
+        assert_eq!(format_currency(1.235), Some("R$ 1,24".to_string()));
+    }
+
+    #[test]
+    fn test_number_to_words_basic() {
+        assert_eq!(number_to_words(0), "zero");
+        assert_eq!(number_to_words(1), "um");
+        assert_eq!(number_to_words(5), "cinco");
+        assert_eq!(number_to_words(10), "dez");
+        assert_eq!(number_to_words(15), "quinze");
         assert_eq!(number_to_words(20), "vinte");
         assert_eq!(number_to_words(25), "vinte e cinco");
         assert_eq!(number_to_words(99), "noventa e nove");
     }
 
     #[test]
-    fn test_number_to_words_hundreds() {
-        assert_eq!(number_to_words(100), "cem");
-        assert_eq!(number_to_words(101), "cento e um");
-        assert_eq!(number_to_words(200), "duzentos");
-        assert_eq!(number_to_words(555), "quinhentos e cinquenta e cinco");
-        assert_eq!(number_to_words(999), "novecentos e noventa e nove");
+    fn test_number_to_words_hundreds() {
+        assert_eq!(number_to_words(100), "cem");
+        assert_eq!(number_to_words(101), "cento e um");
+        assert_eq!(number_to_words(200), "duzentos");
+        assert_eq!(number_to_words(555), "quinhentos e cinquenta e cinco");
+        assert_eq!(number_to_words(999), "novecentos e noventa e nove");
+    }
+
+    #[test]
+    fn test_number_to_words_thousands() {
+        assert_eq!(number_to_words(1000), "mil");
+        assert_eq!(number_to_words(1001), "mil e um");
+        assert_eq!(number_to_words(1111), "mil, cento e onze");
+        assert_eq!(number_to_words(2000), "dois mil");
+        assert_eq!(number_to_words(2500), "dois mil e quinhentos");
+        assert_eq!(number_to_words(10000), "dez mil");
+        assert_eq!(number_to_words(100000), "cem mil");
+    }
+
+    #[test]
+    fn test_number_to_words_large_numbers() {
+        assert_eq!(number_to_words(1_000_000), "um milhão");
+        assert_eq!(number_to_words(2_000_000), "dois milhões");
+        assert_eq!(number_to_words(1_000_000_000), "um bilhão");
+        assert_eq!(number_to_words(2_000_000_000), "dois bilhões");
+        assert_eq!(number_to_words(1_000_000_000_000), "um trilhão");
+        assert_eq!(number_to_words(1_000_000_000_000_000), "um quatrilhão");
+    }
+
+    #[test]
+    fn test_number_to_words_negative() {
+        assert_eq!(number_to_words(-1), "menos um");
+        assert_eq!(number_to_words(-42), "menos quarenta e dois");
+        assert_eq!(number_to_words(-1000), "menos mil");
+    }
+
+    #[test]
+    fn test_convert_real_to_text_basic() {
+        assert_eq!(convert_real_to_text(0.0), "zero real");
+        assert_eq!(convert_real_to_text(1.0), "um real");
+        assert_eq!(convert_real_to_text(2.0), "dois reais");
+        assert_eq!(convert_real_to_text(10.0), "dez reais");
+        assert_eq!(convert_real_to_text(100.0), "cem reais");
+    }
+
+    #[test]
+    fn test_convert_real_to_text_with_cents() {
+        assert_eq!(convert_real_to_text(0.01), "um centavo");
+        assert_eq!(convert_real_to_text(0.50), "cinquenta centavos");
+        assert_eq!(convert_real_to_text(1.50), "um real e cinquenta centavos");
+        assert_eq!(
+            convert_real_to_text(2.50),
+            "dois reais e cinquenta centavos"
+        );
+        assert_eq!(
+            convert_real_to_text(10.99),
+            "dez reais e noventa e nove centavos"
+        );
+    }
+
+    #[test]
+    fn test_convert_real_to_text_large_values() {
+        assert_eq!(convert_real_to_text(1000.0), "mil reais");
+        assert_eq!(convert_real_to_text(1000000.0), "um milhão de reais");
+        assert_eq!(convert_real_to_text(2000000.0), "dois milhões de reais");
+        assert_eq!(convert_real_to_text(1000000000.0), "um bilhão de reais");
+        assert_eq!(
+            convert_real_to_text(1000000.50),
+            "um milhão de reais e cinquenta centavos"
+        );
+    }
+
+    #[test]
+    fn test_convert_real_to_text_negative() {
+        assert_eq!(convert_real_to_text(-1.0), "menos um real");
+        assert_eq!(
+            convert_real_to_text(-2.50),
+            "menos dois reais e cinquenta centavos"
+        );
+        assert_eq!(convert_real_to_text(-100.0), "menos cem reais");
+    }
+
+    #[test]
+    fn test_convert_real_to_text_complex() {
+        assert_eq!(
+            convert_real_to_text(1111.11),
+            "mil, cento e onze reais e onze centavos"
+        );
+        assert_eq!(convert_real_to_text(123456.78), "cento e vinte e três mil, quatrocentos e cinquenta e seis reais e setenta e oito centavos");
+    }
+
+    #[test]
+    fn test_format_currency_uses_decimal_representation() {
+        // 1.005 * 100.0 is 100.49999999999999 in binary floating point
+        assert_eq!(format_currency(1.005), Some("R$ 1,01".to_string()));
+        assert_eq!(format_currency(-1.005), Some("R$ -1,01".to_string()));
+        assert_eq!(format_currency(0.285), Some("R$ 0,29".to_string()));
+    }
+
+    #[test]
+    fn test_format_currency_brl() {
+        assert_eq!(
+            format_currency(Brl::from_centavos(123_456)),
+            Some("R$ 1.234,56".to_string())
+        );
+        assert_eq!(
+            format_currency(Brl::from_centavos(-5)),
+            Some("R$ -0,05".to_string())
+        );
+        assert_eq!(
+            format_currency(Brl::MAX),
+            Some("R$ 92.233.720.368.547.758,07".to_string())
+        );
+        assert_eq!(
+            format_currency(Brl::MIN),
+            Some("R$ -92.233.720.368.547.758,08".to_string())
+        );
+    }
+
+    #[test]
+    fn test_convert_real_to_text_brl() {
+        assert_eq!(convert_real_to_text(Brl::ZERO), "zero real");
+        assert_eq!(convert_real_to_text(Brl::from_centavos(1)), "um centavo");
+        assert_eq!(
+            convert_real_to_text(Brl::from_centavos(-250)),
+            "menos dois reais e cinquenta centavos"
+        );
+        assert_eq!(convert_real_to_text(f64::NAN), "");
+    }
+
+    #[test]
+    fn test_brl_parts() {
+        let amount = Brl::from_centavos(-150);
+        assert_eq!(amount.centavos(), -150);
+        assert_eq!(amount.reais(), -1);
+        assert_eq!(amount.centavos_part(), 50);
+        assert!(amount.is_negative());
+        assert!(!amount.is_positive());
+        assert!(Brl::ZERO.is_zero());
+        assert_eq!(Brl::from_reais(12), Some(Brl::from_centavos(1200)));
+        assert_eq!(Brl::from_reais(i64::MAX), None);
+        assert_eq!(Brl::from_centavos(1999).to_f64(), 19.99);
+    }
+
+    #[test]
+    fn test_brl_from_f64() {
+        assert_eq!(
+            Brl::from_f64(1234.56, Rounding::HalfUp),
+            Some(Brl::from_centavos(123_456))
+        );
+        assert_eq!(
+            Brl::from_f64(2.675, Rounding::HalfUp),
+            Some(Brl::from_centavos(268))
+        );
+        assert_eq!(
+            Brl::from_f64(2.665, Rounding::HalfEven),
+            Some(Brl::from_centavos(266))
+        );
+        assert_eq!(Brl::from_f64(-0.001, Rounding::HalfUp), Some(Brl::ZERO));
+        assert_eq!(Brl::from_f64(f64::INFINITY, Rounding::HalfUp), None);
+        assert_eq!(Brl::from_f64(1e30, Rounding::HalfUp), None);
     }
 
     #[test]
-    fn test_number_to_words_thousands() {
-        assert_eq!(number_to_words(1000), "mil");
-        assert_eq!(number_to_words(1001), "mil e um");
-        assert_eq!(number_to_words(1111), "mil, cento e onze");
-        assert_eq!(number_to_words(2000), "dois mil");
-        assert_eq!(number_to_words(2500), "dois mil e quinhentos");
-        assert_eq!(number_to_words(10000), "dez mil");
-        assert_eq!(number_to_words(100000), "cem mil");
+    fn test_brl_from_decimal_str_rounding_modes() {
+        let cases = [
+            ("1.005", Rounding::HalfEven, 100),
+            ("1.015", Rounding::HalfEven, 102),
+            ("1.0051", Rounding::HalfEven, 101),
+            ("1.005", Rounding::HalfUp, 101),
+            ("1.005", Rounding::HalfDown, 100),
+            ("1.0051", Rounding::HalfDown, 101),
+            ("1.009", Rounding::Down, 100),
+            ("1.001", Rounding::Up, 101),
+            ("-1.005", Rounding::HalfUp, -101),
+            ("-1.001", Rounding::Up, -101),
+            ("-1.009", Rounding::Down, -100),
+            ("-1.015", Rounding::HalfEven, -102),
+        ];
+
+        for (input, rounding, expected) in cases {
+            assert_eq!(
+                Brl::from_decimal_str(input, rounding),
+                Some(Brl::from_centavos(expected)),
+                "{} with {:?}",
+                input,
+                rounding
+            );
+        }
     }
 
     #[test]
-    fn test_number_to_words_large_numbers() {
-        assert_eq!(number_to_words(1_000_000), "um milhão");
-        assert_eq!(number_to_words(2_000_000), "dois milhões");
-        assert_eq!(number_to_words(1_000_000_000), "um bilhão");
-        assert_eq!(number_to_words(2_000_000_000), "dois bilhões");
-        assert_eq!(number_to_words(1_000_000_000_000), "um trilhão");
-        assert_eq!(number_to_words(1_000_000_000_000_000), "um quatrilhão");
+    fn test_brl_from_decimal_str_invalid() {
+        assert_eq!(Brl::from_decimal_str("", Rounding::HalfEven), None);
+        assert_eq!(Brl::from_decimal_str("-", Rounding::HalfEven), None);
+        assert_eq!(Brl::from_decimal_str(".5", Rounding::HalfEven), None);
+        assert_eq!(Brl::from_decimal_str("5.", Rounding::HalfEven), None);
+        assert_eq!(Brl::from_decimal_str("1,50", Rounding::HalfEven), None);
+        assert_eq!(Brl::from_decimal_str("1e3", Rounding::HalfEven), None);
+        assert_eq!(
+            Brl::from_decimal_str("92233720368547758.08", Rounding::HalfEven),
+            None
+        );
+        assert_eq!(
+            Brl::from_decimal_str("-92233720368547758.08", Rounding::HalfEven),
+            Some(Brl::MIN)
+        );
     }
 
     #[test]
-    fn test_number_to_words_negative() {
-        assert_eq!(number_to_words(-1), "menos um");
-        assert_eq!(number_to_words(-42), "menos quarenta e dois");
-        assert_eq!(number_to_words(-1000), "menos mil");
+    fn test_brl_arithmetic() {
+        let a = Brl::from_centavos(1050);
+        let b = Brl::from_centavos(275);
+
+        assert_eq!(a + b, Brl::from_centavos(1325));
+        assert_eq!(a - b, Brl::from_centavos(775));
+        assert_eq!(-a, Brl::from_centavos(-1050));
+        assert_eq!([a, b, b].iter().sum::<Brl>(), Brl::from_centavos(1600));
+
+        let mut c = a;
+        c += b;
+        c -= Brl::from_centavos(25);
+        assert_eq!(c, Brl::from_centavos(1300));
+
+        assert_eq!(Brl::MAX.checked_add(Brl::from_centavos(1)), None);
+        assert_eq!(Brl::MIN.checked_sub(Brl::from_centavos(1)), None);
+        assert_eq!(Brl::MIN.checked_neg(), None);
+        assert_eq!(Brl::MIN.checked_abs(), None);
+        assert_eq!(a.checked_mul(3), Some(Brl::from_centavos(3150)));
+        assert_eq!(Brl::MAX.checked_mul(2), None);
     }
 
     #[test]
-    fn test_convert_real_to_text_basic() {
-        assert_eq!(convert_real_to_text(0.0), "zero real");
-        assert_eq!(convert_real_to_text(1.0), "um real");
-        assert_eq!(convert_real_to_text(2.0), "dois reais");
-        assert_eq!(convert_real_to_text(10.0), "dez reais");
-        assert_eq!(convert_real_to_text(100.0), "cem reais");
+    #[should_panic(expected = "overflow")]
+    fn test_brl_add_overflow_panics() {
+        let _ = Brl::MAX + Brl::from_centavos(1);
     }
 
     #[test]
-    fn test_convert_real_to_text_with_cents() {
-        assert_eq!(convert_real_to_text(0.01), "um centavo");
-        assert_eq!(convert_real_to_text(0.50), "cinquenta centavos");
-        assert_eq!(convert_real_to_text(1.50), "um real e cinquenta centavos");
+    fn test_brl_checked_mul_ratio() {
+        let amount = Brl::from_centavos(100);
+
         assert_eq!(
-            convert_real_to_text(2.50),
-            "dois reais e cinquenta centavos"
+            amount.checked_mul_ratio(1, 8, Rounding::HalfEven),
+            Some(Brl::from_centavos(12))
         );
         assert_eq!(
-            convert_real_to_text(10.99),
-            "dez reais e noventa e nove centavos"
+            amount.checked_mul_ratio(1, 8, Rounding::HalfUp),
+            Some(Brl::from_centavos(13))
+        );
+        assert_eq!(
+            amount.checked_mul_ratio(-1, 8, Rounding::HalfUp),
+            Some(Brl::from_centavos(-13))
+        );
+        assert_eq!(
+            amount.checked_mul_ratio(2, 3, Rounding::Down),
+            Some(Brl::from_centavos(66))
         );
+        assert_eq!(amount.checked_mul_ratio(1, 0, Rounding::HalfEven), None);
+        assert_eq!(Brl::MAX.checked_mul_ratio(3, 2, Rounding::HalfEven), None);
     }
 
     #[test]
-    fn test_convert_real_to_text_large_values() {
-        assert_eq!(convert_real_to_text(1000.0), "mil reais");
-        assert_eq!(convert_real_to_text(1000000.0), "um milhão de reais");
-        assert_eq!(convert_real_to_text(2000000.0), "dois milhões de reais");
-        assert_eq!(convert_real_to_text(1000000000.0), "um bilhão de reais");
+    fn test_brl_checked_mul_f64() {
+        let amount = Brl::from_centavos(1001);
+
         assert_eq!(
-            convert_real_to_text(1000000.50),
-            "um milhão de reais e cinquenta centavos"
+            amount.checked_mul_f64(0.5, Rounding::HalfEven),
+            Some(Brl::from_centavos(500))
+        );
+        assert_eq!(
+            amount.checked_mul_f64(0.5, Rounding::HalfUp),
+            Some(Brl::from_centavos(501))
+        );
+        assert_eq!(
+            amount.checked_mul_f64(0.5, Rounding::HalfDown),
+            Some(Brl::from_centavos(500))
+        );
+        assert_eq!(
+            amount.checked_mul_f64(-0.5, Rounding::Up),
+            Some(Brl::from_centavos(-501))
         );
+        assert_eq!(amount.checked_mul_f64(f64::NAN, Rounding::HalfEven), None);
+        assert_eq!(Brl::MAX.checked_mul_f64(2.0, Rounding::HalfEven), None);
     }
 
     #[test]
-    fn test_convert_real_to_text_negative() {
-        assert_eq!(convert_real_to_text(-1.0), "menos um real");
+    fn test_brl_allocate() {
+        let parts = Brl::from_centavos(-1000).allocate(3).unwrap();
         assert_eq!(
-            convert_real_to_text(-2.50),
-            "menos dois reais e cinquenta centavos"
+            parts,
+            vec![
+                Brl::from_centavos(-334),
+                Brl::from_centavos(-333),
+                Brl::from_centavos(-333)
+            ]
         );
-        assert_eq!(convert_real_to_text(-100.0), "menos cem reais");
+
+        let parts = Brl::from_centavos(2).allocate(4).unwrap();
+        assert_eq!(parts.iter().sum::<Brl>(), Brl::from_centavos(2));
+        assert_eq!(parts[3], Brl::ZERO);
+
+        assert_eq!(Brl::from_centavos(100).allocate(0), None);
+        assert_eq!(Brl::MAX.allocate(7).unwrap().iter().sum::<Brl>(), Brl::MAX);
     }
 
     #[test]
-    fn test_convert_real_to_text_complex() {
+    fn test_brl_allocate_by() {
+        let parts = Brl::from_centavos(100).allocate_by(&[1, 1, 1]).unwrap();
         assert_eq!(
-            convert_real_to_text(1111.11),
-            "mil, cento e onze reais e onze centavos"
+            parts,
+            vec![
+                Brl::from_centavos(34),
+                Brl::from_centavos(33),
+                Brl::from_centavos(33)
+            ]
         );
-        assert_eq!(convert_real_to_text(123456.78), "cento e vinte e três mil, quatrocentos e cinquenta e seis reais e setenta e oito centavos");
+
+        // The largest remainder wins the leftover centavo
+        let parts = Brl::from_centavos(10).allocate_by(&[1, 2]).unwrap();
+        assert_eq!(parts, vec![Brl::from_centavos(3), Brl::from_centavos(7)]);
+
+        let parts = Brl::from_centavos(-10).allocate_by(&[0, 1, 2]).unwrap();
+        assert_eq!(
+            parts,
+            vec![Brl::ZERO, Brl::from_centavos(-3), Brl::from_centavos(-7)]
+        );
+
+        assert_eq!(Brl::from_centavos(10).allocate_by(&[]), None);
+        assert_eq!(Brl::from_centavos(10).allocate_by(&[0, 0]), None);
+    }
+
+    #[test]
+    fn test_brl_display() {
+        assert_eq!(Brl::from_centavos(123_456).to_string(), "1234.56");
+        assert_eq!(Brl::from_centavos(-5).to_string(), "-0.05");
+        assert_eq!(Brl::ZERO.to_string(), "0.00");
+        assert_eq!(Brl::MIN.to_string(), "-92233720368547758.08");
+    }
+
+    #[test]
+    fn test_brl_serde() {
+        let amount = Brl::from_centavos(-123_456);
+        let json = serde_json::to_string(&amount).unwrap();
+        assert_eq!(json, "\"-1234.56\"");
+        assert_eq!(serde_json::from_str::<Brl>(&json).unwrap(), amount);
+
+        assert_eq!(
+            serde_json::from_str::<Brl>("\"10.500\"").unwrap(),
+            Brl::from_centavos(1050)
+        );
+        assert!(serde_json::from_str::<Brl>("\"10.505\"").is_err());
+        assert!(serde_json::from_str::<Brl>("\"R$ 10,50\"").is_err());
+        assert!(serde_json::from_str::<Brl>("10.5").is_err());
+    }
+
+    #[test]
+    fn test_parse_currency_strict() {
+        assert_eq!(parse_currency("R$ 0,00"), Ok(Brl::ZERO));
+        assert_eq!(parse_currency("R$ 0,01"), Ok(Brl::from_centavos(1)));
+        assert_eq!(parse_currency("R$ 123,45"), Ok(Brl::from_centavos(12_345)));
+        assert_eq!(
+            parse_currency("R$ 1.000.000,00"),
+            Ok(Brl::from_centavos(100_000_000))
+        );
+        assert_eq!(parse_currency("-0,50"), Ok(Brl::from_centavos(-50)));
+    }
+
+    #[test]
+    fn test_parse_currency_strict_rejects_loose_input() {
+        let invalid = [
+            "R$1.234,56",
+            "R$-9.876,54",
+            "(1.234,56)",
+            "1.234,5",
+            "1.234,567",
+            "1.234",
+            "12.34,56",
+            "1.2345,67",
+            "01,00",
+            "R$  1,00",
+            " R$ 1,00",
+            "1,00 ",
+            "R$ +1,00",
+        ];
+
+        for input in invalid {
+            assert_eq!(
+                parse_currency(input),
+                Err(ParseCurrencyError::InvalidFormat),
+                "{}",
+                input
+            );
+        }
+
+        assert_eq!(parse_currency(""), Err(ParseCurrencyError::Empty));
+        assert_eq!(parse_currency("   "), Err(ParseCurrencyError::Empty));
+        assert_eq!(
+            parse_currency("R$ 92.233.720.368.547.758,08"),
+            Err(ParseCurrencyError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_parse_currency_round_trip() {
+        let amounts = [
+            0,
+            1,
+            -1,
+            99,
+            100,
+            123_456,
+            -987_654,
+            100_000_000,
+            i64::MAX,
+            i64::MIN,
+        ];
+
+        for centavos in amounts {
+            let amount = Brl::from_centavos(centavos);
+            let formatted = format_currency(amount).unwrap();
+            assert_eq!(parse_currency(&formatted), Ok(amount), "{}", formatted);
+            assert_eq!(
+                parse_currency_lenient(&formatted),
+                Ok(amount),
+                "{}",
+                formatted
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_currency_lenient() {
+        let cases = [
+            ("R$ 1.234,56", 123_456),
+            ("1.234,56", 123_456),
+            ("R$-9.876,54", -987_654),
+            ("-R$ 9.876,54", -987_654),
+            ("R$ -9.876,54", -987_654),
+            ("9.876,54-", -987_654),
+            ("(1.234,56)", -123_456),
+            ("(R$ 1.234,56)", -123_456),
+            ("1234,5", 123_450),
+            ("1234,56", 123_456),
+            ("1234", 123_400),
+            ("1.234.567", 123_456_700),
+            ("1.234.567,8", 123_456_780),
+            ("12.5", 1_250),
+            ("0,99", 99),
+            ("+R$ 10,00", 1_000),
+            ("r$10", 1_000),
+            ("R$\u{a0}1.234,56", 123_456),
+            (" R$  1.234,56 ", 123_456),
+            ("\t-R$\u{a0}10,00\n", -1_000),
+            ("\u{2212}5,00", -500),
+            ("1,234.56", 123_456),
+            ("1,234,567", 123_456_700),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(
+                parse_currency_lenient(input),
+                Ok(Brl::from_centavos(expected)),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_currency_lenient_ambiguous() {
+        assert_eq!(
+            parse_currency_lenient("1.234"),
+            Err(ParseCurrencyError::Ambiguous)
+        );
+        assert_eq!(
+            parse_currency_lenient("R$ 1,234"),
+            Err(ParseCurrencyError::Ambiguous)
+        );
+        assert_eq!(
+            parse_currency_lenient("-0.500"),
+            Err(ParseCurrencyError::Ambiguous)
+        );
+    }
+
+    #[test]
+    fn test_parse_currency_lenient_invalid() {
+        let invalid = [
+            "R$",
+            "-",
+            "()",
+            "abc",
+            "12a,00",
+            "1.234,567",
+            "12,3456",
+            "1.23.456",
+            "1.234,56.78",
+            "1,234,56",
+            "10,",
+            ",50",
+            "--10",
+            "(-10)",
+            "R$R$10",
+            "10-R$",
+            "USD 10",
+            "12 34,56",
+            "R$ 1 234,56",
+            "- 10,00",
+            "1234.567",
+            "12345,678",
+        ];
+
+        for input in invalid {
+            assert_eq!(
+                parse_currency_lenient(input),
+                Err(ParseCurrencyError::InvalidFormat),
+                "{}",
+                input
+            );
+        }
+
+        assert_eq!(parse_currency_lenient(""), Err(ParseCurrencyError::Empty));
+        assert_eq!(
+            parse_currency_lenient("99999999999999999999"),
+            Err(ParseCurrencyError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_words_to_number_basic() {
+        assert_eq!(words_to_number("zero"), Some(0));
+        assert_eq!(words_to_number("um"), Some(1));
+        assert_eq!(words_to_number("dez"), Some(10));
+        assert_eq!(words_to_number("quinze"), Some(15));
+        assert_eq!(words_to_number("vinte e cinco"), Some(25));
+        assert_eq!(words_to_number("cem"), Some(100));
+        assert_eq!(words_to_number("cento e um"), Some(101));
+        assert_eq!(words_to_number("novecentos e noventa e nove"), Some(999));
+        assert_eq!(words_to_number("mil"), Some(1000));
+        assert_eq!(words_to_number("um mil"), Some(1000));
+        assert_eq!(words_to_number("mil, cento e onze"), Some(1111));
+    }
+
+    #[test]
+    fn test_words_to_number_variants() {
+        assert_eq!(words_to_number("tres"), Some(3));
+        assert_eq!(words_to_number("TRÊS"), Some(3));
+        assert_eq!(words_to_number("quatorze"), Some(14));
+        assert_eq!(words_to_number("catorze"), Some(14));
+        assert_eq!(words_to_number("cinqüenta"), Some(50));
+        assert_eq!(words_to_number("duzentas e duas"), Some(202));
+        assert_eq!(words_to_number("vinte  e   um"), Some(21));
+        assert_eq!(words_to_number("vinte um"), Some(21));
+        assert_eq!(
+            words_to_number("dois milhões trezentos mil"),
+            Some(2_300_000)
+        );
+        assert_eq!(words_to_number("um milhao"), Some(1_000_000));
+        assert_eq!(
+            words_to_number("um quatrilhão e um"),
+            Some(1_000_000_000_000_001)
+        );
+        assert_eq!(
+            words_to_number("nove quintilhões"),
+            Some(9_000_000_000_000_000_000)
+        );
+    }
+
+    #[test]
+    fn test_words_to_number_invalid() {
+        let invalid = [
+            "",
+            "e",
+            "um dois",
+            "vinte trinta",
+            "quinze um",
+            "cem e um",
+            "cento duzentos",
+            "um vinte",
+            "mil mil",
+            "mil milhões",
+            "milhão",
+            "zero um",
+            "um zero",
+            "menos",
+            "dez reais",
+            "9",
+        ];
+
+        for input in invalid {
+            assert_eq!(words_to_number(input), None, "{}", input);
+        }
+
+        // Overflows i64
+        assert_eq!(words_to_number("dez quintilhões"), None);
+    }
+
+    #[test]
+    fn test_words_to_number_round_trip() {
+        let mut values: Vec<i64> = (0..=2_000).collect();
+        values.extend([
+            10_001,
+            99_999,
+            100_100,
+            999_999,
+            1_000_001,
+            1_234_567,
+            20_000_300,
+            1_000_000_000,
+            1_002_003_004,
+            999_999_999_999,
+            1_000_000_000_000_000,
+            999_999_999_999_999_999,
+            i64::MAX,
+            -1,
+            -1_234_567,
+            i64::MIN,
+        ]);
+
+        for value in values {
+            assert_eq!(
+                words_to_number(&number_to_words(value)),
+                Some(value),
+                "{}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_convert_text_to_real() {
+        assert_eq!(convert_text_to_real("zero real"), Some(Brl::ZERO));
+        assert_eq!(
+            convert_text_to_real("um real"),
+            Some(Brl::from_centavos(100))
+        );
+        assert_eq!(
+            convert_text_to_real("Um Real e Cinquenta Centavos"),
+            Some(Brl::from_centavos(150))
+        );
+        assert_eq!(
+            convert_text_to_real("um centavo"),
+            Some(Brl::from_centavos(1))
+        );
+        assert_eq!(
+            convert_text_to_real("cinquenta centavos de real"),
+            Some(Brl::from_centavos(50))
+        );
+        assert_eq!(
+            convert_text_to_real("dois milhões de reais e dez centavos"),
+            Some(Brl::from_centavos(200_000_010))
+        );
+        assert_eq!(
+            convert_text_to_real("um milhão e um reais"),
+            Some(Brl::from_centavos(100_000_100))
+        );
+        assert_eq!(
+            convert_text_to_real("um milhão e um de reais"),
+            Some(Brl::from_centavos(100_000_100))
+        );
+        assert_eq!(
+            convert_text_to_real("menos tres reais"),
+            Some(Brl::from_centavos(-300))
+        );
+    }
+
+    #[test]
+    fn test_convert_text_to_real_invalid() {
+        let invalid = [
+            "",
+            "reais",
+            "dez",
+            "dez reais cinquenta",
+            "cem centavos",
+            "dez centavos e um real",
+            "um real e centavos",
+            "menos menos um real",
+            "um dólar",
+        ];
+
+        for input in invalid {
+            assert_eq!(convert_text_to_real(input), None, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_convert_text_to_real_round_trip() {
+        let amounts = [
+            1,
+            99,
+            100,
+            101,
+            250,
+            -250,
+            111_111,
+            12_345_678,
+            100_000_000,
+            100_000_050,
+            123_456_789_012,
+        ];
+
+        for centavos in amounts {
+            let amount = Brl::from_centavos(centavos);
+            assert_eq!(
+                convert_text_to_real(&convert_real_to_text(amount)),
+                Some(amount),
+                "{}",
+                centavos
+            );
+        }
+    }
+
+    #[test]
+    fn test_number_to_words_beyond_quadrillions() {
+        assert_eq!(
+            number_to_words(1_000_000_000_000_000_000i64),
+            "um quintilhão"
+        );
+        assert_eq!(
+            number_to_words(i64::MAX),
+            "nove quintilhões, duzentos e vinte e três quatrilhões, trezentos e setenta e dois \
+             trilhões, trinta e seis bilhões, oitocentos e cinquenta e quatro milhões, setecentos \
+             e setenta e cinco mil, oitocentos e sete"
+        );
+        assert_eq!(
+            number_to_words(i64::MIN),
+            "menos nove quintilhões, duzentos e vinte e três quatrilhões, trezentos e setenta e \
+             dois trilhões, trinta e seis bilhões, oitocentos e cinquenta e quatro milhões, \
+             setecentos e setenta e cinco mil, oitocentos e oito"
+        );
+        assert_eq!(
+            number_to_words_with_gender(u64::MAX.into(), Gender::Masculine),
+            "dezoito quintilhões, quatrocentos e quarenta e seis quatrilhões, setecentos e \
+             quarenta e quatro trilhões, setenta e três bilhões, setecentos e nove milhões, \
+             quinhentos e cinquenta e um mil, seiscentos e quinze"
+        );
+        let masculine = |n: i128| number_to_words_with_gender(n, Gender::Masculine);
+        assert_eq!(masculine(10i128.pow(21)), "um sextilhão");
+        assert_eq!(masculine(2 * 10i128.pow(24)), "dois septilhões");
+        assert_eq!(masculine(10i128.pow(27) + 1), "um octilhão e um");
+        assert_eq!(masculine(10i128.pow(30)), "um nonilhão");
+        assert_eq!(masculine(10i128.pow(33)), "um decilhão");
+        assert_eq!(
+            masculine(i128::MAX).split(',').next(),
+            Some("cento e setenta undecilhões")
+        );
+        assert!(masculine(i128::MIN).starts_with("menos cento e setenta undecilhões"));
+    }
+
+    #[test]
+    fn test_number_to_words_feminine() {
+        let feminine = |n: i128| number_to_words_with_gender(n, Gender::Feminine);
+
+        assert_eq!(feminine(0), "zero");
+        assert_eq!(feminine(1), "uma");
+        assert_eq!(feminine(2), "duas");
+        assert_eq!(feminine(3), "três");
+        assert_eq!(feminine(12), "doze");
+        assert_eq!(feminine(21), "vinte e uma");
+        assert_eq!(feminine(22), "vinte e duas");
+        assert_eq!(feminine(100), "cem");
+        assert_eq!(feminine(101), "cento e uma");
+        assert_eq!(feminine(200), "duzentas");
+        assert_eq!(feminine(532), "quinhentas e trinta e duas");
+        assert_eq!(feminine(1_000), "mil");
+        assert_eq!(feminine(2_000), "duas mil");
+        assert_eq!(feminine(200_000), "duzentas mil");
+        assert_eq!(feminine(1_000_000), "um milhão");
+        assert_eq!(feminine(2_000_002), "dois milhões e duas");
+        assert_eq!(feminine(202_000_000), "duzentos e dois milhões");
+        assert_eq!(feminine(-2), "menos duas");
+    }
+
+    #[test]
+    fn test_number_to_words_with_gender_masculine_matches_default() {
+        for n in (0..5_000).chain([1_234_567, 2_002_002_002, i64::MAX]) {
+            assert_eq!(
+                number_to_words_with_gender(n as i128, Gender::Masculine),
+                number_to_words(n)
+            );
+        }
+    }
+
+    #[test]
+    fn test_words_to_number_feminine_round_trip() {
+        for n in [1i64, 2, 22, 200, 2_222, 202_202, 2_002_002] {
+            let words = number_to_words_with_gender(n as i128, Gender::Feminine);
+            assert_eq!(words_to_number(&words), Some(n), "{}", words);
+        }
+    }
+
+    #[test]
+    fn test_ordinal_to_words() {
+        let ordinal = |n: u64| ordinal_to_words(n, Gender::Masculine).unwrap();
+
+        assert_eq!(ordinal(1), "primeiro");
+        assert_eq!(ordinal(2), "segundo");
+        assert_eq!(ordinal(3), "terceiro");
+        assert_eq!(ordinal(7), "sétimo");
+        assert_eq!(ordinal(10), "décimo");
+        assert_eq!(ordinal(11), "décimo primeiro");
+        assert_eq!(ordinal(21), "vigésimo primeiro");
+        assert_eq!(ordinal(49), "quadragésimo nono");
+        assert_eq!(ordinal(99), "nonagésimo nono");
+        assert_eq!(ordinal(100), "centésimo");
+        assert_eq!(ordinal(101), "centésimo primeiro");
+        assert_eq!(ordinal(345), "trecentésimo quadragésimo quinto");
+        assert_eq!(ordinal(999), "nongentésimo nonagésimo nono");
+        assert_eq!(ordinal(1_000), "milésimo");
+        assert_eq!(ordinal(1_001), "milésimo primeiro");
+        assert_eq!(ordinal(2_000), "segundo milésimo");
+        assert_eq!(ordinal(1_234), "milésimo ducentésimo trigésimo quarto");
+        assert_eq!(ordinal(10_000), "décimo milésimo");
+        assert_eq!(ordinal(1_000_000), "milionésimo");
+        assert_eq!(ordinal(3_000_000_000), "terceiro bilionésimo");
+        assert_eq!(ordinal(1_000_000_000_000_000_000), "quintilionésimo");
+        assert!(ordinal(u64::MAX).starts_with("décimo oitavo quintilionésimo"));
+        assert_eq!(ordinal_to_words(0, Gender::Masculine), None);
+    }
+
+    #[test]
+    fn test_ordinal_to_words_feminine() {
+        let ordinal = |n: u64| ordinal_to_words(n, Gender::Feminine).unwrap();
+
+        assert_eq!(ordinal(1), "primeira");
+        assert_eq!(ordinal(21), "vigésima primeira");
+        assert_eq!(ordinal(100), "centésima");
+        assert_eq!(ordinal(2_000), "segunda milésima");
+        assert_eq!(ordinal(1_000_001), "milionésima primeira");
+        assert_eq!(ordinal_to_words(0, Gender::Feminine), None);
+    }
+
+    #[test]
+    fn test_ordinal_abbreviation() {
+        assert_eq!(ordinal_abbreviation(1, Gender::Masculine), "1º");
+        assert_eq!(ordinal_abbreviation(1, Gender::Feminine), "1ª");
+        assert_eq!(ordinal_abbreviation(21, Gender::Feminine), "21ª");
+        assert_eq!(ordinal_abbreviation(1_000, Gender::Masculine), "1000º");
+    }
+
+    #[test]
+    fn test_convert_real_to_text_de_from_million() {
+        assert_eq!(
+            convert_real_to_text(999_999.0),
+            "novecentos e noventa e nove mil, novecentos e noventa e nove reais"
+        );
+        assert_eq!(convert_real_to_text(1_000_001.0), "um milhão e um de reais");
+        assert_eq!(
+            convert_real_to_text(1_500_000.0),
+            "um milhão, quinhentos mil de reais"
+        );
+        assert_eq!(
+            convert_real_to_text(3_000_000_000.0),
+            "três bilhões de reais"
+        );
+    }
+
+    #[test]
+    fn test_convert_amount_to_text_currencies() {
+        assert_eq!(convert_amount_to_text(0, &Unit::USD), "zero dólar");
+        assert_eq!(convert_amount_to_text(1, &Unit::USD), "um centavo");
+        assert_eq!(convert_amount_to_text(100, &Unit::USD), "um dólar");
+        assert_eq!(
+            convert_amount_to_text(100_010, &Unit::USD),
+            "mil dólares e dez centavos"
+        );
+        assert_eq!(
+            convert_amount_to_text(-250, &Unit::EUR),
+            "menos dois euros e cinquenta centavos"
+        );
+        assert_eq!(
+            convert_amount_to_text(100_000_000, &Unit::EUR),
+            "um milhão de euros"
+        );
+        assert_eq!(convert_amount_to_text(200, &Unit::GBP), "duas libras");
+        assert_eq!(
+            convert_amount_to_text(20_002, &Unit::GBP),
+            "duzentas libras e dois pence"
+        );
+        assert_eq!(
+            convert_amount_to_text(200_000_000, &Unit::GBP),
+            "dois milhões de libras"
+        );
+    }
+
+    #[test]
+    fn test_convert_amount_to_text_matches_convert_real_to_text() {
+        for centavos in [0, 1, 99, 100, 150, -250, 111_111, 100_000_050, i64::MAX] {
+            assert_eq!(
+                convert_amount_to_text(centavos as i128, &Unit::BRL),
+                convert_real_to_text(Brl::from_centavos(centavos))
+            );
+        }
+    }
+
+    #[test]
+    fn test_convert_amount_to_text_custom_units() {
+        let kilos = Unit::new(UnitName::new("quilo", "quilos", Gender::Masculine))
+            .with_minor(UnitName::new("grama", "gramas", Gender::Masculine), 1000);
+        assert_eq!(convert_amount_to_text(1_000, &kilos), "um quilo");
+        assert_eq!(
+            convert_amount_to_text(2_500, &kilos),
+            "dois quilos e quinhentos gramas"
+        );
+        assert_eq!(convert_amount_to_text(1, &kilos), "um grama");
+
+        let square_meters = Unit::new(UnitName::new(
+            "metro quadrado",
+            "metros quadrados",
+            Gender::Masculine,
+        ));
+        assert_eq!(
+            convert_amount_to_text(1, &square_meters),
+            "um metro quadrado"
+        );
+        assert_eq!(
+            convert_amount_to_text(3_000_000, &square_meters),
+            "três milhões de metros quadrados"
+        );
+
+        let times = Unit::new(UnitName::new("vez", "vezes", Gender::Feminine).without_de());
+        assert_eq!(
+            convert_amount_to_text(2_000_000, &times),
+            "dois milhões vezes"
+        );
+
+        let installments = Unit::new(UnitName::new("parcela", "parcelas", Gender::Feminine));
+        assert_eq!(convert_amount_to_text(1, &installments), "uma parcela");
+        assert_eq!(
+            convert_amount_to_text(222, &installments),
+            "duzentas e vinte e duas parcelas"
+        );
+
+        let name = String::from("hectare");
+        let plural = format!("{}s", name);
+        let hectares = Unit::new(UnitName::new(&name, &plural, Gender::Masculine));
+        assert_eq!(convert_amount_to_text(2, &hectares), "dois hectares");
     }
 }
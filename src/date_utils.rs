@@ -1,6 +1,11 @@
-use crate::currency::number_to_words;
+use crate::currency::{number_to_words, number_to_words_with_gender, words_to_number, Gender};
 /// Date utilities for Brazilian dates.
-use chrono::{Datelike, NaiveDate, Weekday};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::sync::OnceLock;
 
 /// Get the month name in Brazilian Portuguese.
 ///
@@ -42,6 +47,7 @@ fn get_month_name(month: u32) -> &'static str {
 /// # Returns
 ///
 /// A string with the date written out in Brazilian Portuguese, or None if the date is invalid.
+/// Use [`date_to_text`] to write a `NaiveDate` in other styles.
 ///
 /// # Examples
 ///
@@ -69,25 +75,701 @@ pub fn convert_date_to_text(date: &str) -> Option<String> {
     let month: u32 = parts[1].parse().ok()?;
     let year: i32 = parts[2].parse().ok()?;
 
-    // Validate the date
-    NaiveDate::from_ymd_opt(year, month, day)?;
+    let date = NaiveDate::from_ymd_opt(year, month, day)?;
 
-    // Convert day to text
-    let day_str = if day == 1 {
-        "Primeiro".to_string()
-    } else {
-        let mut text = number_to_words(day as i64);
-        // Capitalize first letter
-        if let Some(first_char) = text.chars().next() {
-            text = first_char.to_uppercase().to_string() + &text[first_char.len_utf8()..];
+    Some(date_to_text(date, &DateStyle::Plain))
+}
+
+/// Style of a date written out in words by [`date_to_text`].
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::date_utils::{date_to_text, DateStyle};
+/// use chrono::NaiveDate;
+///
+/// let date = NaiveDate::from_ymd_opt(1990, 8, 15).unwrap();
+///
+/// assert_eq!(
+///     date_to_text(date, &DateStyle::Plain),
+///     "Quinze de agosto de mil, novecentos e noventa"
+/// );
+/// assert_eq!(date_to_text(date, &DateStyle::Long), "15 de agosto de 1990");
+/// assert_eq!(
+///     date_to_text(date, &DateStyle::Notarial),
+///     "aos quinze dias do mês de agosto do ano de mil novecentos e noventa"
+/// );
+/// assert_eq!(
+///     date_to_text(date, &DateStyle::WithWeekday),
+///     "15 de agosto de 1990 (quarta-feira)"
+/// );
+/// assert_eq!(
+///     date_to_text(date, &DateStyle::WithCity { city: "São Paulo".to_string(), weekday: true }),
+///     "São Paulo, 15 de agosto de 1990 (quarta-feira)"
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum DateStyle {
+    /// Day and year in words, as [`convert_date_to_text`]:
+    /// "Quinze de agosto de mil, novecentos e noventa".
+    #[default]
+    Plain,
+    /// Day and year in digits, with "1º" for the first day: "15 de agosto de 1990".
+    Long,
+    /// The style of deeds and minutes: "aos quinze dias do mês de agosto do
+    /// ano de mil novecentos e noventa".
+    Notarial,
+    /// The [`Long`](Self::Long) style followed by the weekday:
+    /// "15 de agosto de 1990 (quarta-feira)".
+    WithWeekday,
+    /// The [`Long`](Self::Long) style preceded by a place, as in the closing
+    /// of letters and petitions: "São Paulo, 15 de agosto de 1990", optionally
+    /// followed by the weekday.
+    WithCity { city: String, weekday: bool },
+}
+
+/// Writes a date out in words in the given style.
+///
+/// See [`DateStyle`] for examples of each style.
+///
+/// # Arguments
+///
+/// * `date` - The date to be written.
+/// * `style` - The style of the text.
+///
+/// # Returns
+///
+/// The date in Brazilian Portuguese.
+pub fn date_to_text(date: NaiveDate, style: &DateStyle) -> String {
+    let month = get_month_name(date.month());
+
+    match style {
+        DateStyle::Plain => {
+            let day = match date.day() {
+                1 => "Primeiro".to_string(),
+                day => capitalize(&number_to_words(day as i64)),
+            };
+            format!(
+                "{} de {} de {}",
+                day,
+                month,
+                number_to_words(date.year() as i64)
+            )
+        }
+        DateStyle::Long => long_date(date),
+        DateStyle::Notarial => {
+            let day = match date.day() {
+                1 => "ao primeiro dia".to_string(),
+                day => format!("aos {} dias", number_to_words(day as i64)),
+            };
+            let year = number_to_words(date.year() as i64).replace(", ", " ");
+            format!("{} do mês de {} do ano de {}", day, month, year)
+        }
+        DateStyle::WithWeekday => {
+            format!("{} ({})", long_date(date), get_weekday_name(date.weekday()))
+        }
+        DateStyle::WithCity { city, weekday } => {
+            let text = format!("{}, {}", city, long_date(date));
+            if *weekday {
+                format!("{} ({})", text, get_weekday_name(date.weekday()))
+            } else {
+                text
+            }
+        }
+    }
+}
+
+/// Writes a time of day out in words, as in formal documents.
+///
+/// Seconds are ignored, and so are minutes when zero.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::date_utils::time_to_text;
+/// use chrono::NaiveTime;
+///
+/// let time = |hour, minute| NaiveTime::from_hms_opt(hour, minute, 0).unwrap();
+///
+/// assert_eq!(time_to_text(time(14, 30)), "às catorze horas e trinta minutos");
+/// assert_eq!(time_to_text(time(1, 1)), "à uma hora e um minuto");
+/// assert_eq!(time_to_text(time(0, 0)), "à zero hora");
+/// assert_eq!(time_to_text(time(22, 0)), "às vinte e duas horas");
+/// ```
+pub fn time_to_text(time: NaiveTime) -> String {
+    let hour = time.hour();
+    let hours = match hour {
+        0 => "à zero hora".to_string(),
+        1 => "à uma hora".to_string(),
+        _ => format!(
+            "às {} horas",
+            number_to_words_with_gender(hour as i128, Gender::Feminine)
+        ),
+    };
+
+    match time.minute() {
+        0 => hours,
+        1 => format!("{} e um minuto", hours),
+        minute => format!("{} e {} minutos", hours, number_to_words(minute as i64)),
+    }
+}
+
+/// Writes a date and time out in words: the date in the given style followed
+/// by the time as written by [`time_to_text`].
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::date_utils::{datetime_to_text, DateStyle};
+/// use chrono::NaiveDate;
+///
+/// let datetime = NaiveDate::from_ymd_opt(1990, 8, 15)
+///     .unwrap()
+///     .and_hms_opt(14, 30, 0)
+///     .unwrap();
+///
+/// assert_eq!(
+///     datetime_to_text(datetime, &DateStyle::Notarial),
+///     "aos quinze dias do mês de agosto do ano de mil novecentos e noventa, \
+///      às catorze horas e trinta minutos"
+/// );
+/// ```
+pub fn datetime_to_text(datetime: NaiveDateTime, style: &DateStyle) -> String {
+    format!(
+        "{}, {}",
+        date_to_text(datetime.date(), style),
+        time_to_text(datetime.time())
+    )
+}
+
+/// Writes a date with the day and year in digits: "15 de agosto de 1990".
+fn long_date(date: NaiveDate) -> String {
+    let day = match date.day() {
+        1 => "1º".to_string(),
+        day => day.to_string(),
+    };
+    format!(
+        "{} de {} de {}",
+        day,
+        get_month_name(date.month()),
+        date.year()
+    )
+}
+
+/// Get the weekday name in Brazilian Portuguese.
+fn get_weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "segunda-feira",
+        Weekday::Tue => "terça-feira",
+        Weekday::Wed => "quarta-feira",
+        Weekday::Thu => "quinta-feira",
+        Weekday::Fri => "sexta-feira",
+        Weekday::Sat => "sábado",
+        Weekday::Sun => "domingo",
+    }
+}
+
+/// Uppercases the first letter of a text.
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// A month of a year, such as the month-year of a reference date ("ago/1990").
+///
+/// Ordered chronologically and displayed as `MM/AAAA`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MonthYear {
+    year: i32,
+    month: u32,
+}
+
+impl MonthYear {
+    /// Creates a month-year, or returns `None` if the month is not 1-12 or the
+    /// year is out of the range supported by `chrono`.
+    pub fn new(year: i32, month: u32) -> Option<Self> {
+        NaiveDate::from_ymd_opt(year, month, 1)?;
+        Some(MonthYear { year, month })
+    }
+
+    /// The year.
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// The month (1-12).
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    /// The first day of the month.
+    pub fn first_day(&self) -> NaiveDate {
+        NaiveDate::from_ymd_opt(self.year, self.month, 1).expect("validated on creation")
+    }
+
+    /// The last day of the month.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::date_utils::MonthYear;
+    /// use chrono::NaiveDate;
+    ///
+    /// let february = MonthYear::new(2024, 2).unwrap();
+    /// assert_eq!(february.last_day(), NaiveDate::from_ymd_opt(2024, 2, 29).unwrap());
+    /// assert_eq!(february.to_string(), "02/2024");
+    /// ```
+    pub fn last_day(&self) -> NaiveDate {
+        let first_day = self.first_day();
+        first_day
+            .checked_add_months(chrono::Months::new(1))
+            .and_then(|next| next.pred_opt())
+            .unwrap_or(NaiveDate::MAX)
+    }
+}
+
+impl fmt::Display for MonthYear {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02}/{:04}", self.month, self.year)
+    }
+}
+
+/// A date read by [`DateParser`]: a full date or only a month and year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParsedDate {
+    /// A full date, e.g. "15/08/1990" or "15 de agosto de 1990".
+    Date(NaiveDate),
+    /// A month and year, e.g. "ago/1990" or "agosto de 1990".
+    MonthYear(MonthYear),
+}
+
+/// Parser of dates written in Brazilian formats, the inverse of
+/// [`convert_date_to_text`].
+///
+/// Accepted inputs, with accents and case ignored:
+///
+/// - Numeric dates in day-month-year order, separated by `/`, `.` or `-`:
+///   "15/08/1990", "15.08.1990", "15-08-90", "1/8/1990". ISO dates
+///   ("1990-08-15") are also accepted.
+/// - Dates in words, with the day and year in digits or words: "15 de agosto
+///   de 1990", "Primeiro de janeiro de dois mil e vinte", "1º de jan. de 2020".
+///   Text before the day, such as a city or weekday ("São Paulo, 15 de agosto
+///   de 1990"), is ignored.
+/// - Month-years: "08/1990", "ago/1990", "agosto/90", "agosto de 1990".
+///
+/// Two-digit years are placed in a century by the pivot: years below it are
+/// in the 2000s, the others in the 1900s.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::date_utils::{DateParser, MonthYear, ParsedDate};
+/// use chrono::NaiveDate;
+///
+/// let parser = DateParser::new().with_century_pivot(30);
+///
+/// assert_eq!(parser.parse_date("15/08/29"), NaiveDate::from_ymd_opt(2029, 8, 15));
+/// assert_eq!(parser.parse_date("15/08/30"), NaiveDate::from_ymd_opt(1930, 8, 15));
+/// assert_eq!(
+///     parser.parse("ago/1990"),
+///     Some(ParsedDate::MonthYear(MonthYear::new(1990, 8).unwrap()))
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DateParser {
+    century_pivot: u32,
+}
+
+impl Default for DateParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DateParser {
+    /// Creates a parser with a century pivot of 50: "49" is 2049 and "50" is 1950.
+    pub fn new() -> Self {
+        DateParser { century_pivot: 50 }
+    }
+
+    /// Sets the century pivot for two-digit years (0-100): years below it are
+    /// in the 2000s, the others in the 1900s. A pivot of 0 puts every
+    /// two-digit year in the 1900s and 100 every one in the 2000s.
+    pub fn with_century_pivot(self, pivot: u32) -> Self {
+        DateParser {
+            century_pivot: pivot.min(100),
+        }
+    }
+
+    /// Parses a full date or a month-year.
+    ///
+    /// # Returns
+    ///
+    /// The parsed value, or `None` if the text is not in a supported format
+    /// or is not a valid date.
+    pub fn parse(&self, text: &str) -> Option<ParsedDate> {
+        let normalized = normalize_date_text(text);
+        self.parse_numeric(&normalized)
+            .or_else(|| self.parse_words(&normalized))
+    }
+
+    /// Parses a full date, rejecting month-years.
+    pub fn parse_date(&self, text: &str) -> Option<NaiveDate> {
+        match self.parse(text)? {
+            ParsedDate::Date(date) => Some(date),
+            ParsedDate::MonthYear(_) => None,
+        }
+    }
+
+    /// Parses a month-year, rejecting full dates.
+    pub fn parse_month_year(&self, text: &str) -> Option<MonthYear> {
+        match self.parse(text)? {
+            ParsedDate::MonthYear(month_year) => Some(month_year),
+            ParsedDate::Date(_) => None,
+        }
+    }
+
+    /// Parses "15/08/1990", "1990-08-15", "15/ago/1990", "08/1990" or "ago/1990".
+    fn parse_numeric(&self, text: &str) -> Option<ParsedDate> {
+        let separator = text.chars().find(|c| matches!(c, '/' | '.' | '-'))?;
+        let parts: Vec<&str> = text.split(separator).map(str::trim).collect();
+
+        match parts.as_slice() {
+            [year, month, day] if year.len() == 4 && is_digits(year) => {
+                let date = NaiveDate::from_ymd_opt(
+                    year.parse().ok()?,
+                    parse_number_part(month, 2)?,
+                    parse_number_part(day, 2)?,
+                )?;
+                Some(ParsedDate::Date(date))
+            }
+            [day, month, year] => {
+                let date = NaiveDate::from_ymd_opt(
+                    self.parse_year_digits(year)?,
+                    parse_month(month)?,
+                    parse_number_part(day, 2)?,
+                )?;
+                Some(ParsedDate::Date(date))
+            }
+            [month, year] => {
+                let month_year =
+                    MonthYear::new(self.parse_year_digits(year)?, parse_month(month)?)?;
+                Some(ParsedDate::MonthYear(month_year))
+            }
+            _ => None,
+        }
+    }
+
+    /// Parses "[prefix] <day> de <month> de <year>" or "<month> de <year>".
+    fn parse_words(&self, text: &str) -> Option<ParsedDate> {
+        let text = text.trim_end_matches('.').replace(',', " ");
+        let tokens: Vec<&str> = text.split_whitespace().collect();
+
+        // The month is the first month name followed by "de" and a year:
+        // "dez" may also be the day ("dez de setembro")
+        let (index, month, year) = tokens.iter().enumerate().find_map(|(index, token)| {
+            let month = month_from_name(token)?;
+            if tokens.get(index + 1) != Some(&"de") {
+                return None;
+            }
+            let year = self.parse_year_words(&tokens[index + 2..])?;
+            Some((index, month, year))
+        })?;
+
+        if index >= 2 && tokens[index - 1] == "de" {
+            let day_tokens = &tokens[..index - 1];
+            let day =
+                (0..day_tokens.len()).find_map(|start| parse_day_words(&day_tokens[start..]))?;
+            NaiveDate::from_ymd_opt(year, month, day).map(ParsedDate::Date)
+        } else {
+            MonthYear::new(year, month).map(ParsedDate::MonthYear)
+        }
+    }
+
+    /// Reads a year with 4 digits, or 2 digits placed by the century pivot.
+    fn parse_year_digits(&self, text: &str) -> Option<i32> {
+        if !is_digits(text) {
+            return None;
+        }
+
+        let year: u32 = text.parse().ok()?;
+        match text.len() {
+            4 => Some(year as i32),
+            2 if year < self.century_pivot => Some(2000 + year as i32),
+            2 => Some(1900 + year as i32),
+            _ => None,
+        }
+    }
+
+    /// Reads a year in digits or in words.
+    fn parse_year_words(&self, tokens: &[&str]) -> Option<i32> {
+        match tokens {
+            [] => None,
+            [digits] if is_digits(digits) => self.parse_year_digits(digits),
+            _ => words_to_number(&tokens.join(" "))
+                .filter(|year| *year > 0)
+                .and_then(|year| i32::try_from(year).ok()),
+        }
+    }
+}
+
+/// Parses a date written in a Brazilian format with the default [`DateParser`].
+///
+/// Accepts numeric dates ("15/08/1990", "15.08.90") and dates in words ("15 de
+/// agosto de 1990", "Primeiro de janeiro de dois mil e vinte"). See
+/// [`DateParser`] for all formats and to change the century of two-digit
+/// years.
+///
+/// # Arguments
+///
+/// * `text` - The date to be parsed.
+///
+/// # Returns
+///
+/// The date, or `None` if the text is not a valid date in a supported format.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::date_utils::{convert_date_to_text, parse_date};
+/// use chrono::NaiveDate;
+///
+/// assert_eq!(parse_date("15 de agosto de 1990"), NaiveDate::from_ymd_opt(1990, 8, 15));
+/// assert_eq!(
+///     parse_date("Primeiro de janeiro de dois mil e vinte"),
+///     NaiveDate::from_ymd_opt(2020, 1, 1)
+/// );
+/// assert_eq!(parse_date("15/08/90"), NaiveDate::from_ymd_opt(1990, 8, 15));
+/// assert_eq!(parse_date("15.08.1990"), NaiveDate::from_ymd_opt(1990, 8, 15));
+///
+/// let text = convert_date_to_text("29/02/2024").unwrap();
+/// assert_eq!(parse_date(&text), NaiveDate::from_ymd_opt(2024, 2, 29));
+///
+/// assert_eq!(parse_date("31/02/2024"), None);
+/// ```
+pub fn parse_date(text: &str) -> Option<NaiveDate> {
+    DateParser::new().parse_date(text)
+}
+
+/// Parses a month-year written in a Brazilian format with the default [`DateParser`].
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::date_utils::{parse_month_year, MonthYear};
+///
+/// assert_eq!(parse_month_year("ago/1990"), MonthYear::new(1990, 8));
+/// assert_eq!(parse_month_year("Agosto de 1990"), MonthYear::new(1990, 8));
+/// assert_eq!(parse_month_year("08/1990"), MonthYear::new(1990, 8));
+/// assert_eq!(parse_month_year("15/08/1990"), None);
+/// ```
+pub fn parse_month_year(text: &str) -> Option<MonthYear> {
+    DateParser::new().parse_month_year(text)
+}
+
+/// Lowercases a date text and removes accents and surrounding spaces.
+fn normalize_date_text(text: &str) -> String {
+    use unicode_normalization::UnicodeNormalization;
+
+    text.trim()
+        .nfd()
+        .filter(|c| !unicode_normalization::char::is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Whether the text is made only of ASCII digits.
+fn is_digits(text: &str) -> bool {
+    !text.is_empty() && text.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+/// Reads a number of at most `max_len` digits.
+fn parse_number_part(text: &str, max_len: usize) -> Option<u32> {
+    (is_digits(text) && text.len() <= max_len)
+        .then(|| text.parse().ok())
+        .flatten()
+}
+
+/// Reads a month given by number or by name.
+fn parse_month(text: &str) -> Option<u32> {
+    parse_number_part(text, 2).or_else(|| month_from_name(text))
+}
+
+/// Reads a month name or its three-letter abbreviation ("ago", "ago.").
+fn month_from_name(text: &str) -> Option<u32> {
+    let name = text.strip_suffix('.').unwrap_or(text);
+
+    (1..=12).find(|&month| {
+        let full = normalize_date_text(get_month_name(month));
+        name == full || (name.len() == 3 && full.starts_with(name))
+    })
+}
+
+/// Reads a day in digits ("15", "1º") or in words ("quinze", "primeiro").
+fn parse_day_words(tokens: &[&str]) -> Option<u32> {
+    let day = match tokens {
+        ["primeiro"] => 1,
+        [digits] if is_digits(digits.trim_end_matches(['º', '°', 'o'])) => {
+            parse_number_part(digits.trim_end_matches(['º', '°', 'o']), 2)?
         }
-        text
+        _ => u32::try_from(words_to_number(&tokens.join(" "))?).ok()?,
     };
 
-    let month_name = get_month_name(month);
-    let year_str = number_to_words(year as i64);
+    (1..=31).contains(&day).then_some(day)
+}
+
+/// An epidemiological week (semana epidemiológica) of the Ministry of Health.
+///
+/// Epidemiological weeks run from Sunday to Saturday, unlike ISO weeks, which
+/// start on Monday. Week 1 ends on the first Saturday of January with at
+/// least four days of the new year (the first Saturday on or after January
+/// 4), so the first days of January may belong to the last week of the
+/// previous year and the last days of December to week 1 of the next one. A
+/// year has 52 or 53 weeks.
+///
+/// Ordered chronologically and displayed as `SE WW/AAAA`.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::date_utils::EpiWeek;
+/// use chrono::NaiveDate;
+///
+/// // January 1, 2022 was a Saturday: it closes the last week of 2021
+/// let week = EpiWeek::from_date(NaiveDate::from_ymd_opt(2022, 1, 1).unwrap());
+/// assert_eq!((week.year(), week.week()), (2021, 52));
+///
+/// let week = EpiWeek::new(2022, 1).unwrap();
+/// assert_eq!(week.first_day(), NaiveDate::from_ymd_opt(2022, 1, 2).unwrap());
+/// assert_eq!(week.last_day(), NaiveDate::from_ymd_opt(2022, 1, 8).unwrap());
+/// assert_eq!(week.to_string(), "SE 01/2022");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EpiWeek {
+    year: i32,
+    week: u32,
+}
+
+impl EpiWeek {
+    /// Creates an epidemiological week, or returns `None` if the week is not
+    /// between 1 and the number of weeks of the year.
+    pub fn new(year: i32, week: u32) -> Option<Self> {
+        (1..=weeks_in_epi_year(year))
+            .contains(&week)
+            .then_some(EpiWeek { year, week })
+    }
+
+    /// The epidemiological week containing a date.
+    pub fn from_date(date: NaiveDate) -> Self {
+        let day = date.num_days_from_ce();
+        let year = (date.year() - 1..=date.year() + 1)
+            .rev()
+            .find(|&year| epi_year_start(year) <= day)
+            .expect("the previous epidemiological year starts before the date");
+        let week = (day - epi_year_start(year)) / 7 + 1;
+
+        EpiWeek {
+            year,
+            week: week as u32,
+        }
+    }
+
+    /// The epidemiological year, which may differ from the calendar year of
+    /// the days at the turn of the year.
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// The week of the year (1-53).
+    pub fn week(&self) -> u32 {
+        self.week
+    }
+
+    /// The Sunday starting the week.
+    pub fn first_day(&self) -> NaiveDate {
+        let day = epi_year_start(self.year) + (self.week as i32 - 1) * 7;
+        NaiveDate::from_num_days_from_ce_opt(day).unwrap_or(NaiveDate::MIN)
+    }
 
-    Some(format!("{} de {} de {}", day_str, month_name, year_str))
+    /// The Saturday ending the week.
+    pub fn last_day(&self) -> NaiveDate {
+        let day = epi_year_start(self.year) + self.week as i32 * 7 - 1;
+        NaiveDate::from_num_days_from_ce_opt(day).unwrap_or(NaiveDate::MAX)
+    }
+}
+
+impl fmt::Display for EpiWeek {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SE {:02}/{:04}", self.week, self.year)
+    }
+}
+
+/// Returns the epidemiological week (semana epidemiológica) of a date.
+///
+/// See [`EpiWeek`] for the rules.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::date_utils::epi_week;
+/// use chrono::NaiveDate;
+///
+/// let week = epi_week(NaiveDate::from_ymd_opt(2024, 3, 15).unwrap());
+/// assert_eq!((week.year(), week.week()), (2024, 11));
+///
+/// // December 31, 2024 already belongs to 2025
+/// let week = epi_week(NaiveDate::from_ymd_opt(2024, 12, 31).unwrap());
+/// assert_eq!((week.year(), week.week()), (2025, 1));
+/// ```
+pub fn epi_week(date: NaiveDate) -> EpiWeek {
+    EpiWeek::from_date(date)
+}
+
+/// Returns the days of an epidemiological week, from Sunday to Saturday.
+///
+/// # Arguments
+///
+/// * `year` - The epidemiological year.
+/// * `week` - The week of the year (1-53).
+///
+/// # Returns
+///
+/// The range of days, or `None` if the year has no such week.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::date_utils::epi_week_range;
+/// use chrono::NaiveDate;
+///
+/// let range = epi_week_range(2020, 53).unwrap();
+/// assert_eq!(*range.start(), NaiveDate::from_ymd_opt(2020, 12, 27).unwrap());
+/// assert_eq!(*range.end(), NaiveDate::from_ymd_opt(2021, 1, 2).unwrap());
+/// assert_eq!(epi_week_range(2021, 53), None);
+/// ```
+pub fn epi_week_range(year: i32, week: u32) -> Option<RangeInclusive<NaiveDate>> {
+    let week = EpiWeek::new(year, week)?;
+    Some(week.first_day()..=week.last_day())
+}
+
+/// Returns the number of epidemiological weeks of a year (52 or 53).
+pub fn weeks_in_epi_year(year: i32) -> u32 {
+    ((epi_year_start(year + 1) - epi_year_start(year)) / 7) as u32
+}
+
+/// The day, counted like [`Datelike::num_days_from_ce`], of the Sunday
+/// starting epidemiological week 1 of a year.
+///
+/// Computed arithmetically so that years at the limits of `NaiveDate` work.
+fn epi_year_start(year: i32) -> i32 {
+    let before = year - 1;
+    let january_4 =
+        365 * before + before.div_euclid(4) - before.div_euclid(100) + before.div_euclid(400) + 4;
+    // Day 7 (January 7 of year 1) was a Sunday
+    january_4 - january_4.rem_euclid(7)
 }
 
 /// Calculates the date of Easter Sunday for a given year using the Anonymous Gregorian algorithm.
@@ -99,7 +781,7 @@ pub fn convert_date_to_text(date: &str) -> Option<String> {
 /// # Returns
 ///
 /// The date of Easter Sunday
-fn calculate_easter(year: i32) -> NaiveDate {
+pub(crate) fn calculate_easter(year: i32) -> NaiveDate {
     let a = year % 19;
     let b = year / 100;
     let c = year % 100;
@@ -118,6 +800,70 @@ fn calculate_easter(year: i32) -> NaiveDate {
     NaiveDate::from_ymd_opt(year, month as u32, day as u32).unwrap()
 }
 
+/// Valid state abbreviations (UFs) accepted by the holiday functions.
+const VALID_UFS: &[&str] = &[
+    "AC", "AL", "AM", "AP", "BA", "CE", "DF", "ES", "GO", "MA", "MG", "MS", "MT", "PA", "PB", "PE",
+    "PI", "PR", "RJ", "RN", "RO", "RR", "RS", "SC", "SE", "SP", "TO",
+];
+
+/// Checks if the given UF has built-in holidays.
+pub(crate) fn is_valid_uf(uf: &str) -> bool {
+    VALID_UFS.contains(&uf)
+}
+
+/// Legal basis of the state holidays whose state law has not been identified
+/// yet. Each state fixes its own holidays by state law, as allowed by Lei nº
+/// 9.093/1995, but the number of that law is unknown for these entries.
+const STATE_LAW_UNKNOWN: &str = "Lei estadual não identificada";
+
+/// Legal basis of the pontos facultativos, declared yearly by the federal government.
+const OPTIONAL_LEGAL_BASIS: &str = "Portaria anual do Governo Federal sobre pontos facultativos";
+
+/// Legal basis of the municipal holidays, fixed by municipal law.
+const MUNICIPAL_LEGAL_BASIS: &str = "Lei municipal, nos termos da Lei nº 9.093/1995";
+
+/// Scope in which a holiday is observed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HolidayScope {
+    /// Observed in the whole country.
+    National,
+    /// Observed only in the given state (UF).
+    State(String),
+    /// Observed only in the municipality with the given IBGE code.
+    Municipal(String),
+    /// Defined by a rule of a [`HolidayCalendar`](crate::holiday_calendar::HolidayCalendar).
+    Custom,
+}
+
+/// Whether a day is a holiday or a day off that is not mandatory.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HolidayKind {
+    /// A holiday established by law (feriado).
+    #[default]
+    Holiday,
+    /// A ponto facultativo: a day off at the discretion of each employer.
+    Optional,
+    /// A ponto facultativo for part of the day only (e.g. until 14h).
+    HalfDay,
+}
+
+/// A Brazilian holiday.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Holiday {
+    /// The date of the holiday.
+    pub date: NaiveDate,
+    /// The name of the holiday in Portuguese.
+    pub name: String,
+    /// Whether the holiday is national or specific to a state or municipality.
+    pub scope: HolidayScope,
+    /// Whether it is a holiday or a ponto facultativo.
+    pub kind: HolidayKind,
+    /// The law that establishes the holiday, or `"Lei estadual não
+    /// identificada"` for the state holidays whose law is not known.
+    pub legal_basis: String,
+}
+
 /// Checks if the given date is a national or state holiday in Brazil.
 ///
 /// This function takes a date and an optional UF (Unidade Federativa),
@@ -156,260 +902,836 @@ fn calculate_easter(year: i32) -> NaiveDate {
 /// assert_eq!(is_holiday(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(), Some("XX")), None);
 /// ```
 pub fn is_holiday(target_date: NaiveDate, uf: Option<&str>) -> Option<bool> {
-    const VALID_UFS: &[&str] = &[
-        "AC", "AL", "AM", "AP", "BA", "CE", "DF", "ES", "GO", "MA", "MG", "MS", "MT", "PA", "PB",
-        "PE", "PI", "PR", "RJ", "RN", "RO", "RR", "RS", "SC", "SE", "SP", "TO",
-    ];
-
     // Check if UF is valid
     if let Some(state) = uf {
-        if !VALID_UFS.contains(&state) {
+        if !is_valid_uf(state) {
             return None;
         }
     }
 
-    let year = target_date.year();
-    let month = target_date.month();
-    let day = target_date.day();
+    Some(!holidays_on(target_date, uf).is_empty())
+}
 
-    // Check national holidays
-    if is_national_holiday(year, month, day, target_date) {
-        return Some(true);
+/// Looks up the holiday that falls on the given date.
+///
+/// When a date is both a national and a state holiday (e.g. April 21 in the
+/// Federal District), the national holiday is returned.
+///
+/// # Arguments
+///
+/// * `target_date` - The date to be checked.
+/// * `uf` - The state abbreviation (UF) to check for state holidays.
+///   If not provided, only national holidays will be considered.
+///
+/// # Returns
+///
+/// The holiday on that date, or `None` if the date is not a holiday or the UF
+/// is invalid.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::date_utils::{get_holiday, HolidayScope};
+/// use chrono::NaiveDate;
+///
+/// let holiday = get_holiday(NaiveDate::from_ymd_opt(2024, 7, 9).unwrap(), Some("SP")).unwrap();
+/// assert_eq!(holiday.name, "Revolução Constitucionalista de 1932");
+/// assert_eq!(holiday.scope, HolidayScope::State("SP".to_string()));
+///
+/// assert_eq!(get_holiday(NaiveDate::from_ymd_opt(2024, 7, 9).unwrap(), None), None);
+/// ```
+pub fn get_holiday(target_date: NaiveDate, uf: Option<&str>) -> Option<Holiday> {
+    if let Some(state) = uf {
+        if !is_valid_uf(state) {
+            return None;
+        }
     }
 
-    // Check state holidays if UF is provided
+    holidays_on(target_date, uf).into_iter().next()
+}
+
+/// Lists all national and state holidays of a year.
+///
+/// # Arguments
+///
+/// * `year` - The year to list.
+/// * `uf` - The state abbreviation (UF) whose holidays are included.
+///   If not provided, only national holidays are listed.
+///
+/// # Returns
+///
+/// The holidays sorted by date, national before state holidays on the same
+/// date, or `None` if the UF is invalid.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::date_utils::list_holidays;
+///
+/// let holidays = list_holidays(2024, Some("BA")).unwrap();
+/// let names: Vec<&str> = holidays.iter().map(|h| h.name.as_str()).collect();
+///
+/// assert_eq!(holidays.len(), 11);
+/// assert_eq!(names[0], "Confraternização Universal");
+/// assert!(names.contains(&"Independência da Bahia"));
+///
+/// assert_eq!(list_holidays(2024, Some("XX")), None);
+/// ```
+pub fn list_holidays(year: i32, uf: Option<&str>) -> Option<Vec<Holiday>> {
     if let Some(state) = uf {
-        if is_state_holiday(year, month, day, target_date, state) {
-            return Some(true);
+        if !is_valid_uf(state) {
+            return None;
         }
     }
 
-    Some(false)
+    let first_day = NaiveDate::from_ymd_opt(year, 1, 1)?;
+
+    Some(
+        first_day
+            .iter_days()
+            .take_while(|date| date.year() == year)
+            .flat_map(|date| holidays_on(date, uf))
+            .collect(),
+    )
+}
+
+/// Collects the national and state holidays on a date, national first.
+pub(crate) fn holidays_on(date: NaiveDate, uf: Option<&str>) -> Vec<Holiday> {
+    let national = NATIONAL_HOLIDAYS
+        .iter()
+        .filter(|entry| entry.matches(date))
+        .map(|entry| Holiday {
+            date,
+            name: entry.name.to_string(),
+            scope: HolidayScope::National,
+            kind: HolidayKind::Holiday,
+            legal_basis: entry.legal_basis.to_string(),
+        });
+
+    // State holidays only from 1996 onwards
+    let state = STATE_HOLIDAYS
+        .iter()
+        .filter(|(state, entry)| {
+            uf == Some(*state) && date.year() >= FIRST_STATE_HOLIDAY_YEAR && entry.matches(date)
+        })
+        .map(|(state, entry)| Holiday {
+            date,
+            name: entry.name.to_string(),
+            scope: HolidayScope::State(state.to_string()),
+            kind: HolidayKind::Holiday,
+            legal_basis: entry.legal_basis.to_string(),
+        });
+
+    national.chain(state).collect()
 }
 
-/// Checks if a date is a national holiday in Brazil.
-fn is_national_holiday(year: i32, month: u32, day: u32, date: NaiveDate) -> bool {
-    // Check fixed national holidays
-    let is_fixed_holiday = match (month, day) {
-        (1, 1) => true, // New Year's Day - Confraternização Universal
-        (4, 21) if year != 1931 && year != 1932 => true, // Tiradentes' Day
-        (5, 1) if year >= 1925 => true, // Labor Day
-        (9, 7) if year >= 1890 => true, // Independence Day
-        (10, 12) if year <= 1930 || year >= 1980 => true, // Our Lady of Aparecida
-        (11, 2) => true, // All Souls' Day - Finados
-        (11, 15) => true, // Republic Proclamation Day
-        (12, 25) if year >= 1922 => true, // Christmas Day
-        _ => false,
+/// Looks up the ponto facultativo that falls on the given date.
+///
+/// Pontos facultativos are days off declared every year by the federal
+/// government and observed by most banks and public agencies, but which are
+/// not holidays: Carnival Monday and Tuesday, Ash Wednesday (a
+/// [`HalfDay`](HolidayKind::HalfDay), with work resuming at 14h) and Corpus
+/// Christi. They are all computed from Easter Sunday.
+///
+/// # Arguments
+///
+/// * `target_date` - The date to be checked.
+///
+/// # Returns
+///
+/// The optional day, or `None` if the date is not one.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::date_utils::{get_optional_day, HolidayKind};
+/// use chrono::NaiveDate;
+///
+/// let carnival = get_optional_day(NaiveDate::from_ymd_opt(2024, 2, 13).unwrap()).unwrap();
+/// assert_eq!(carnival.name, "Carnaval");
+/// assert_eq!(carnival.kind, HolidayKind::Optional);
+///
+/// let ash_wednesday = get_optional_day(NaiveDate::from_ymd_opt(2024, 2, 14).unwrap()).unwrap();
+/// assert_eq!(ash_wednesday.kind, HolidayKind::HalfDay);
+///
+/// assert_eq!(get_optional_day(NaiveDate::from_ymd_opt(2024, 2, 15).unwrap()), None);
+/// ```
+pub fn get_optional_day(target_date: NaiveDate) -> Option<Holiday> {
+    let easter = calculate_easter(target_date.year());
+    let offset = (target_date - easter).num_days();
+
+    let (name, kind) = match offset {
+        -48 | -47 => ("Carnaval", HolidayKind::Optional),
+        -46 => ("Quarta-feira de Cinzas", HolidayKind::HalfDay),
+        60 => ("Corpus Christi", HolidayKind::Optional),
+        _ => return None,
     };
 
-    if is_fixed_holiday {
-        return true;
-    }
+    Some(Holiday {
+        date: target_date,
+        name: name.to_string(),
+        scope: HolidayScope::National,
+        kind,
+        legal_basis: OPTIONAL_LEGAL_BASIS.to_string(),
+    })
+}
 
-    // Check movable holidays (Easter-based)
+/// Lists the pontos facultativos of a year.
+///
+/// See [`get_optional_day`].
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::date_utils::list_optional_days;
+///
+/// let days: Vec<String> = list_optional_days(2024)
+///     .iter()
+///     .map(|day| day.date.format("%d/%m").to_string())
+///     .collect();
+///
+/// assert_eq!(days, vec!["12/02", "13/02", "14/02", "30/05"]);
+/// ```
+pub fn list_optional_days(year: i32) -> Vec<Holiday> {
     let easter = calculate_easter(year);
 
-    // Good Friday (2 days before Easter)
-    let good_friday = easter - chrono::Duration::days(2);
-    date == good_friday
+    [-48, -47, -46, 60]
+        .into_iter()
+        .filter_map(|offset| get_optional_day(easter + chrono::Duration::days(offset)))
+        .collect()
 }
 
-/// Checks if a date is a state holiday in Brazil.
-fn is_state_holiday(year: i32, month: u32, day: u32, date: NaiveDate, uf: &str) -> bool {
-    if year < 1996 {
-        // Lei n. 9.093, de 12.09.1995 - state holidays only from 1996 onwards
-        return false;
-    }
+/// Checks if the given date is a holiday in a municipality.
+///
+/// National, state and municipal holidays are all considered. Municipalities
+/// are identified by their 7-digit IBGE code, as returned in
+/// [`Address::ibge`](crate::cep::Address::ibge). Municipal holidays are
+/// available for all state capitals.
+///
+/// # Arguments
+///
+/// * `target_date` - The date to be checked.
+/// * `ibge` - The IBGE code of the municipality.
+///
+/// # Returns
+///
+/// Returns `Some(true)` if the date is a holiday, `Some(false)` if it is not,
+/// or `None` if the municipality is unknown.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::date_utils::is_municipal_holiday;
+/// use chrono::NaiveDate;
+///
+/// // São Paulo anniversary
+/// let date = NaiveDate::from_ymd_opt(2024, 1, 25).unwrap();
+/// assert_eq!(is_municipal_holiday(date, "3550308"), Some(true));
+///
+/// // Not a holiday in Rio de Janeiro
+/// assert_eq!(is_municipal_holiday(date, "3304557"), Some(false));
+///
+/// // Unknown municipality
+/// assert_eq!(is_municipal_holiday(date, "0000000"), None);
+/// ```
+pub fn is_municipal_holiday(target_date: NaiveDate, ibge: &str) -> Option<bool> {
+    Some(!municipal_holidays_on(target_date, ibge)?.is_empty())
+}
 
-    match uf {
-        "AC" => {
-            match (month, day) {
-                (1, 23) if year >= 2005 => true, // Evangelical Day
-                (3, 8) if year >= 2002 => true,  // International Women's Day
-                (6, 15) => true,                 // Founding of Acre
-                (9, 5) if year >= 2004 => true,  // Amazonia Day
-                (11, 17) => true,                // Signing of the Petropolis Treaty
-                _ => false,
-            }
-        }
-        "AL" => {
-            matches!(
-                (month, day),
-                (6, 24) |   // Saint John's Day
-                (6, 29) |   // Saint Peter's Day
-                (9, 16) |   // Political Emancipation of Alagoas
-                (11, 20) // Black Awareness Day
-            ) || (month == 11 && day == 30 && year >= 2013) // Evangelical Day
-        }
-        "AM" => {
-            (month == 9 && day == 5) ||  // Elevation of Amazonas to province
-            (month == 11 && day == 20 && year >= 2010) // Black Awareness Day
-        }
-        "AP" => {
-            match (month, day) {
-                (3, 19) if year >= 2003 => true,  // Saint Joseph's Day
-                (7, 25) if year >= 2012 => true,  // Saint James' Day
-                (9, 13) => true,                  // Creation of the Federal Territory
-                (11, 20) if year >= 2008 => true, // Black Awareness Day
-                _ => false,
-            }
-        }
-        "BA" => {
-            matches!((month, day), (7, 2)) // Bahia Independence Day
-        }
-        "CE" => {
-            matches!(
-                (month, day),
-                (3, 19) |   // Saint Joseph's Day
-                (3, 25) // Abolition of slavery in Ceará
-            ) || (month == 8 && day == 15 && year >= 2004) // Our Lady of Assumption
-        }
-        "DF" => {
-            matches!(
-                (month, day),
-                (4, 21) |   // Founding of Brasilia
-                (11, 30) // Evangelical Day
-            )
-        }
-        "ES" => {
-            // Our Lady of Penha (Easter Sunday + 8 days)
-            if year >= 2020 {
-                let easter = calculate_easter(year);
-                let penha = easter + chrono::Duration::days(8);
-                date == penha
-            } else {
-                false
-            }
-        }
-        "GO" => {
-            matches!(
-                (month, day),
-                (7, 26) |   // Foundation of Goiás city
-                (10, 24) // Foundation of Goiânia
-            )
-        }
-        "MA" => {
-            matches!((month, day), (7, 28)) // Maranhão joining to independence
-        }
-        "MG" => {
-            matches!((month, day), (4, 21)) // Tiradentes' Execution
-        }
-        "MS" => {
-            matches!((month, day), (10, 11)) // State Creation Day
-        }
-        "MT" => {
-            month == 11 && day == 20 && year >= 2003 // Black Awareness Day
-        }
-        "PA" => {
-            matches!((month, day), (8, 15)) // Grão-Pará joining to independence
-        }
-        "PB" => {
-            matches!((month, day), (8, 5)) // State Founding Day
+/// Looks up the holiday that falls on the given date in a municipality.
+///
+/// National holidays take precedence over state ones, which take precedence
+/// over municipal ones.
+///
+/// # Arguments
+///
+/// * `target_date` - The date to be checked.
+/// * `ibge` - The IBGE code of the municipality.
+///
+/// # Returns
+///
+/// The holiday on that date, or `None` if the date is not a holiday or the
+/// municipality is unknown.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::date_utils::{get_municipal_holiday, HolidayScope};
+/// use chrono::NaiveDate;
+///
+/// let date = NaiveDate::from_ymd_opt(2024, 1, 20).unwrap();
+/// let holiday = get_municipal_holiday(date, "3304557").unwrap();
+///
+/// assert_eq!(holiday.name, "São Sebastião");
+/// assert_eq!(holiday.scope, HolidayScope::Municipal("3304557".to_string()));
+/// ```
+pub fn get_municipal_holiday(target_date: NaiveDate, ibge: &str) -> Option<Holiday> {
+    municipal_holidays_on(target_date, ibge)?.into_iter().next()
+}
+
+/// Lists all holidays observed in a municipality in a year.
+///
+/// The list is the full local calendar: national holidays, the holidays of
+/// the municipality's state and its own municipal holidays.
+///
+/// # Arguments
+///
+/// * `year` - The year to list.
+/// * `ibge` - The IBGE code of the municipality.
+///
+/// # Returns
+///
+/// The holidays sorted by date, or `None` if the municipality is unknown.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::date_utils::{list_municipal_holidays, HolidayScope};
+///
+/// let holidays = list_municipal_holidays(2024, "3106200").unwrap();
+/// let municipal: Vec<&str> = holidays
+///     .iter()
+///     .filter(|h| matches!(h.scope, HolidayScope::Municipal(_)))
+///     .map(|h| h.name.as_str())
+///     .collect();
+///
+/// assert_eq!(
+///     municipal,
+///     vec!["Corpus Christi", "Assunção de Nossa Senhora", "Imaculada Conceição"]
+/// );
+/// ```
+pub fn list_municipal_holidays(year: i32, ibge: &str) -> Option<Vec<Holiday>> {
+    municipality(ibge)?;
+    let first_day = NaiveDate::from_ymd_opt(year, 1, 1)?;
+
+    Some(
+        first_day
+            .iter_days()
+            .take_while(|date| date.year() == year)
+            .flat_map(|date| municipal_holidays_on(date, ibge).unwrap_or_default())
+            .collect(),
+    )
+}
+
+/// Returns the UF of a municipality with municipal holiday data.
+pub(crate) fn municipality_uf(ibge: &str) -> Option<&'static str> {
+    municipality(ibge).map(|municipality| municipality.uf.as_str())
+}
+
+/// A municipality with its own holidays.
+#[derive(Debug, Deserialize)]
+struct Municipality {
+    uf: String,
+    holidays: Vec<MunicipalHolidayRule>,
+}
+
+/// A municipal holiday, on a fixed date or relative to Easter Sunday.
+#[derive(Debug, Deserialize)]
+struct MunicipalHolidayRule {
+    name: String,
+    month: Option<u32>,
+    day: Option<u32>,
+    easter_offset: Option<i64>,
+    since: Option<i32>,
+    until: Option<i32>,
+}
+
+/// Load municipal holiday data from JSON, keyed by IBGE code.
+fn municipalities() -> &'static HashMap<String, Municipality> {
+    static DATA: OnceLock<HashMap<String, Municipality>> = OnceLock::new();
+
+    DATA.get_or_init(|| {
+        const JSON_DATA: &str = include_str!("data/municipal_holidays.json");
+        serde_json::from_str(JSON_DATA).expect("Failed to parse municipal_holidays.json")
+    })
+}
+
+/// Looks up a municipality by IBGE code.
+fn municipality(ibge: &str) -> Option<&'static Municipality> {
+    municipalities().get(ibge)
+}
+
+/// Collects all holidays on a date in a municipality: national, state, municipal.
+pub(crate) fn municipal_holidays_on(date: NaiveDate, ibge: &str) -> Option<Vec<Holiday>> {
+    let municipality = municipality(ibge)?;
+    let mut holidays = holidays_on(date, Some(&municipality.uf));
+
+    for rule in &municipality.holidays {
+        if rule.since.is_some_and(|since| date.year() < since)
+            || rule.until.is_some_and(|until| date.year() > until)
+        {
+            continue;
         }
-        "PE" => {
-            // Pernambuco Revolution (1st Sunday of March)
-            if year >= 2008 && month == 3 {
-                let first_day = NaiveDate::from_ymd_opt(year, 3, 1).unwrap();
-                let first_sunday = match first_day.weekday() {
-                    Weekday::Sun => first_day,
-                    _ => {
-                        let days_until_sunday = 7 - first_day.weekday().num_days_from_sunday();
-                        first_day + chrono::Duration::days(days_until_sunday as i64)
-                    }
-                };
-                date == first_sunday
-            } else {
-                false
+
+        let matches = match (rule.easter_offset, rule.month, rule.day) {
+            (Some(offset), _, _) => {
+                date == calculate_easter(date.year()) + chrono::Duration::days(offset)
             }
+            (None, Some(month), Some(day)) => date.month() == month && date.day() == day,
+            _ => false,
+        };
+
+        if matches {
+            holidays.push(Holiday {
+                date,
+                name: rule.name.clone(),
+                scope: HolidayScope::Municipal(ibge.to_string()),
+                kind: HolidayKind::Holiday,
+                legal_basis: MUNICIPAL_LEGAL_BASIS.to_string(),
+            });
         }
-        "PI" => {
-            matches!((month, day), (10, 19)) // Piauí Day
-        }
-        "PR" => {
-            matches!((month, day), (12, 19)) // Emancipation of Paraná
-        }
-        "RJ" => {
-            match (month, day) {
-                (4, 23) if year >= 2008 => true,  // Saint George's Day
-                (11, 20) if year >= 2002 => true, // Black Awareness Day
-                _ => false,
-            }
+    }
+
+    Some(holidays)
+}
+
+/// How the date of a built-in holiday is found in each year.
+#[derive(Debug, Clone, Copy)]
+enum EntryDate {
+    /// The same day every year.
+    Fixed(u32, u32),
+    /// A number of days after (or, if negative, before) Easter Sunday.
+    Easter(i64),
+    /// The first Sunday on or after a day, if it is still in the same month.
+    SundayFrom(u32, u32),
+}
+
+/// A holiday established by law, valid in a range of years.
+///
+/// A law that creates, moves or abolishes a holiday is recorded by closing
+/// the range of the old entry and opening a new one, so the calendar of any
+/// past year can be reproduced.
+#[derive(Debug, Clone)]
+struct HolidayEntry {
+    name: &'static str,
+    date: EntryDate,
+    years: RangeInclusive<i32>,
+    legal_basis: &'static str,
+}
+
+impl HolidayEntry {
+    /// A holiday on the same day every year, in force in all years.
+    const fn fixed(month: u32, day: u32, name: &'static str, legal_basis: &'static str) -> Self {
+        HolidayEntry {
+            name,
+            date: EntryDate::Fixed(month, day),
+            years: i32::MIN..=i32::MAX,
+            legal_basis,
         }
-        "RN" => {
-            match (month, day) {
-                (8, 7) if year >= 2000 => true,  // Rio Grande do Norte Day
-                (10, 3) if year >= 2007 => true, // Uruaçú and Cunhaú Martyrs Day
-                _ => false,
-            }
+    }
+
+    /// A holiday relative to Easter Sunday, in force in all years.
+    const fn easter(offset: i64, name: &'static str, legal_basis: &'static str) -> Self {
+        HolidayEntry {
+            name,
+            date: EntryDate::Easter(offset),
+            years: i32::MIN..=i32::MAX,
+            legal_basis,
         }
-        "RO" => {
-            (month == 1 && day == 4) ||  // State Creation Day
-            (month == 6 && day == 18 && year >= 2002) // Evangelical Day
-        }
-        "RR" => {
-            matches!((month, day), (10, 5)) // State Creation Day
-        }
-        "RS" => {
-            matches!((month, day), (9, 20)) // Gaucho Day
-        }
-        "SC" => {
-            // Santa Catarina State Day (1st Sunday from Aug 11, if >= 2005)
-            let is_sc_state_day = if year >= 2005 {
-                if month == 8 && day >= 11 {
-                    let target = NaiveDate::from_ymd_opt(year, 8, 11).unwrap();
-                    let first_sunday = match target.weekday() {
-                        Weekday::Sun => target,
-                        _ => {
-                            let days_until_sunday = 7 - target.weekday().num_days_from_sunday();
-                            target + chrono::Duration::days(days_until_sunday as i64)
-                        }
-                    };
-                    date == first_sunday
-                } else {
-                    false
-                }
-            } else if year == 2004 {
-                month == 8 && day == 11
-            } else {
-                false
-            };
+    }
 
-            // Saint Catherine of Alexandria Day (Nov 25 or 1st Sunday from Nov 25)
-            let is_saint_catherine = if (1999..=2030).contains(&year) && year != 2004 {
-                if month == 11 && day >= 25 {
-                    let target = NaiveDate::from_ymd_opt(year, 11, 25).unwrap();
-                    let first_sunday = match target.weekday() {
-                        Weekday::Sun => target,
-                        _ => {
-                            let days_until_sunday = 7 - target.weekday().num_days_from_sunday();
-                            target + chrono::Duration::days(days_until_sunday as i64)
-                        }
-                    };
-                    date == first_sunday
-                } else {
-                    false
-                }
-            } else {
-                month == 11 && day == 25
-            };
+    /// A holiday on the first Sunday on or after a day, in force in all years.
+    const fn sunday_from(
+        month: u32,
+        day: u32,
+        name: &'static str,
+        legal_basis: &'static str,
+    ) -> Self {
+        HolidayEntry {
+            name,
+            date: EntryDate::SundayFrom(month, day),
+            years: i32::MIN..=i32::MAX,
+            legal_basis,
+        }
+    }
 
-            is_sc_state_day || is_saint_catherine
+    /// Restricts the entry to the given year onwards.
+    const fn since(self, year: i32) -> Self {
+        HolidayEntry {
+            years: year..=*self.years.end(),
+            ..self
         }
-        "SE" => {
-            matches!((month, day), (7, 8)) // Sergipe Political Emancipation Day
+    }
+
+    /// Restricts the entry to the years up to the given one.
+    const fn until(self, year: i32) -> Self {
+        HolidayEntry {
+            years: *self.years.start()..=year,
+            ..self
         }
-        "SP" => {
-            month == 7 && day == 9 && year >= 1997 // Constitutionalist Revolution
+    }
+
+    /// Whether the holiday falls on the given date.
+    fn matches(&self, date: NaiveDate) -> bool {
+        if !self.years.contains(&date.year()) {
+            return false;
         }
-        "TO" => {
-            match (month, day) {
-                (3, 18) if year >= 1998 => true, // Autonomy Day
-                (9, 8) => true,                  // Our Lady of Nativity
-                (10, 5) => true,                 // State Creation Day
-                _ => false,
+
+        match self.date {
+            EntryDate::Fixed(month, day) => date.month() == month && date.day() == day,
+            EntryDate::Easter(offset) => {
+                date == calculate_easter(date.year()) + chrono::Duration::days(offset)
             }
+            EntryDate::SundayFrom(month, day) => {
+                date.month() == month
+                    && date.day() >= day
+                    && date == first_sunday_from(date.year(), month, day)
+            }
+        }
+    }
+}
+
+/// Legal basis of the fixed national holidays since 1949.
+const FIXED_LAW: &str = "Lei nº 662/1949, alterada pela Lei nº 10.607/2002";
+
+/// Legal basis of the national holidays of the first Republic, until Lei nº
+/// 662/1949 replaced it.
+const REPUBLIC_DECREE: &str = "Decreto nº 155-B/1890";
+
+/// Legal basis of the national holidays before 1949 whose decree has not
+/// been identified yet.
+const UNIDENTIFIED_DECREE: &str = "Decreto federal anterior à Lei nº 662/1949 não identificado";
+
+/// National holidays, with the years in which each one was in force.
+const NATIONAL_HOLIDAYS: &[HolidayEntry] = &[
+    HolidayEntry::fixed(1, 1, "Confraternização Universal", REPUBLIC_DECREE).until(1948),
+    HolidayEntry::fixed(1, 1, "Confraternização Universal", FIXED_LAW).since(1949),
+    // Suspended by Decreto nº 19.488/1930 and restored in 1933
+    HolidayEntry::fixed(4, 21, "Tiradentes", REPUBLIC_DECREE).until(1930),
+    HolidayEntry::fixed(4, 21, "Tiradentes", UNIDENTIFIED_DECREE)
+        .since(1933)
+        .until(1948),
+    HolidayEntry::fixed(4, 21, "Tiradentes", FIXED_LAW).since(1949),
+    HolidayEntry::fixed(5, 1, "Dia do Trabalho", UNIDENTIFIED_DECREE)
+        .since(1925)
+        .until(1948),
+    HolidayEntry::fixed(5, 1, "Dia do Trabalho", FIXED_LAW).since(1949),
+    HolidayEntry::fixed(9, 7, "Independência do Brasil", REPUBLIC_DECREE)
+        .since(1890)
+        .until(1948),
+    HolidayEntry::fixed(9, 7, "Independência do Brasil", FIXED_LAW).since(1949),
+    // Descobrimento da América until 1930, restored as Nossa Senhora Aparecida
+    // by Lei nº 6.802/1980
+    HolidayEntry::fixed(10, 12, "Descobrimento da América", REPUBLIC_DECREE).until(1930),
+    HolidayEntry::fixed(10, 12, "Nossa Senhora Aparecida", "Lei nº 6.802/1980").since(1980),
+    HolidayEntry::fixed(11, 2, "Finados", REPUBLIC_DECREE).until(1948),
+    HolidayEntry::fixed(11, 2, "Finados", FIXED_LAW).since(1949),
+    HolidayEntry::fixed(11, 15, "Proclamação da República", REPUBLIC_DECREE).until(1948),
+    HolidayEntry::fixed(11, 15, "Proclamação da República", FIXED_LAW).since(1949),
+    HolidayEntry::fixed(
+        11,
+        20,
+        "Dia Nacional de Zumbi e da Consciência Negra",
+        "Lei nº 14.759/2023",
+    )
+    .since(2024),
+    HolidayEntry::fixed(12, 25, "Natal", UNIDENTIFIED_DECREE)
+        .since(1922)
+        .until(1948),
+    HolidayEntry::fixed(12, 25, "Natal", FIXED_LAW).since(1949),
+    HolidayEntry::easter(-2, "Sexta-feira Santa", "Lei nº 9.093/1995"),
+];
+
+/// First year of the state holidays, which were allowed by Lei nº 9.093/1995.
+const FIRST_STATE_HOLIDAY_YEAR: i32 = 1996;
+
+/// State holidays by UF, with the years in which each one was in force.
+///
+/// The Dia da Consciência Negra of the states that had it ends in 2023: from
+/// 2024 on it is a national holiday.
+const STATE_HOLIDAYS: &[(&str, HolidayEntry)] = &[
+    (
+        "AC",
+        HolidayEntry::fixed(1, 23, "Dia do Evangélico", STATE_LAW_UNKNOWN).since(2005),
+    ),
+    (
+        "AC",
+        HolidayEntry::fixed(3, 8, "Dia Internacional da Mulher", STATE_LAW_UNKNOWN).since(2002),
+    ),
+    (
+        "AC",
+        HolidayEntry::fixed(6, 15, "Aniversário do Acre", STATE_LAW_UNKNOWN),
+    ),
+    (
+        "AC",
+        HolidayEntry::fixed(9, 5, "Dia da Amazônia", STATE_LAW_UNKNOWN).since(2004),
+    ),
+    (
+        "AC",
+        HolidayEntry::fixed(
+            11,
+            17,
+            "Assinatura do Tratado de Petrópolis",
+            STATE_LAW_UNKNOWN,
+        ),
+    ),
+    (
+        "AL",
+        HolidayEntry::fixed(6, 24, "São João", STATE_LAW_UNKNOWN),
+    ),
+    (
+        "AL",
+        HolidayEntry::fixed(6, 29, "São Pedro", STATE_LAW_UNKNOWN),
+    ),
+    (
+        "AL",
+        HolidayEntry::fixed(9, 16, "Emancipação Política de Alagoas", STATE_LAW_UNKNOWN),
+    ),
+    (
+        "AL",
+        HolidayEntry::fixed(11, 20, "Dia da Consciência Negra", STATE_LAW_UNKNOWN).until(2023),
+    ),
+    (
+        "AL",
+        HolidayEntry::fixed(11, 30, "Dia do Evangélico", STATE_LAW_UNKNOWN).since(2013),
+    ),
+    (
+        "AM",
+        HolidayEntry::fixed(
+            9,
+            5,
+            "Elevação do Amazonas à categoria de Província",
+            STATE_LAW_UNKNOWN,
+        ),
+    ),
+    (
+        "AM",
+        HolidayEntry::fixed(11, 20, "Dia da Consciência Negra", STATE_LAW_UNKNOWN)
+            .since(2010)
+            .until(2023),
+    ),
+    (
+        "AP",
+        HolidayEntry::fixed(3, 19, "São José", STATE_LAW_UNKNOWN).since(2003),
+    ),
+    (
+        "AP",
+        HolidayEntry::fixed(7, 25, "São Tiago", STATE_LAW_UNKNOWN).since(2012),
+    ),
+    (
+        "AP",
+        HolidayEntry::fixed(
+            9,
+            13,
+            "Criação do Território Federal do Amapá",
+            STATE_LAW_UNKNOWN,
+        ),
+    ),
+    (
+        "AP",
+        HolidayEntry::fixed(11, 20, "Dia da Consciência Negra", STATE_LAW_UNKNOWN)
+            .since(2008)
+            .until(2023),
+    ),
+    (
+        "BA",
+        HolidayEntry::fixed(7, 2, "Independência da Bahia", STATE_LAW_UNKNOWN),
+    ),
+    (
+        "CE",
+        HolidayEntry::fixed(3, 19, "São José", STATE_LAW_UNKNOWN),
+    ),
+    (
+        "CE",
+        HolidayEntry::fixed(3, 25, "Data Magna do Ceará", STATE_LAW_UNKNOWN),
+    ),
+    (
+        "CE",
+        HolidayEntry::fixed(8, 15, "Nossa Senhora da Assunção", STATE_LAW_UNKNOWN).since(2004),
+    ),
+    (
+        "DF",
+        HolidayEntry::fixed(4, 21, "Fundação de Brasília", STATE_LAW_UNKNOWN),
+    ),
+    (
+        "DF",
+        HolidayEntry::fixed(11, 30, "Dia do Evangélico", "Lei distrital nº 963/1995"),
+    ),
+    (
+        "ES",
+        HolidayEntry::easter(8, "Nossa Senhora da Penha", STATE_LAW_UNKNOWN).since(2020),
+    ),
+    (
+        "GO",
+        HolidayEntry::fixed(7, 26, "Fundação da Cidade de Goiás", STATE_LAW_UNKNOWN),
+    ),
+    (
+        "GO",
+        HolidayEntry::fixed(10, 24, "Pedra Fundamental de Goiânia", STATE_LAW_UNKNOWN),
+    ),
+    (
+        "MA",
+        HolidayEntry::fixed(
+            7,
+            28,
+            "Adesão do Maranhão à Independência do Brasil",
+            STATE_LAW_UNKNOWN,
+        ),
+    ),
+    (
+        "MG",
+        HolidayEntry::fixed(4, 21, "Data Magna de Minas Gerais", STATE_LAW_UNKNOWN),
+    ),
+    (
+        "MS",
+        HolidayEntry::fixed(
+            10,
+            11,
+            "Criação do Estado de Mato Grosso do Sul",
+            STATE_LAW_UNKNOWN,
+        ),
+    ),
+    (
+        "MT",
+        HolidayEntry::fixed(
+            11,
+            20,
+            "Dia da Consciência Negra",
+            "Lei estadual nº 7.879/2002",
+        )
+        .since(2003)
+        .until(2023),
+    ),
+    (
+        "PA",
+        HolidayEntry::fixed(
+            8,
+            15,
+            "Adesão do Grão-Pará à Independência do Brasil",
+            STATE_LAW_UNKNOWN,
+        ),
+    ),
+    (
+        "PB",
+        HolidayEntry::fixed(8, 5, "Fundação do Estado da Paraíba", STATE_LAW_UNKNOWN),
+    ),
+    (
+        "PE",
+        HolidayEntry::sunday_from(3, 1, "Revolução Pernambucana", STATE_LAW_UNKNOWN).since(2008),
+    ),
+    (
+        "PI",
+        HolidayEntry::fixed(10, 19, "Dia do Piauí", STATE_LAW_UNKNOWN),
+    ),
+    (
+        "PR",
+        HolidayEntry::fixed(12, 19, "Emancipação Política do Paraná", STATE_LAW_UNKNOWN),
+    ),
+    (
+        "RJ",
+        HolidayEntry::fixed(4, 23, "São Jorge", "Lei estadual nº 5.198/2008").since(2008),
+    ),
+    (
+        "RJ",
+        HolidayEntry::fixed(
+            11,
+            20,
+            "Dia da Consciência Negra",
+            "Lei estadual nº 4.007/2002",
+        )
+        .since(2002)
+        .until(2023),
+    ),
+    (
+        "RN",
+        HolidayEntry::fixed(8, 7, "Dia do Rio Grande do Norte", STATE_LAW_UNKNOWN).since(2000),
+    ),
+    (
+        "RN",
+        HolidayEntry::fixed(10, 3, "Mártires de Cunhaú e Uruaçu", STATE_LAW_UNKNOWN).since(2007),
+    ),
+    (
+        "RO",
+        HolidayEntry::fixed(1, 4, "Criação do Estado de Rondônia", STATE_LAW_UNKNOWN),
+    ),
+    (
+        "RO",
+        HolidayEntry::fixed(6, 18, "Dia do Evangélico", STATE_LAW_UNKNOWN).since(2002),
+    ),
+    (
+        "RR",
+        HolidayEntry::fixed(10, 5, "Criação do Estado de Roraima", STATE_LAW_UNKNOWN),
+    ),
+    (
+        "RS",
+        HolidayEntry::fixed(9, 20, "Revolução Farroupilha", STATE_LAW_UNKNOWN),
+    ),
+    // Dia de Santa Catarina: on August 11 in 2004, then moved to the following Sunday
+    (
+        "SC",
+        HolidayEntry::fixed(8, 11, "Dia de Santa Catarina", STATE_LAW_UNKNOWN)
+            .since(2004)
+            .until(2004),
+    ),
+    (
+        "SC",
+        HolidayEntry::sunday_from(8, 11, "Dia de Santa Catarina", STATE_LAW_UNKNOWN).since(2005),
+    ),
+    // Santa Catarina de Alexandria: moved to the following Sunday from 1999 to 2030, except in 2004
+    (
+        "SC",
+        HolidayEntry::fixed(11, 25, "Santa Catarina de Alexandria", STATE_LAW_UNKNOWN).until(1998),
+    ),
+    (
+        "SC",
+        HolidayEntry::sunday_from(11, 25, "Santa Catarina de Alexandria", STATE_LAW_UNKNOWN)
+            .since(1999)
+            .until(2003),
+    ),
+    (
+        "SC",
+        HolidayEntry::fixed(11, 25, "Santa Catarina de Alexandria", STATE_LAW_UNKNOWN)
+            .since(2004)
+            .until(2004),
+    ),
+    (
+        "SC",
+        HolidayEntry::sunday_from(11, 25, "Santa Catarina de Alexandria", STATE_LAW_UNKNOWN)
+            .since(2005)
+            .until(2030),
+    ),
+    (
+        "SC",
+        HolidayEntry::fixed(11, 25, "Santa Catarina de Alexandria", STATE_LAW_UNKNOWN).since(2031),
+    ),
+    (
+        "SE",
+        HolidayEntry::fixed(7, 8, "Emancipação Política de Sergipe", STATE_LAW_UNKNOWN),
+    ),
+    (
+        "SP",
+        HolidayEntry::fixed(
+            7,
+            9,
+            "Revolução Constitucionalista de 1932",
+            "Lei estadual nº 9.497/1997",
+        )
+        .since(1997),
+    ),
+    (
+        "TO",
+        HolidayEntry::fixed(3, 18, "Autonomia do Tocantins", "Lei estadual nº 960/1998")
+            .since(1998),
+    ),
+    (
+        "TO",
+        HolidayEntry::fixed(9, 8, "Nossa Senhora da Natividade", STATE_LAW_UNKNOWN),
+    ),
+    (
+        "TO",
+        HolidayEntry::fixed(10, 5, "Criação do Estado do Tocantins", STATE_LAW_UNKNOWN),
+    ),
+];
+
+/// Returns the first Sunday on or after the given day of the year.
+fn first_sunday_from(year: i32, month: u32, day: u32) -> NaiveDate {
+    let target = NaiveDate::from_ymd_opt(year, month, day).unwrap();
+    match target.weekday() {
+        Weekday::Sun => target,
+        _ => {
+            let days_until_sunday = 7 - target.weekday().num_days_from_sunday();
+            target + chrono::Duration::days(days_until_sunday as i64)
         }
-        _ => false,
     }
 }
 
@@ -441,6 +1763,310 @@ mod tests {
         assert_eq!(convert_date_to_text("29/02/2023"), None); // Not a leap year
     }
 
+    #[test]
+    fn test_date_to_text_styles() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let city = |weekday| DateStyle::WithCity {
+            city: "Rio de Janeiro".to_string(),
+            weekday,
+        };
+
+        assert_eq!(
+            date_to_text(date, &DateStyle::Plain),
+            "Primeiro de março de dois mil e vinte e quatro"
+        );
+        assert_eq!(date_to_text(date, &DateStyle::Long), "1º de março de 2024");
+        assert_eq!(
+            date_to_text(date, &DateStyle::Notarial),
+            "ao primeiro dia do mês de março do ano de dois mil e vinte e quatro"
+        );
+        assert_eq!(
+            date_to_text(date, &DateStyle::WithWeekday),
+            "1º de março de 2024 (sexta-feira)"
+        );
+        assert_eq!(
+            date_to_text(date, &city(false)),
+            "Rio de Janeiro, 1º de março de 2024"
+        );
+        assert_eq!(
+            date_to_text(date, &city(true)),
+            "Rio de Janeiro, 1º de março de 2024 (sexta-feira)"
+        );
+
+        let date = NaiveDate::from_ymd_opt(1821, 12, 21).unwrap();
+        assert_eq!(
+            date_to_text(date, &DateStyle::Notarial),
+            "aos vinte e um dias do mês de dezembro do ano de mil oitocentos e vinte e um"
+        );
+        assert_eq!(
+            date_to_text(date, &DateStyle::default()),
+            convert_date_to_text("21/12/1821").unwrap()
+        );
+
+        let weekdays: Vec<String> = NaiveDate::from_ymd_opt(2024, 9, 1)
+            .unwrap()
+            .iter_days()
+            .take(7)
+            .map(|day| date_to_text(day, &DateStyle::WithWeekday))
+            .collect();
+        assert_eq!(weekdays[0], "1º de setembro de 2024 (domingo)");
+        assert_eq!(weekdays[2], "3 de setembro de 2024 (terça-feira)");
+        assert_eq!(weekdays[6], "7 de setembro de 2024 (sábado)");
+    }
+
+    #[test]
+    fn test_time_to_text() {
+        let time = |hour, minute, second| NaiveTime::from_hms_opt(hour, minute, second).unwrap();
+
+        assert_eq!(
+            time_to_text(time(14, 30, 0)),
+            "às catorze horas e trinta minutos"
+        );
+        assert_eq!(time_to_text(time(2, 2, 0)), "às duas horas e dois minutos");
+        assert_eq!(time_to_text(time(12, 0, 59)), "às doze horas");
+        assert_eq!(
+            time_to_text(time(21, 45, 0)),
+            "às vinte e uma horas e quarenta e cinco minutos"
+        );
+        assert_eq!(time_to_text(time(0, 15, 0)), "à zero hora e quinze minutos");
+        assert_eq!(time_to_text(time(1, 0, 0)), "à uma hora");
+
+        let datetime = NaiveDate::from_ymd_opt(2024, 5, 13)
+            .unwrap()
+            .and_hms_opt(9, 1, 0)
+            .unwrap();
+        assert_eq!(
+            datetime_to_text(datetime, &DateStyle::WithWeekday),
+            "13 de maio de 2024 (segunda-feira), às nove horas e um minuto"
+        );
+    }
+
+    #[test]
+    fn test_parse_date_numeric() {
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day);
+
+        assert_eq!(parse_date("15/08/1990"), date(1990, 8, 15));
+        assert_eq!(parse_date("15.08.1990"), date(1990, 8, 15));
+        assert_eq!(parse_date("15-08-1990"), date(1990, 8, 15));
+        assert_eq!(parse_date("1/8/1990"), date(1990, 8, 1));
+        assert_eq!(parse_date(" 15/08/90 "), date(1990, 8, 15));
+        assert_eq!(parse_date("15/08/20"), date(2020, 8, 15));
+        assert_eq!(parse_date("1990-08-15"), date(1990, 8, 15));
+        assert_eq!(parse_date("15/ago/1990"), date(1990, 8, 15));
+        assert_eq!(parse_date("15/Ago./1990"), date(1990, 8, 15));
+
+        assert_eq!(parse_date("31/04/1990"), None);
+        assert_eq!(parse_date("15/08/990"), None);
+        assert_eq!(parse_date("15/08.1990"), None);
+        assert_eq!(parse_date("115/08/1990"), None);
+        assert_eq!(parse_date("15//1990"), None);
+        assert_eq!(parse_date("08/1990"), None);
+        assert_eq!(parse_date(""), None);
+    }
+
+    #[test]
+    fn test_parse_date_words() {
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day);
+
+        assert_eq!(parse_date("15 de agosto de 1990"), date(1990, 8, 15));
+        assert_eq!(parse_date("15 de Agosto de 90"), date(1990, 8, 15));
+        assert_eq!(
+            parse_date("Primeiro de janeiro de dois mil e vinte"),
+            date(2020, 1, 1)
+        );
+        assert_eq!(parse_date("1º de janeiro de 2020"), date(2020, 1, 1));
+        assert_eq!(parse_date("1o de jan. de 2020."), date(2020, 1, 1));
+        assert_eq!(
+            parse_date("vinte e cinco de dezembro de mil novecentos e noventa e nove"),
+            date(1999, 12, 25)
+        );
+        assert_eq!(parse_date("02 de MARCO de 2024"), date(2024, 3, 2));
+        assert_eq!(parse_date("dez de dezembro de 2024"), date(2024, 12, 10));
+        assert_eq!(
+            parse_date("São Paulo, 15 de agosto de 1990"),
+            date(1990, 8, 15)
+        );
+        assert_eq!(
+            parse_date("segunda-feira, 13 de maio de 2024"),
+            date(2024, 5, 13)
+        );
+
+        assert_eq!(parse_date("trinta de fevereiro de 2024"), None);
+        assert_eq!(parse_date("quarenta de agosto de 1990"), None);
+        assert_eq!(parse_date("15 de agosto"), None);
+        assert_eq!(parse_date("15 de agosto de"), None);
+        assert_eq!(parse_date("agosto de 1990"), None);
+
+        // Inverse of convert_date_to_text
+        let mut day = NaiveDate::from_ymd_opt(1899, 12, 25).unwrap();
+        while day.year() < 2101 {
+            let text = convert_date_to_text(&day.format("%d/%m/%Y").to_string()).unwrap();
+            assert_eq!(parse_date(&text), Some(day), "{text}");
+            day += chrono::Duration::days(37);
+        }
+    }
+
+    #[test]
+    fn test_parse_month_year() {
+        let august_1990 = MonthYear::new(1990, 8);
+
+        assert_eq!(parse_month_year("ago/1990"), august_1990);
+        assert_eq!(parse_month_year("AGO/90"), august_1990);
+        assert_eq!(parse_month_year("agosto/1990"), august_1990);
+        assert_eq!(parse_month_year("ago-1990"), august_1990);
+        assert_eq!(parse_month_year("08/1990"), august_1990);
+        assert_eq!(parse_month_year("8.1990"), august_1990);
+        assert_eq!(parse_month_year("agosto de 1990"), august_1990);
+        assert_eq!(
+            parse_month_year("março de dois mil e vinte e quatro"),
+            MonthYear::new(2024, 3)
+        );
+
+        assert_eq!(parse_month_year("13/1990"), None);
+        assert_eq!(parse_month_year("xyz/1990"), None);
+        assert_eq!(parse_month_year("15/08/1990"), None);
+
+        let parsed = DateParser::new().parse("ago/1990");
+        assert_eq!(parsed, Some(ParsedDate::MonthYear(august_1990.unwrap())));
+    }
+
+    #[test]
+    fn test_date_parser_century_pivot() {
+        let default = DateParser::default();
+        assert_eq!(
+            default.parse_date("01/01/49"),
+            NaiveDate::from_ymd_opt(2049, 1, 1)
+        );
+        assert_eq!(
+            default.parse_date("01/01/50"),
+            NaiveDate::from_ymd_opt(1950, 1, 1)
+        );
+
+        let parser = DateParser::new().with_century_pivot(70);
+        assert_eq!(
+            parser.parse_date("01/01/69"),
+            NaiveDate::from_ymd_opt(2069, 1, 1)
+        );
+        assert_eq!(
+            parser.parse_date("01/01/70"),
+            NaiveDate::from_ymd_opt(1970, 1, 1)
+        );
+        assert_eq!(parser.parse_month_year("jan/00"), MonthYear::new(2000, 1));
+
+        let all_1900s = DateParser::new().with_century_pivot(0);
+        assert_eq!(
+            all_1900s.parse_date("01/01/00"),
+            NaiveDate::from_ymd_opt(1900, 1, 1)
+        );
+        let all_2000s = DateParser::new().with_century_pivot(200);
+        assert_eq!(
+            all_2000s.parse_date("01/01/99"),
+            NaiveDate::from_ymd_opt(2099, 1, 1)
+        );
+
+        // Four-digit years are not affected
+        assert_eq!(
+            parser.parse_date("01/01/1969"),
+            NaiveDate::from_ymd_opt(1969, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_month_year() {
+        let month = MonthYear::new(2023, 12).unwrap();
+
+        assert_eq!(month.year(), 2023);
+        assert_eq!(month.month(), 12);
+        assert_eq!(
+            month.first_day(),
+            NaiveDate::from_ymd_opt(2023, 12, 1).unwrap()
+        );
+        assert_eq!(
+            month.last_day(),
+            NaiveDate::from_ymd_opt(2023, 12, 31).unwrap()
+        );
+        assert_eq!(month.to_string(), "12/2023");
+        assert!(month < MonthYear::new(2024, 1).unwrap());
+        assert_eq!(MonthYear::new(2024, 0), None);
+        assert_eq!(MonthYear::new(2024, 13), None);
+    }
+
+    #[test]
+    fn test_epi_week_first_weeks() {
+        // (year, first day of week 1) from the Ministry of Health calendars
+        for (year, month, day, first_year) in [
+            (2019, 12, 29, 2020),
+            (2021, 1, 3, 2021),
+            (2022, 1, 2, 2022),
+            (2023, 1, 1, 2023),
+            (2023, 12, 31, 2024),
+            (2024, 12, 29, 2025),
+        ] {
+            let start = NaiveDate::from_ymd_opt(year, month, day).unwrap();
+            let week = EpiWeek::new(first_year, 1).unwrap();
+            assert_eq!(week.first_day(), start, "{}", first_year);
+            assert_eq!(epi_week(start), week);
+            assert_eq!(epi_week(start.pred_opt().unwrap()).year(), first_year - 1);
+        }
+    }
+
+    #[test]
+    fn test_epi_week_of_date() {
+        let week = |year, month, day| {
+            let week = epi_week(NaiveDate::from_ymd_opt(year, month, day).unwrap());
+            (week.year(), week.week())
+        };
+
+        assert_eq!(week(2024, 1, 6), (2024, 1));
+        assert_eq!(week(2024, 1, 7), (2024, 2));
+        assert_eq!(week(2024, 12, 28), (2024, 52));
+        assert_eq!(week(2020, 12, 31), (2020, 53));
+        assert_eq!(week(2021, 1, 2), (2020, 53));
+        assert_eq!(week(2022, 1, 1), (2021, 52));
+    }
+
+    #[test]
+    fn test_epi_week_range_round_trip() {
+        let mut date = NaiveDate::from_ymd_opt(2015, 1, 1).unwrap();
+        while date.year() < 2030 {
+            let week = epi_week(date);
+            let range = epi_week_range(week.year(), week.week()).unwrap();
+            assert!(range.contains(&date));
+            assert_eq!(range.start().weekday(), Weekday::Sun);
+            assert_eq!(range.end().weekday(), Weekday::Sat);
+            date = date.succ_opt().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_weeks_in_epi_year() {
+        let long_years: Vec<i32> = (2000..=2030)
+            .filter(|&year| weeks_in_epi_year(year) == 53)
+            .collect();
+        assert_eq!(long_years, vec![2003, 2008, 2014, 2020, 2025]);
+
+        assert!(EpiWeek::new(2020, 53).is_some());
+        assert_eq!(EpiWeek::new(2021, 53), None);
+        assert_eq!(EpiWeek::new(2021, 0), None);
+        assert_eq!(epi_week_range(2024, 54), None);
+    }
+
+    #[test]
+    fn test_epi_week_display_and_order() {
+        let week = EpiWeek::new(2024, 9).unwrap();
+        assert_eq!(week.to_string(), "SE 09/2024");
+        assert!(EpiWeek::new(2023, 52).unwrap() < week);
+        assert!(week < EpiWeek::new(2024, 10).unwrap());
+    }
+
+    #[test]
+    fn test_epi_week_limits() {
+        let week = epi_week(NaiveDate::MIN);
+        assert!(week.first_day() <= NaiveDate::MIN);
+        let week = epi_week(NaiveDate::MAX);
+        assert!(week.last_day() >= NaiveDate::MAX);
+    }
+
     #[test]
     fn test_is_holiday_national() {
         // New Year's Day
@@ -512,6 +2138,383 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_get_holiday() {
+        let christmas = get_holiday(NaiveDate::from_ymd_opt(2024, 12, 25).unwrap(), None).unwrap();
+        assert_eq!(christmas.name, "Natal");
+        assert_eq!(christmas.scope, HolidayScope::National);
+        assert_eq!(
+            christmas.legal_basis,
+            "Lei nº 662/1949, alterada pela Lei nº 10.607/2002"
+        );
+
+        let good_friday = get_holiday(NaiveDate::from_ymd_opt(2024, 3, 29).unwrap(), None).unwrap();
+        assert_eq!(good_friday.name, "Sexta-feira Santa");
+
+        let penha = get_holiday(NaiveDate::from_ymd_opt(2024, 4, 8).unwrap(), Some("ES")).unwrap();
+        assert_eq!(penha.name, "Nossa Senhora da Penha");
+        assert_eq!(penha.scope, HolidayScope::State("ES".to_string()));
+        assert_eq!(penha.legal_basis, "Lei estadual não identificada");
+
+        // National holiday takes precedence over the state one
+        let tiradentes =
+            get_holiday(NaiveDate::from_ymd_opt(2024, 4, 21).unwrap(), Some("DF")).unwrap();
+        assert_eq!(tiradentes.name, "Tiradentes");
+
+        assert_eq!(
+            get_holiday(NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(), None),
+            None
+        );
+        assert_eq!(
+            get_holiday(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(), Some("XX")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_list_holidays_national() {
+        let holidays = list_holidays(2024, None).unwrap();
+        let dates: Vec<String> = holidays
+            .iter()
+            .map(|h| h.date.format("%d/%m").to_string())
+            .collect();
+
+        assert_eq!(
+            dates,
+            vec![
+                "01/01", "29/03", "21/04", "01/05", "07/09", "12/10", "02/11", "15/11", "20/11",
+                "25/12"
+            ]
+        );
+        assert!(holidays.iter().all(|h| h.scope == HolidayScope::National));
+    }
+
+    #[test]
+    fn test_list_holidays_state() {
+        let holidays = list_holidays(2024, Some("DF")).unwrap();
+
+        // April 21 is both Tiradentes and the founding of Brasília
+        let april_21: Vec<&str> = holidays
+            .iter()
+            .filter(|h| h.date == NaiveDate::from_ymd_opt(2024, 4, 21).unwrap())
+            .map(|h| h.name.as_str())
+            .collect();
+        assert_eq!(april_21, vec!["Tiradentes", "Fundação de Brasília"]);
+        assert_eq!(holidays.len(), 12);
+
+        let sc = list_holidays(2023, Some("SC")).unwrap();
+        let sc_state: Vec<String> = sc
+            .iter()
+            .filter(|h| h.scope != HolidayScope::National)
+            .map(|h| format!("{} {}", h.date, h.name))
+            .collect();
+        assert_eq!(
+            sc_state,
+            vec![
+                "2023-08-13 Dia de Santa Catarina",
+                "2023-11-26 Santa Catarina de Alexandria"
+            ]
+        );
+
+        assert_eq!(list_holidays(2024, Some("XX")), None);
+    }
+
+    #[test]
+    fn test_black_awareness_day_national_from_2024() {
+        let date_2023 = NaiveDate::from_ymd_opt(2023, 11, 20).unwrap();
+        let date_2024 = NaiveDate::from_ymd_opt(2024, 11, 20).unwrap();
+
+        // Lei nº 14.759/2023: national holiday from 2024 on
+        assert_eq!(is_holiday(date_2023, None), Some(false));
+        assert_eq!(is_holiday(date_2024, None), Some(true));
+        assert_eq!(is_holiday(date_2023, Some("SP")), Some(false));
+        assert_eq!(is_holiday(date_2024, Some("SP")), Some(true));
+
+        let holiday = get_holiday(date_2024, None).unwrap();
+        assert_eq!(holiday.name, "Dia Nacional de Zumbi e da Consciência Negra");
+        assert_eq!(holiday.scope, HolidayScope::National);
+        assert_eq!(holiday.legal_basis, "Lei nº 14.759/2023");
+
+        // Before 2024 it was a state holiday in some states only
+        let holiday = get_holiday(date_2023, Some("RJ")).unwrap();
+        assert_eq!(holiday.scope, HolidayScope::State("RJ".to_string()));
+        assert_eq!(holiday.legal_basis, "Lei estadual nº 4.007/2002");
+
+        // From 2024 the state holidays are superseded by the national one
+        let holidays = list_holidays(2024, Some("RJ")).unwrap();
+        let november_20: Vec<&Holiday> = holidays.iter().filter(|h| h.date == date_2024).collect();
+        assert_eq!(november_20.len(), 1);
+        assert_eq!(november_20[0].scope, HolidayScope::National);
+
+        // The same in the municipal calendar of São Paulo
+        let holidays = list_municipal_holidays(2024, "3550308").unwrap();
+        assert_eq!(holidays.iter().filter(|h| h.date == date_2024).count(), 1);
+        assert_eq!(
+            get_municipal_holiday(date_2023, "3550308").unwrap().scope,
+            HolidayScope::Municipal("3550308".to_string())
+        );
+    }
+
+    #[test]
+    fn test_historical_national_holidays() {
+        let holiday =
+            |year, month, day| is_holiday(NaiveDate::from_ymd_opt(year, month, day).unwrap(), None);
+
+        // Tiradentes was not a holiday in 1931 and 1932
+        assert_eq!(holiday(1930, 4, 21), Some(true));
+        assert_eq!(holiday(1931, 4, 21), Some(false));
+        assert_eq!(holiday(1932, 4, 21), Some(false));
+        assert_eq!(holiday(1933, 4, 21), Some(true));
+
+        // Labour Day from 1925
+        assert_eq!(holiday(1924, 5, 1), Some(false));
+        assert_eq!(holiday(1925, 5, 1), Some(true));
+
+        // Independence Day from 1890
+        assert_eq!(holiday(1889, 9, 7), Some(false));
+        assert_eq!(holiday(1890, 9, 7), Some(true));
+
+        // October 12: Descobrimento da América until 1930, then Nossa Senhora
+        // Aparecida from Lei nº 6.802/1980
+        let discovery = get_holiday(NaiveDate::from_ymd_opt(1929, 10, 12).unwrap(), None).unwrap();
+        assert_eq!(discovery.name, "Descobrimento da América");
+        assert_eq!(discovery.legal_basis, "Decreto nº 155-B/1890");
+        assert_eq!(holiday(1930, 10, 12), Some(true));
+        assert_eq!(holiday(1931, 10, 12), Some(false));
+        assert_eq!(holiday(1979, 10, 12), Some(false));
+        assert_eq!(holiday(1980, 10, 12), Some(true));
+        assert_eq!(
+            get_holiday(NaiveDate::from_ymd_opt(1980, 10, 12).unwrap(), None)
+                .unwrap()
+                .legal_basis,
+            "Lei nº 6.802/1980"
+        );
+
+        // Christmas from 1922
+        assert_eq!(holiday(1921, 12, 25), Some(false));
+        assert_eq!(holiday(1922, 12, 25), Some(true));
+
+        // Lei nº 662/1949 only applies from 1949
+        let legal_basis = |year, month, day| {
+            get_holiday(NaiveDate::from_ymd_opt(year, month, day).unwrap(), None)
+                .unwrap()
+                .legal_basis
+        };
+        assert_eq!(legal_basis(1900, 9, 7), "Decreto nº 155-B/1890");
+        assert_eq!(
+            legal_basis(1948, 12, 25),
+            "Decreto federal anterior à Lei nº 662/1949 não identificado"
+        );
+        assert_eq!(
+            legal_basis(1949, 4, 21),
+            "Lei nº 662/1949, alterada pela Lei nº 10.607/2002"
+        );
+
+        // Good Friday on April 21, 2000: both listed, Tiradentes first
+        let holidays: Vec<String> = list_holidays(2000, None)
+            .unwrap()
+            .into_iter()
+            .filter(|h| h.date == NaiveDate::from_ymd_opt(2000, 4, 21).unwrap())
+            .map(|h| h.name)
+            .collect();
+        assert_eq!(holidays, vec!["Tiradentes", "Sexta-feira Santa"]);
+    }
+
+    #[test]
+    fn test_historical_state_holidays() {
+        let holiday = |year, month, day, uf| {
+            is_holiday(NaiveDate::from_ymd_opt(year, month, day).unwrap(), Some(uf))
+        };
+
+        // No state holidays before Lei nº 9.093/1995
+        assert_eq!(holiday(1995, 7, 2, "BA"), Some(false));
+        assert_eq!(holiday(1996, 7, 2, "BA"), Some(true));
+
+        // Revolução Constitucionalista: Lei estadual nº 9.497/1997
+        assert_eq!(holiday(1996, 7, 9, "SP"), Some(false));
+        assert_eq!(holiday(1997, 7, 9, "SP"), Some(true));
+
+        // São Jorge in Rio de Janeiro from 2008
+        assert_eq!(holiday(2007, 4, 23, "RJ"), Some(false));
+        assert_eq!(holiday(2008, 4, 23, "RJ"), Some(true));
+
+        // Nossa Senhora da Penha (Easter + 8 days) in Espírito Santo from 2020
+        assert_eq!(holiday(2019, 4, 29, "ES"), Some(false));
+        assert_eq!(holiday(2020, 4, 20, "ES"), Some(true));
+
+        // Revolução Pernambucana (first Sunday of March) from 2008
+        assert_eq!(holiday(2007, 3, 4, "PE"), Some(false));
+        assert_eq!(holiday(2008, 3, 2, "PE"), Some(true));
+
+        // Dia de Santa Catarina: August 11 in 2004, the following Sunday since 2005
+        assert_eq!(holiday(2003, 8, 11, "SC"), Some(false));
+        assert_eq!(holiday(2004, 8, 11, "SC"), Some(true));
+        assert_eq!(holiday(2005, 8, 11, "SC"), Some(false));
+        assert_eq!(holiday(2005, 8, 14, "SC"), Some(true));
+
+        // Santa Catarina de Alexandria: on November 25 in 2004, on the following Sunday around it
+        assert_eq!(holiday(2003, 11, 30, "SC"), Some(true));
+        assert_eq!(holiday(2004, 11, 25, "SC"), Some(true));
+        assert_eq!(holiday(2005, 11, 25, "SC"), Some(false));
+        assert_eq!(holiday(2005, 11, 27, "SC"), Some(true));
+        assert_eq!(holiday(2031, 11, 25, "SC"), Some(true));
+
+        // Dia da Consciência Negra in Mato Grosso from 2003
+        assert_eq!(holiday(2002, 11, 20, "MT"), Some(false));
+        assert_eq!(holiday(2003, 11, 20, "MT"), Some(true));
+    }
+
+    #[test]
+    fn test_list_holidays_matches_is_holiday() {
+        for uf in [
+            None,
+            Some("AC"),
+            Some("PE"),
+            Some("RJ"),
+            Some("SC"),
+            Some("TO"),
+        ] {
+            for year in [1995, 2004, 2024] {
+                let holidays = list_holidays(year, uf).unwrap();
+                let mut date = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
+
+                while date.year() == year {
+                    let listed = holidays.iter().any(|h| h.date == date);
+                    assert_eq!(is_holiday(date, uf), Some(listed), "{date} {uf:?}");
+                    date = date.succ_opt().unwrap();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_municipal_holidays() {
+        // São Paulo: anniversary, Corpus Christi and Black Awareness Day since 2004
+        let sp = "3550308";
+        assert_eq!(
+            is_municipal_holiday(NaiveDate::from_ymd_opt(2024, 1, 25).unwrap(), sp),
+            Some(true)
+        );
+        assert_eq!(
+            is_municipal_holiday(NaiveDate::from_ymd_opt(2024, 5, 30).unwrap(), sp),
+            Some(true)
+        );
+        assert_eq!(
+            is_municipal_holiday(NaiveDate::from_ymd_opt(2010, 11, 20).unwrap(), sp),
+            Some(true)
+        );
+        assert_eq!(
+            is_municipal_holiday(NaiveDate::from_ymd_opt(2003, 11, 20).unwrap(), sp),
+            Some(false)
+        );
+
+        // State and national holidays are part of the local calendar
+        assert_eq!(
+            is_municipal_holiday(NaiveDate::from_ymd_opt(2024, 7, 9).unwrap(), sp),
+            Some(true)
+        );
+        assert_eq!(
+            is_municipal_holiday(NaiveDate::from_ymd_opt(2024, 12, 25).unwrap(), sp),
+            Some(true)
+        );
+        assert_eq!(
+            is_municipal_holiday(NaiveDate::from_ymd_opt(2024, 1, 24).unwrap(), sp),
+            Some(false)
+        );
+
+        assert_eq!(
+            is_municipal_holiday(NaiveDate::from_ymd_opt(2024, 1, 25).unwrap(), "355030"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_get_municipal_holiday_precedence() {
+        // December 8 in Salvador
+        let holiday =
+            get_municipal_holiday(NaiveDate::from_ymd_opt(2024, 12, 8).unwrap(), "2927408")
+                .unwrap();
+        assert_eq!(holiday.name, "Nossa Senhora da Conceição da Praia");
+        assert_eq!(
+            holiday.legal_basis,
+            "Lei municipal, nos termos da Lei nº 9.093/1995"
+        );
+
+        // July 2 in Salvador is a state holiday
+        let holiday =
+            get_municipal_holiday(NaiveDate::from_ymd_opt(2024, 7, 2).unwrap(), "2927408").unwrap();
+        assert_eq!(holiday.scope, HolidayScope::State("BA".to_string()));
+
+        assert_eq!(
+            get_municipal_holiday(NaiveDate::from_ymd_opt(2024, 7, 3).unwrap(), "2927408"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_list_municipal_holidays() {
+        let holidays = list_municipal_holidays(2024, "4106902").unwrap();
+        let state = list_holidays(2024, Some("PR")).unwrap();
+
+        assert_eq!(holidays.len(), state.len() + 2);
+        assert!(holidays.windows(2).all(|pair| pair[0].date <= pair[1].date));
+        assert_eq!(list_municipal_holidays(2024, "9999999"), None);
+    }
+
+    #[test]
+    fn test_municipal_holidays_cover_all_capitals() {
+        let mut ufs: Vec<&str> = municipalities().values().map(|m| m.uf.as_str()).collect();
+        ufs.sort_unstable();
+        assert_eq!(ufs, VALID_UFS);
+
+        for (ibge, municipality) in municipalities() {
+            assert_eq!(ibge.len(), 7);
+            assert_eq!(municipality_uf(ibge), Some(municipality.uf.as_str()));
+            for rule in &municipality.holidays {
+                assert!(
+                    rule.easter_offset.is_some() || (rule.month.is_some() && rule.day.is_some())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_optional_days() {
+        let days = list_optional_days(2025);
+        let summary: Vec<(String, &str, HolidayKind)> = days
+            .iter()
+            .map(|d| (d.date.format("%d/%m").to_string(), d.name.as_str(), d.kind))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("03/03".to_string(), "Carnaval", HolidayKind::Optional),
+                ("04/03".to_string(), "Carnaval", HolidayKind::Optional),
+                (
+                    "05/03".to_string(),
+                    "Quarta-feira de Cinzas",
+                    HolidayKind::HalfDay
+                ),
+                ("19/06".to_string(), "Corpus Christi", HolidayKind::Optional),
+            ]
+        );
+        assert!(days.iter().all(|d| d.scope == HolidayScope::National));
+
+        // Optional days are not holidays
+        for day in &days {
+            assert_eq!(is_holiday(day.date, None), Some(false));
+        }
+        assert_eq!(
+            get_optional_day(NaiveDate::from_ymd_opt(2025, 4, 18).unwrap()),
+            None
+        );
+        assert!(list_holidays(2025, None)
+            .unwrap()
+            .iter()
+            .all(|h| h.kind == HolidayKind::Holiday));
+    }
+
     #[test]
     fn test_calculate_easter() {
         // Easter 2024 - March 31
@@ -0,0 +1,814 @@
+//! Loan amortization schedules (Tabela Price and SAC) following Brazilian conventions.
+
+use crate::business_days::BusinessCalendar;
+use crate::currency::{format_currency, Brl, Rounding};
+use chrono::{Datelike, Months, NaiveDate};
+use std::fmt;
+
+// TYPES
+// =====
+
+/// Amortization system used to build the installment schedule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AmortizationSystem {
+    /// Tabela Price (French system): equal installments, decreasing interest.
+    Price,
+    /// Sistema de Amortização Constante: equal principal, decreasing installments.
+    Sac,
+}
+
+/// Day-count convention used to accrue interest between two dates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DayCount {
+    /// Commercial months of 30 days: the monthly rate is compounded for
+    /// `days / 30` periods, with days counted by the 30/360 rule.
+    Thirty360,
+    /// Business days over a 252-day year, as used by the CDI: the monthly
+    /// rate is converted to its effective annual rate and compounded for
+    /// `business days / 252` years. Without dates each month counts as 21
+    /// business days.
+    Business252,
+}
+
+/// IOF (Imposto sobre Operações Financeiras) rates on credit operations.
+///
+/// The tax is the additional rate over the financed amount plus the daily
+/// rate over each principal repayment, for the days between the release of
+/// the funds and its due date (limited to 365 days).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IofRates {
+    /// Rate charged per day over each principal repayment.
+    pub daily: f64,
+    /// Flat rate charged once over the financed amount.
+    pub additional: f64,
+}
+
+impl IofRates {
+    /// Rates for individuals (pessoa física): 0,0082% a day plus 0,38%.
+    pub const INDIVIDUAL: IofRates = IofRates {
+        daily: 0.000082,
+        additional: 0.0038,
+    };
+
+    /// Rates for companies (pessoa jurídica): 0,0041% a day plus 0,38%.
+    pub const COMPANY: IofRates = IofRates {
+        daily: 0.000041,
+        additional: 0.0038,
+    };
+}
+
+/// Terms of a loan to be amortized.
+///
+/// Build it with [`Loan::new`] and adjust the public fields for dates, day
+/// count, holidays and IOF.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::currency::Brl;
+/// use brazilian_utils::financing::{AmortizationSystem, DayCount, IofRates, Loan};
+/// use chrono::NaiveDate;
+///
+/// let loan = Loan {
+///     release_date: NaiveDate::from_ymd_opt(2024, 1, 15),
+///     day_count: DayCount::Business252,
+///     uf: Some("SP".to_string()),
+///     iof: Some(IofRates::INDIVIDUAL),
+///     ..Loan::new(Brl::from_reais(10_000).unwrap(), 0.0199, 12, AmortizationSystem::Price)
+/// };
+/// assert_eq!(loan.installments, 12);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Loan {
+    /// Amount released to the borrower.
+    pub principal: Brl,
+    /// Effective monthly interest rate, e.g. `0.0199` for 1,99% a.m.
+    pub monthly_rate: f64,
+    /// Number of monthly installments.
+    pub installments: u32,
+    /// Amortization system.
+    pub system: AmortizationSystem,
+    /// Day-count convention used when dates are known.
+    pub day_count: DayCount,
+    /// Date the funds are released. Required for date-based interest and IOF.
+    pub release_date: Option<NaiveDate>,
+    /// Due date of the first installment. Defaults to one month after the release.
+    pub first_due_date: Option<NaiveDate>,
+    /// State (UF) whose holidays are skipped when counting business days.
+    pub uf: Option<String>,
+    /// IOF rates to compute the tax due on the operation.
+    pub iof: Option<IofRates>,
+}
+
+impl Loan {
+    /// Creates a loan with monthly periods, the 30/360 convention and no IOF.
+    pub fn new(
+        principal: Brl,
+        monthly_rate: f64,
+        installments: u32,
+        system: AmortizationSystem,
+    ) -> Self {
+        Loan {
+            principal,
+            monthly_rate,
+            installments,
+            system,
+            day_count: DayCount::Thirty360,
+            release_date: None,
+            first_due_date: None,
+            uf: None,
+            iof: None,
+        }
+    }
+}
+
+/// A single installment of an amortization schedule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Installment {
+    /// Installment number, starting at 1.
+    pub number: u32,
+    /// Due date, when the loan has a release date.
+    pub due_date: Option<NaiveDate>,
+    /// Total amount paid in the installment (principal plus interest).
+    pub payment: Brl,
+    /// Part of the payment that repays the principal (amortização).
+    pub principal: Brl,
+    /// Part of the payment that pays interest.
+    pub interest: Brl,
+    /// Outstanding balance after the payment (saldo devedor).
+    pub balance: Brl,
+}
+
+/// Amortization schedule of a loan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    /// The installments, in payment order.
+    pub installments: Vec<Installment>,
+    /// IOF due on the operation (zero when no IOF rates were given).
+    pub iof: Brl,
+}
+
+impl Schedule {
+    /// Sum of all payments.
+    pub fn total_payment(&self) -> Brl {
+        self.installments.iter().map(|i| i.payment).sum()
+    }
+
+    /// Sum of all interest paid.
+    pub fn total_interest(&self) -> Brl {
+        self.installments.iter().map(|i| i.interest).sum()
+    }
+
+    /// Sum of all principal repaid, equal to the loan principal.
+    pub fn total_principal(&self) -> Brl {
+        self.installments.iter().map(|i| i.principal).sum()
+    }
+}
+
+impl fmt::Display for Schedule {
+    /// Writes the schedule as a table with values formatted by [`format_currency`].
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let money = |value: Brl| format_currency(value).unwrap_or_default();
+
+        writeln!(
+            f,
+            "{:>4} {:>10} {:>18} {:>18} {:>18} {:>18}",
+            "Nº", "Vencimento", "Prestação", "Amortização", "Juros", "Saldo devedor"
+        )?;
+
+        for installment in &self.installments {
+            let due_date = installment
+                .due_date
+                .map(|date| date.format("%d/%m/%Y").to_string())
+                .unwrap_or_else(|| "-".to_string());
+
+            writeln!(
+                f,
+                "{:>4} {:>10} {:>18} {:>18} {:>18} {:>18}",
+                installment.number,
+                due_date,
+                money(installment.payment),
+                money(installment.principal),
+                money(installment.interest),
+                money(installment.balance)
+            )?;
+        }
+
+        write!(
+            f,
+            "{:>4} {:>10} {:>18} {:>18} {:>18}",
+            "",
+            "Total",
+            money(self.total_payment()),
+            money(self.total_principal()),
+            money(self.total_interest())
+        )?;
+
+        if !self.iof.is_zero() {
+            write!(f, "\nIOF: {}", money(self.iof))?;
+        }
+
+        Ok(())
+    }
+}
+
+// OPERATIONS
+// ==========
+
+/// Builds the amortization schedule of a loan.
+///
+/// Interest of each installment is computed over the outstanding balance and
+/// rounded to the centavo (half to even). The payment of the Tabela Price is
+/// rounded once and the last installment absorbs the rounding differences,
+/// while the SAC spreads leftover centavos of the principal over the first
+/// installments, so the principal is always repaid exactly.
+///
+/// # Arguments
+///
+/// * `loan` - The loan terms.
+///
+/// # Returns
+///
+/// The schedule, or `None` if the terms are invalid: no installments, a
+/// non-positive principal, a negative or non-finite rate, an invalid UF or a
+/// first due date that is not after the release date.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::currency::Brl;
+/// use brazilian_utils::financing::{schedule, AmortizationSystem, Loan};
+///
+/// let loan = Loan::new(Brl::from_reais(1_000).unwrap(), 0.01, 3, AmortizationSystem::Price);
+/// let table = schedule(&loan).unwrap();
+///
+/// assert_eq!(table.installments[0].payment, Brl::from_centavos(34_002));
+/// assert_eq!(table.installments[0].interest, Brl::from_centavos(1_000));
+/// assert_eq!(table.total_principal(), loan.principal);
+/// assert!(table.installments[2].balance.is_zero());
+/// ```
+pub fn schedule(loan: &Loan) -> Option<Schedule> {
+    if loan.installments == 0
+        || !loan.principal.is_positive()
+        || !loan.monthly_rate.is_finite()
+        || loan.monthly_rate < 0.0
+    {
+        return None;
+    }
+
+    let calendar = BusinessCalendar::new(loan.uf.as_deref())?;
+    let due_dates = due_dates(loan)?;
+    let factors = period_factors(loan, &due_dates, &calendar);
+
+    let installments = match loan.system {
+        AmortizationSystem::Price => price_installments(loan, &due_dates, &factors)?,
+        AmortizationSystem::Sac => sac_installments(loan, &due_dates, &factors)?,
+    };
+
+    let iof = match loan.iof {
+        Some(rates) => compute_iof(loan, &installments, rates)?,
+        None => Brl::ZERO,
+    };
+
+    Some(Schedule { installments, iof })
+}
+
+/// Computes the fixed payment of the Tabela Price for monthly periods.
+///
+/// # Arguments
+///
+/// * `principal` - The financed amount.
+/// * `monthly_rate` - The effective monthly interest rate.
+/// * `installments` - The number of installments.
+///
+/// # Returns
+///
+/// The installment rounded to the centavo, or `None` for invalid terms.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::currency::Brl;
+/// use brazilian_utils::financing::price_payment;
+///
+/// assert_eq!(
+///     price_payment(Brl::from_reais(10_000).unwrap(), 0.02, 12),
+///     Some(Brl::from_centavos(94_560))
+/// );
+/// ```
+pub fn price_payment(principal: Brl, monthly_rate: f64, installments: u32) -> Option<Brl> {
+    if installments == 0
+        || !principal.is_positive()
+        || !monthly_rate.is_finite()
+        || monthly_rate < 0.0
+    {
+        return None;
+    }
+
+    // PMT = PV * i / (1 - (1 + i)^-n), or PV / n without interest
+    let factor = if monthly_rate == 0.0 {
+        1.0 / installments as f64
+    } else {
+        monthly_rate / (1.0 - (1.0 + monthly_rate).powf(-(installments as f64)))
+    };
+
+    principal.checked_mul_f64(factor, Rounding::HalfEven)
+}
+
+/// Converts an effective monthly rate to the equivalent effective annual rate.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::financing::monthly_to_annual_rate;
+///
+/// assert!((monthly_to_annual_rate(0.01) - 0.126825).abs() < 1e-6);
+/// ```
+pub fn monthly_to_annual_rate(monthly_rate: f64) -> f64 {
+    (1.0 + monthly_rate).powi(12) - 1.0
+}
+
+/// Converts an effective annual rate to the equivalent effective monthly rate.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::financing::annual_to_monthly_rate;
+///
+/// assert!((annual_to_monthly_rate(0.126825) - 0.01).abs() < 1e-6);
+/// ```
+pub fn annual_to_monthly_rate(annual_rate: f64) -> f64 {
+    (1.0 + annual_rate).powf(1.0 / 12.0) - 1.0
+}
+
+/// Counts the days between two dates with the 30/360 convention.
+///
+/// Every month is treated as having 30 days; day 31 counts as day 30.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::financing::days_30_360;
+/// use chrono::NaiveDate;
+///
+/// let start = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+/// let end = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+/// assert_eq!(days_30_360(start, end), 31);
+/// ```
+pub fn days_30_360(start: NaiveDate, end: NaiveDate) -> i64 {
+    let start_day = start.day().min(30) as i64;
+    let end_day = if end.day() == 31 && start_day == 30 {
+        30
+    } else {
+        end.day() as i64
+    };
+
+    (end.year() - start.year()) as i64 * 360
+        + (end.month() as i64 - start.month() as i64) * 30
+        + (end_day - start_day)
+}
+
+// HELPER FUNCTIONS
+// ================
+
+/// Computes the due date of every installment, if the loan has dates.
+fn due_dates(loan: &Loan) -> Option<Vec<Option<NaiveDate>>> {
+    let Some(release) = loan.release_date else {
+        return Some(vec![None; loan.installments as usize]);
+    };
+
+    let first_due = match loan.first_due_date {
+        Some(date) => date,
+        None => release.checked_add_months(Months::new(1))?,
+    };
+
+    if first_due <= release {
+        return None;
+    }
+
+    (0..loan.installments)
+        .map(|index| first_due.checked_add_months(Months::new(index)).map(Some))
+        .collect()
+}
+
+/// Computes the interest factor `(1 + rate)^t` of every period.
+fn period_factors(
+    loan: &Loan,
+    due_dates: &[Option<NaiveDate>],
+    calendar: &BusinessCalendar,
+) -> Vec<f64> {
+    let monthly = 1.0 + loan.monthly_rate;
+    let annual = 1.0 + monthly_to_annual_rate(loan.monthly_rate);
+    let mut previous = loan.release_date;
+
+    due_dates
+        .iter()
+        .map(|due_date| {
+            let factor = match (loan.day_count, previous, *due_date) {
+                (DayCount::Thirty360, Some(start), Some(end)) => {
+                    monthly.powf(days_30_360(start, end) as f64 / 30.0)
+                }
+                (DayCount::Business252, Some(start), Some(end)) => {
+                    let days = calendar.count_business_days(start, end);
+                    annual.powf(days as f64 / 252.0)
+                }
+                _ => monthly,
+            };
+            previous = *due_date;
+            factor
+        })
+        .collect()
+}
+
+/// Builds the installments of the Tabela Price.
+fn price_installments(
+    loan: &Loan,
+    due_dates: &[Option<NaiveDate>],
+    factors: &[f64],
+) -> Option<Vec<Installment>> {
+    // Present value of one unit paid at each due date
+    let mut accumulated = 1.0;
+    let mut annuity = 0.0;
+    for factor in factors {
+        accumulated *= factor;
+        annuity += 1.0 / accumulated;
+    }
+
+    let payment = loan
+        .principal
+        .checked_mul_f64(1.0 / annuity, Rounding::HalfEven)?;
+
+    build_installments(loan, due_dates, factors, |_, interest| {
+        payment.checked_sub(interest)
+    })
+}
+
+/// Builds the installments of the SAC.
+fn sac_installments(
+    loan: &Loan,
+    due_dates: &[Option<NaiveDate>],
+    factors: &[f64],
+) -> Option<Vec<Installment>> {
+    let parts = loan.principal.allocate(loan.installments as usize)?;
+    build_installments(loan, due_dates, factors, |index, _| Some(parts[index]))
+}
+
+/// Builds the installments given a rule for the principal repaid in each one.
+///
+/// The last installment always repays the whole remaining balance.
+fn build_installments<F>(
+    loan: &Loan,
+    due_dates: &[Option<NaiveDate>],
+    factors: &[f64],
+    principal_for: F,
+) -> Option<Vec<Installment>>
+where
+    F: Fn(usize, Brl) -> Option<Brl>,
+{
+    let mut balance = loan.principal;
+    let mut installments = Vec::with_capacity(factors.len());
+
+    for (index, (factor, due_date)) in factors.iter().zip(due_dates).enumerate() {
+        let interest = balance.checked_mul_f64(factor - 1.0, Rounding::HalfEven)?;
+
+        let principal = if index + 1 == factors.len() {
+            balance
+        } else {
+            principal_for(index, interest)?.min(balance)
+        };
+
+        balance = balance.checked_sub(principal)?;
+
+        installments.push(Installment {
+            number: index as u32 + 1,
+            due_date: *due_date,
+            payment: principal.checked_add(interest)?,
+            principal,
+            interest,
+            balance,
+        });
+    }
+
+    Some(installments)
+}
+
+/// Computes the IOF due on the operation.
+fn compute_iof(loan: &Loan, installments: &[Installment], rates: IofRates) -> Option<Brl> {
+    let additional = loan
+        .principal
+        .checked_mul_f64(rates.additional, Rounding::HalfEven)?;
+
+    let mut daily_total = 0.0;
+    for installment in installments {
+        let days = match (loan.release_date, installment.due_date) {
+            (Some(release), Some(due_date)) => (due_date - release).num_days(),
+            _ => 30 * installment.number as i64,
+        };
+
+        daily_total += installment.principal.centavos() as f64 * rates.daily * days.min(365) as f64;
+    }
+
+    let daily = Brl::from_f64(daily_total / 100.0, Rounding::HalfEven)?;
+    additional.checked_add(daily)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reais(value: i64) -> Brl {
+        Brl::from_reais(value).unwrap()
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_price_schedule() {
+        let loan = Loan::new(reais(1_000), 0.01, 3, AmortizationSystem::Price);
+        let table = schedule(&loan).unwrap();
+
+        let rows: Vec<(i64, i64, i64, i64)> = table
+            .installments
+            .iter()
+            .map(|i| {
+                (
+                    i.payment.centavos(),
+                    i.principal.centavos(),
+                    i.interest.centavos(),
+                    i.balance.centavos(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            rows,
+            vec![
+                (34_002, 33_002, 1_000, 66_998),
+                (34_002, 33_332, 670, 33_666),
+                (34_003, 33_666, 337, 0),
+            ]
+        );
+        assert_eq!(table.total_principal(), reais(1_000));
+        assert_eq!(table.total_interest(), Brl::from_centavos(2_007));
+        assert_eq!(table.iof, Brl::ZERO);
+    }
+
+    #[test]
+    fn test_price_payment() {
+        assert_eq!(
+            price_payment(reais(10_000), 0.02, 12),
+            Some(Brl::from_centavos(94_560))
+        );
+        assert_eq!(price_payment(reais(1_200), 0.0, 12), Some(reais(100)));
+        assert_eq!(price_payment(reais(1_000), 0.01, 0), None);
+        assert_eq!(price_payment(reais(1_000), -0.01, 12), None);
+        assert_eq!(price_payment(reais(1_000), f64::NAN, 12), None);
+        assert_eq!(price_payment(Brl::ZERO, 0.01, 12), None);
+
+        // Same as the first installment of the schedule
+        for (principal, rate, installments) in [(1_000, 0.01, 3), (250_000, 0.0085, 360)] {
+            let loan = Loan::new(
+                reais(principal),
+                rate,
+                installments,
+                AmortizationSystem::Price,
+            );
+            assert_eq!(
+                price_payment(reais(principal), rate, installments),
+                Some(schedule(&loan).unwrap().installments[0].payment)
+            );
+        }
+    }
+
+    #[test]
+    fn test_sac_schedule() {
+        let loan = Loan::new(
+            Brl::from_centavos(100_000),
+            0.02,
+            3,
+            AmortizationSystem::Sac,
+        );
+        let table = schedule(&loan).unwrap();
+
+        let principal: Vec<i64> = table
+            .installments
+            .iter()
+            .map(|i| i.principal.centavos())
+            .collect();
+        let interest: Vec<i64> = table
+            .installments
+            .iter()
+            .map(|i| i.interest.centavos())
+            .collect();
+
+        // R$ 1.000,00 / 3 leaves one centavo for the first installment
+        assert_eq!(principal, vec![33_334, 33_333, 33_333]);
+        assert_eq!(interest, vec![2_000, 1_333, 667]);
+        assert_eq!(table.installments[0].payment, Brl::from_centavos(35_334));
+        assert_eq!(table.total_principal(), Brl::from_centavos(100_000));
+        assert!(table.installments[2].balance.is_zero());
+    }
+
+    #[test]
+    fn test_schedule_principal_is_always_repaid_exactly() {
+        for system in [AmortizationSystem::Price, AmortizationSystem::Sac] {
+            for installments in [1, 7, 24, 360] {
+                let loan = Loan::new(Brl::from_centavos(1_234_567), 0.0149, installments, system);
+                let table = schedule(&loan).unwrap();
+
+                assert_eq!(table.installments.len(), installments as usize);
+                assert_eq!(table.total_principal(), loan.principal);
+                assert!(table.installments.last().unwrap().balance.is_zero());
+                assert_eq!(
+                    table.total_payment(),
+                    table.total_principal() + table.total_interest()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_price_last_installment_absorbs_rounding() {
+        let loan = Loan::new(reais(10_000), 0.0199, 36, AmortizationSystem::Price);
+        let table = schedule(&loan).unwrap();
+        let payment = table.installments[0].payment;
+
+        for installment in &table.installments[..35] {
+            assert_eq!(installment.payment, payment);
+        }
+
+        let last = table.installments[35].payment;
+        assert!((last.centavos() - payment.centavos()).abs() <= 36);
+    }
+
+    #[test]
+    fn test_schedule_with_dates_thirty_360() {
+        let loan = Loan {
+            release_date: Some(date(2024, 1, 15)),
+            ..Loan::new(reais(1_000), 0.01, 3, AmortizationSystem::Price)
+        };
+        let table = schedule(&loan).unwrap();
+
+        let due_dates: Vec<_> = table.installments.iter().map(|i| i.due_date).collect();
+        assert_eq!(
+            due_dates,
+            vec![
+                Some(date(2024, 2, 15)),
+                Some(date(2024, 3, 15)),
+                Some(date(2024, 4, 15))
+            ]
+        );
+
+        // Monthly 30/360 periods match the schedule without dates
+        let undated = schedule(&Loan::new(reais(1_000), 0.01, 3, AmortizationSystem::Price));
+        let payments: Vec<_> = table.installments.iter().map(|i| i.payment).collect();
+        let undated_payments: Vec<_> = undated
+            .unwrap()
+            .installments
+            .iter()
+            .map(|i| i.payment)
+            .collect();
+        assert_eq!(payments, undated_payments);
+    }
+
+    #[test]
+    fn test_schedule_with_grace_period() {
+        // First installment 45 days (30/360) after the release
+        let loan = Loan {
+            release_date: Some(date(2024, 1, 1)),
+            first_due_date: Some(date(2024, 2, 16)),
+            ..Loan::new(reais(1_000), 0.01, 2, AmortizationSystem::Sac)
+        };
+        let table = schedule(&loan).unwrap();
+
+        // 1000 * (1.01^1.5 - 1) = 15.0375...
+        assert_eq!(table.installments[0].interest, Brl::from_centavos(1_504));
+        assert_eq!(table.installments[1].due_date, Some(date(2024, 3, 16)));
+        assert_eq!(table.installments[1].interest, Brl::from_centavos(500));
+    }
+
+    #[test]
+    fn test_schedule_business_252() {
+        let loan = Loan {
+            release_date: Some(date(2024, 1, 2)),
+            day_count: DayCount::Business252,
+            ..Loan::new(reais(1_000), 0.01, 1, AmortizationSystem::Price)
+        };
+        let table = schedule(&loan).unwrap();
+
+        // 2024-01-02 to 2024-02-02: 23 business days
+        let expected = 1000.0 * ((1.01f64).powi(12).powf(23.0 / 252.0) - 1.0);
+        assert_eq!(
+            table.installments[0].interest,
+            Brl::from_f64(expected, Rounding::HalfEven).unwrap()
+        );
+
+        // Without dates a month is 21 business days, i.e. exactly the monthly rate
+        let undated = Loan {
+            day_count: DayCount::Business252,
+            ..Loan::new(reais(1_000), 0.01, 1, AmortizationSystem::Price)
+        };
+        assert_eq!(
+            schedule(&undated).unwrap().installments[0].interest,
+            reais(10)
+        );
+    }
+
+    #[test]
+    fn test_schedule_invalid_terms() {
+        let base = Loan::new(reais(1_000), 0.01, 3, AmortizationSystem::Price);
+
+        let invalid_uf = Loan {
+            uf: Some("XX".to_string()),
+            ..base.clone()
+        };
+        assert_eq!(schedule(&invalid_uf), None);
+
+        let due_before_release = Loan {
+            release_date: Some(date(2024, 2, 1)),
+            first_due_date: Some(date(2024, 1, 1)),
+            ..base.clone()
+        };
+        assert_eq!(schedule(&due_before_release), None);
+
+        let negative_principal = Loan {
+            principal: reais(-1),
+            ..base
+        };
+        assert_eq!(schedule(&negative_principal), None);
+    }
+
+    #[test]
+    fn test_iof() {
+        let loan = Loan {
+            release_date: Some(date(2024, 1, 1)),
+            iof: Some(IofRates::INDIVIDUAL),
+            ..Loan::new(reais(1_200), 0.0, 12, AmortizationSystem::Sac)
+        };
+        let table = schedule(&loan).unwrap();
+
+        // 0.38% of 1200 = 4.56, plus 100 * 0.000082 * days for each month
+        let days: i64 = (1..=12)
+            .map(|month| {
+                (date(2024, 1, 1)
+                    .checked_add_months(Months::new(month))
+                    .unwrap()
+                    - date(2024, 1, 1))
+                .num_days()
+                .min(365)
+            })
+            .sum();
+        let expected = 4.56 + 100.0 * 0.000082 * days as f64;
+        assert_eq!(
+            table.iof,
+            Brl::from_f64(expected, Rounding::HalfEven).unwrap()
+        );
+
+        // Without dates every installment is 30 days apart
+        let undated = Loan {
+            iof: Some(IofRates::COMPANY),
+            ..Loan::new(reais(1_000), 0.0, 1, AmortizationSystem::Price)
+        };
+        assert_eq!(
+            schedule(&undated).unwrap().iof,
+            Brl::from_centavos(380 + 123)
+        );
+    }
+
+    #[test]
+    fn test_rate_conversion() {
+        let annual = monthly_to_annual_rate(0.015);
+        assert!((annual_to_monthly_rate(annual) - 0.015).abs() < 1e-12);
+        assert_eq!(monthly_to_annual_rate(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_days_30_360() {
+        assert_eq!(days_30_360(date(2024, 1, 15), date(2024, 2, 15)), 30);
+        assert_eq!(days_30_360(date(2024, 1, 30), date(2024, 1, 31)), 0);
+        assert_eq!(days_30_360(date(2024, 1, 29), date(2024, 1, 31)), 2);
+        assert_eq!(days_30_360(date(2024, 2, 28), date(2024, 3, 28)), 30);
+        assert_eq!(days_30_360(date(2023, 12, 1), date(2024, 12, 1)), 360);
+    }
+
+    #[test]
+    fn test_schedule_display() {
+        let loan = Loan {
+            release_date: Some(date(2024, 1, 15)),
+            iof: Some(IofRates::INDIVIDUAL),
+            ..Loan::new(reais(1_000), 0.01, 2, AmortizationSystem::Sac)
+        };
+        let text = schedule(&loan).unwrap().to_string();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].contains("Amortização"));
+        assert!(lines[1].contains("15/02/2024"));
+        assert!(lines[1].contains("R$ 510,00"));
+        assert!(lines[1].contains("R$ 500,00"));
+        assert!(lines[3].contains("Total"));
+        assert!(lines[4].starts_with("IOF: R$ "));
+    }
+}
@@ -0,0 +1,1421 @@
+//! User-defined and data-driven holiday calendars.
+
+use crate::date_utils::{
+    calculate_easter, get_optional_day, holidays_on, is_valid_uf, municipal_holidays_on,
+    municipality_uf, Holiday, HolidayKind, HolidayScope,
+};
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+// TYPES
+// =====
+
+/// How the date of a holiday is found in each year.
+///
+/// In JSON and TOML files the variant is given by the `type` field, in snake
+/// case, next to the variant's own fields.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HolidayDate {
+    /// The same day every year, e.g. `{"type": "fixed", "month": 7, "day": 9}`.
+    Fixed { month: u32, day: u32 },
+    /// A number of days after (or, if negative, before) Easter Sunday,
+    /// e.g. `{"type": "easter_offset", "days": 60}` for Corpus Christi.
+    EasterOffset { days: i64 },
+    /// The `nth` weekday of a month, e.g. the second Sunday of May. A negative
+    /// `nth` counts from the end of the month: `-1` is the last one.
+    /// Weekdays are written in English, e.g. `"sunday"` or `"Sun"`.
+    NthWeekday {
+        month: u32,
+        weekday: Weekday,
+        nth: i8,
+    },
+    /// A single date, e.g. `{"type": "date", "date": "2024-05-10"}`.
+    Date { date: NaiveDate },
+}
+
+/// A holiday rule of a [`HolidayCalendar`].
+///
+/// A rule either adds a holiday or, if [`remove`](Self::remove) is set,
+/// cancels every holiday that an earlier rule or the built-in calendar puts
+/// on the same date. Combined with the year bounds, this expresses a law
+/// that moves or abolishes a holiday.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::holiday_calendar::HolidayRule;
+/// use chrono::Weekday;
+///
+/// let rule = HolidayRule::nth_weekday("Dia das Mães", 5, Weekday::Sun, 2).since(1932);
+/// assert_eq!(rule.since, Some(1932));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct HolidayRule {
+    /// The name of the holiday.
+    pub name: String,
+    /// How the date of the holiday is found.
+    #[serde(flatten)]
+    pub date: HolidayDate,
+    /// Whether it is a holiday or a ponto facultativo.
+    #[serde(default)]
+    pub kind: HolidayKind,
+    /// The first year in which the rule applies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub since: Option<i32>,
+    /// The last year in which the rule applies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<i32>,
+    /// The law that establishes the holiday.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub legal_basis: Option<String>,
+    /// Whether the rule cancels the holidays on its date instead of adding one.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub remove: bool,
+}
+
+/// A holiday calendar made of the built-in national, state and municipal
+/// holidays and of user-defined rules.
+///
+/// The rules are applied in order on top of the built-in holidays, so a
+/// calendar can add company or municipal holidays, cancel a built-in
+/// holiday from a given year on, or move it to another date. Calendars can
+/// be built in code or loaded from JSON or TOML files, and used wherever
+/// [`is_holiday`](crate::date_utils::is_holiday) is used, e.g. to build a
+/// [`BusinessCalendar`](crate::business_days::BusinessCalendar).
+///
+/// Built-in pontos facultativos are included with their
+/// [`HolidayKind`], but only [`HolidayKind::Holiday`] days are reported by
+/// [`is_holiday`](Self::is_holiday).
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::holiday_calendar::{HolidayCalendar, HolidayRule};
+/// use chrono::NaiveDate;
+///
+/// let calendar = HolidayCalendar::new(Some("SP"))
+///     .unwrap()
+///     .with_rule(HolidayRule::fixed("Aniversário da empresa", 3, 15).since(2010));
+///
+/// assert!(calendar.is_holiday(NaiveDate::from_ymd_opt(2024, 3, 15).unwrap()));
+/// assert!(!calendar.is_holiday(NaiveDate::from_ymd_opt(2009, 3, 15).unwrap()));
+/// assert!(calendar.is_holiday(NaiveDate::from_ymd_opt(2024, 7, 9).unwrap()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HolidayCalendar {
+    uf: Option<String>,
+    ibge: Option<String>,
+    builtin: bool,
+    rules: Vec<HolidayRule>,
+}
+
+/// Error returned when a holiday calendar file cannot be loaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HolidayCalendarError {
+    /// The file is not valid JSON, TOML or iCalendar, or does not follow the
+    /// calendar format. Holds the parser's message.
+    Parse(String),
+    /// The file gives an invalid UF.
+    InvalidUf(String),
+    /// The file gives a municipality without holiday data.
+    UnknownMunicipality(String),
+    /// A rule can never match a date, e.g. month 13 or `since` after `until`.
+    /// Holds the rule's name.
+    InvalidRule(String),
+}
+
+impl fmt::Display for HolidayCalendarError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HolidayCalendarError::Parse(message) => {
+                write!(f, "invalid holiday calendar: {}", message)
+            }
+            HolidayCalendarError::InvalidUf(uf) => write!(f, "invalid UF: {}", uf),
+            HolidayCalendarError::UnknownMunicipality(ibge) => {
+                write!(f, "unknown municipality: {}", ibge)
+            }
+            HolidayCalendarError::InvalidRule(name) => {
+                write!(f, "invalid holiday rule: {}", name)
+            }
+        }
+    }
+}
+
+impl Error for HolidayCalendarError {}
+
+/// Layout of a holiday calendar file.
+#[derive(Debug, Deserialize)]
+struct CalendarFile {
+    uf: Option<String>,
+    ibge: Option<String>,
+    #[serde(default = "default_builtin")]
+    builtin: bool,
+    #[serde(default)]
+    holidays: Vec<HolidayRule>,
+}
+
+fn default_builtin() -> bool {
+    true
+}
+
+impl HolidayDate {
+    /// Returns the date in the given year, or `None` if there is none (e.g.
+    /// February 29 in a common year, or a single date in another year).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::holiday_calendar::HolidayDate;
+    /// use chrono::{NaiveDate, Weekday};
+    ///
+    /// let last_friday = HolidayDate::NthWeekday { month: 11, weekday: Weekday::Fri, nth: -1 };
+    /// assert_eq!(last_friday.in_year(2024), NaiveDate::from_ymd_opt(2024, 11, 29));
+    ///
+    /// let corpus_christi = HolidayDate::EasterOffset { days: 60 };
+    /// assert_eq!(corpus_christi.in_year(2024), NaiveDate::from_ymd_opt(2024, 5, 30));
+    /// ```
+    pub fn in_year(&self, year: i32) -> Option<NaiveDate> {
+        match *self {
+            HolidayDate::Fixed { month, day } => NaiveDate::from_ymd_opt(year, month, day),
+            HolidayDate::EasterOffset { days } => {
+                NaiveDate::from_ymd_opt(year, 1, 1)?;
+                calculate_easter(year).checked_add_signed(chrono::Duration::days(days))
+            }
+            HolidayDate::NthWeekday {
+                month,
+                weekday,
+                nth,
+            } => nth_weekday(year, month, weekday, nth),
+            HolidayDate::Date { date } => (date.year() == year).then_some(date),
+        }
+    }
+
+    /// Whether the date can match a day at all.
+    fn is_valid(&self) -> bool {
+        match *self {
+            HolidayDate::Fixed { month, day } => {
+                NaiveDate::from_ymd_opt(2000, month, day).is_some()
+            }
+            HolidayDate::EasterOffset { days } => days.abs() <= 366,
+            HolidayDate::NthWeekday { month, nth, .. } => {
+                (1..=12).contains(&month) && nth != 0 && (-5..=5).contains(&nth)
+            }
+            HolidayDate::Date { .. } => true,
+        }
+    }
+}
+
+impl HolidayRule {
+    /// Creates a rule for a holiday with the given date.
+    pub fn new(name: &str, date: HolidayDate) -> Self {
+        HolidayRule {
+            name: name.to_string(),
+            date,
+            kind: HolidayKind::Holiday,
+            since: None,
+            until: None,
+            legal_basis: None,
+            remove: false,
+        }
+    }
+
+    /// Creates a rule for a holiday on the same day every year.
+    pub fn fixed(name: &str, month: u32, day: u32) -> Self {
+        Self::new(name, HolidayDate::Fixed { month, day })
+    }
+
+    /// Creates a rule for a holiday a number of days after Easter Sunday.
+    pub fn easter_offset(name: &str, days: i64) -> Self {
+        Self::new(name, HolidayDate::EasterOffset { days })
+    }
+
+    /// Creates a rule for a holiday on the `nth` weekday of a month
+    /// (negative `nth` counts from the end of the month).
+    pub fn nth_weekday(name: &str, month: u32, weekday: Weekday, nth: i8) -> Self {
+        Self::new(
+            name,
+            HolidayDate::NthWeekday {
+                month,
+                weekday,
+                nth,
+            },
+        )
+    }
+
+    /// Creates a rule for a holiday on a single date.
+    pub fn on(name: &str, date: NaiveDate) -> Self {
+        Self::new(name, HolidayDate::Date { date })
+    }
+
+    /// Applies the rule only from the given year on.
+    pub fn since(mut self, year: i32) -> Self {
+        self.since = Some(year);
+        self
+    }
+
+    /// Applies the rule only up to the given year.
+    pub fn until(mut self, year: i32) -> Self {
+        self.until = Some(year);
+        self
+    }
+
+    /// Sets the kind of the holiday.
+    pub fn kind(mut self, kind: HolidayKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Sets the law that establishes the holiday.
+    pub fn legal_basis(mut self, legal_basis: &str) -> Self {
+        self.legal_basis = Some(legal_basis.to_string());
+        self
+    }
+
+    /// Turns the rule into one that cancels the holidays on its date.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::holiday_calendar::{HolidayCalendar, HolidayRule};
+    /// use chrono::NaiveDate;
+    ///
+    /// // A state law abolishing a holiday from 2025 on
+    /// let calendar = HolidayCalendar::new(Some("SP"))
+    ///     .unwrap()
+    ///     .with_rule(HolidayRule::fixed("Revolução Constitucionalista", 7, 9).since(2025).removing());
+    ///
+    /// assert!(calendar.is_holiday(NaiveDate::from_ymd_opt(2024, 7, 9).unwrap()));
+    /// assert!(!calendar.is_holiday(NaiveDate::from_ymd_opt(2025, 7, 9).unwrap()));
+    /// ```
+    pub fn removing(mut self) -> Self {
+        self.remove = true;
+        self
+    }
+
+    /// Returns the date of the rule in the given year, if the rule applies in it.
+    pub fn date_in(&self, year: i32) -> Option<NaiveDate> {
+        if self.since.is_some_and(|since| year < since)
+            || self.until.is_some_and(|until| year > until)
+        {
+            return None;
+        }
+
+        self.date.in_year(year)
+    }
+
+    /// Whether the rule applies on the given date.
+    fn matches(&self, date: NaiveDate) -> bool {
+        self.date_in(date.year()) == Some(date)
+    }
+
+    /// Builds the holiday added by the rule on a date.
+    fn holiday(&self, date: NaiveDate) -> Holiday {
+        Holiday {
+            date,
+            name: self.name.clone(),
+            scope: HolidayScope::Custom,
+            kind: self.kind,
+            legal_basis: self.legal_basis.clone().unwrap_or_default(),
+        }
+    }
+}
+
+impl HolidayCalendar {
+    /// Creates a calendar with the built-in national holidays and, if given,
+    /// the holidays of a state.
+    ///
+    /// # Returns
+    ///
+    /// The calendar, or `None` if the UF is invalid.
+    pub fn new(uf: Option<&str>) -> Option<Self> {
+        if let Some(uf) = uf {
+            if !is_valid_uf(uf) {
+                return None;
+            }
+        }
+
+        Some(HolidayCalendar {
+            uf: uf.map(str::to_string),
+            ibge: None,
+            builtin: true,
+            rules: Vec::new(),
+        })
+    }
+
+    /// Creates a calendar with the built-in national, state and municipal
+    /// holidays of a municipality.
+    ///
+    /// # Returns
+    ///
+    /// The calendar, or `None` if there is no holiday data for the municipality.
+    pub fn for_municipality(ibge: &str) -> Option<Self> {
+        let uf = municipality_uf(ibge)?;
+
+        Some(HolidayCalendar {
+            uf: Some(uf.to_string()),
+            ibge: Some(ibge.to_string()),
+            builtin: true,
+            rules: Vec::new(),
+        })
+    }
+
+    /// Creates a calendar without any built-in holiday, only with the rules
+    /// added to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::holiday_calendar::{HolidayCalendar, HolidayRule};
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = HolidayCalendar::empty().with_rule(HolidayRule::fixed("Natal", 12, 25));
+    ///
+    /// assert!(calendar.is_holiday(NaiveDate::from_ymd_opt(2024, 12, 25).unwrap()));
+    /// assert!(!calendar.is_holiday(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()));
+    /// ```
+    pub fn empty() -> Self {
+        HolidayCalendar {
+            uf: None,
+            ibge: None,
+            builtin: false,
+            rules: Vec::new(),
+        }
+    }
+
+    /// Adds a rule, applied after the rules already in the calendar.
+    pub fn with_rule(mut self, rule: HolidayRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Loads a calendar from JSON.
+    ///
+    /// The document may give a `uf` or an `ibge` code whose built-in holidays
+    /// are included, `builtin: false` to include none (not even the national
+    /// ones), and the list of `holidays` rules (see [`HolidayRule`] and
+    /// [`HolidayDate`] for their fields).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::holiday_calendar::HolidayCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = HolidayCalendar::from_json(r#"{
+    ///     "uf": "RJ",
+    ///     "holidays": [
+    ///         {"name": "Dia do Comerciário", "type": "nth_weekday", "month": 10, "weekday": "monday", "nth": 3},
+    ///         {"name": "Recesso", "type": "date", "date": "2024-12-24", "kind": "optional"}
+    ///     ]
+    /// }"#).unwrap();
+    ///
+    /// assert!(calendar.is_holiday(NaiveDate::from_ymd_opt(2024, 10, 21).unwrap()));
+    /// assert!(!calendar.is_holiday(NaiveDate::from_ymd_opt(2024, 12, 24).unwrap()));
+    /// assert_eq!(calendar.list_holidays(2024).last().unwrap().name, "Natal");
+    /// ```
+    pub fn from_json(json: &str) -> Result<Self, HolidayCalendarError> {
+        let file: CalendarFile = serde_json::from_str(json)
+            .map_err(|error| HolidayCalendarError::Parse(error.to_string()))?;
+        Self::from_file(file)
+    }
+
+    /// Loads a calendar from TOML.
+    ///
+    /// The format is the same as for [`from_json`](Self::from_json), with the
+    /// rules in a `[[holidays]]` array of tables. Single dates must be quoted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::holiday_calendar::HolidayCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = HolidayCalendar::from_toml(r#"
+    ///     uf = "SP"
+    ///
+    ///     [[holidays]]
+    ///     name = "Consciência Negra"
+    ///     type = "fixed"
+    ///     month = 11
+    ///     day = 20
+    ///     until = 2023
+    ///     legal_basis = "Lei estadual nº 17.746/2023"
+    /// "#).unwrap();
+    ///
+    /// assert!(calendar.is_holiday(NaiveDate::from_ymd_opt(2023, 11, 20).unwrap()));
+    /// ```
+    pub fn from_toml(toml: &str) -> Result<Self, HolidayCalendarError> {
+        let file: CalendarFile = toml::from_str(toml)
+            .map_err(|error| HolidayCalendarError::Parse(error.message().to_string()))?;
+        Self::from_file(file)
+    }
+
+    /// Validates a calendar file and builds the calendar.
+    fn from_file(file: CalendarFile) -> Result<Self, HolidayCalendarError> {
+        let mut calendar = match (&file.ibge, &file.uf) {
+            (Some(ibge), uf) => {
+                let calendar = Self::for_municipality(ibge)
+                    .ok_or_else(|| HolidayCalendarError::UnknownMunicipality(ibge.clone()))?;
+                if uf.as_ref().is_some_and(|uf| calendar.uf() != Some(uf)) {
+                    return Err(HolidayCalendarError::InvalidUf(
+                        uf.clone().unwrap_or_default(),
+                    ));
+                }
+                calendar
+            }
+            (None, uf) => Self::new(uf.as_deref())
+                .ok_or_else(|| HolidayCalendarError::InvalidUf(uf.clone().unwrap_or_default()))?,
+        };
+        calendar.builtin = file.builtin;
+
+        for rule in file.holidays {
+            let bounded = match (rule.since, rule.until) {
+                (Some(since), Some(until)) => since <= until,
+                _ => true,
+            };
+            if !bounded || !rule.date.is_valid() {
+                return Err(HolidayCalendarError::InvalidRule(rule.name));
+            }
+            calendar.rules.push(rule);
+        }
+
+        Ok(calendar)
+    }
+
+    /// The state (UF) whose built-in holidays are included, if any.
+    pub fn uf(&self) -> Option<&str> {
+        self.uf.as_deref()
+    }
+
+    /// The IBGE code of the municipality whose built-in holidays are included, if any.
+    pub fn ibge(&self) -> Option<&str> {
+        self.ibge.as_deref()
+    }
+
+    /// The user-defined rules, in the order they are applied.
+    pub fn rules(&self) -> &[HolidayRule] {
+        &self.rules
+    }
+
+    /// Checks whether a date is a holiday in the calendar.
+    ///
+    /// Pontos facultativos are not holidays.
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.holidays_on(date)
+            .iter()
+            .any(|holiday| holiday.kind == HolidayKind::Holiday)
+    }
+
+    /// Looks up the holiday that falls on the given date.
+    ///
+    /// Holidays take precedence over pontos facultativos, and built-in
+    /// holidays over user-defined ones.
+    pub fn get_holiday(&self, date: NaiveDate) -> Option<Holiday> {
+        let mut holidays = self.holidays_on(date);
+        let index = holidays
+            .iter()
+            .position(|holiday| holiday.kind == HolidayKind::Holiday)
+            .unwrap_or(0);
+
+        (!holidays.is_empty()).then(|| holidays.swap_remove(index))
+    }
+
+    /// Collects all holidays and pontos facultativos on a date.
+    pub fn holidays_on(&self, date: NaiveDate) -> Vec<Holiday> {
+        let mut holidays = Vec::new();
+
+        if self.builtin {
+            holidays = match self.ibge.as_deref() {
+                Some(ibge) => municipal_holidays_on(date, ibge).unwrap_or_default(),
+                None => holidays_on(date, self.uf.as_deref()),
+            };
+            holidays.extend(get_optional_day(date));
+        }
+
+        for rule in self.rules.iter().filter(|rule| rule.matches(date)) {
+            if rule.remove {
+                holidays.clear();
+            } else {
+                holidays.push(rule.holiday(date));
+            }
+        }
+
+        holidays
+    }
+
+    /// Lists all holidays and pontos facultativos of a year, sorted by date.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::holiday_calendar::{HolidayCalendar, HolidayRule};
+    /// use brazilian_utils::date_utils::HolidayKind;
+    ///
+    /// let calendar = HolidayCalendar::new(None).unwrap();
+    /// let holidays = calendar.list_holidays(2024);
+    ///
+    /// assert_eq!(holidays.iter().filter(|h| h.kind == HolidayKind::Holiday).count(), 10);
+    /// assert_eq!(holidays.len(), 14);
+    /// ```
+    pub fn list_holidays(&self, year: i32) -> Vec<Holiday> {
+        (1..=366)
+            .map_while(|ordinal| NaiveDate::from_yo_opt(year, ordinal))
+            .flat_map(|date| self.holidays_on(date))
+            .collect()
+    }
+
+    /// Exports the holidays of a range of years as an iCalendar file (RFC 5545).
+    ///
+    /// Each holiday or ponto facultativo becomes an all-day `VEVENT` with its
+    /// Portuguese name as summary, its scope and legal basis as description,
+    /// and its kind as category (`Feriado`, `Ponto facultativo` or
+    /// `Meio expediente`). UIDs are derived from the date, scope and name, so
+    /// re-exporting a calendar updates the events already imported by
+    /// calendar applications instead of duplicating them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::holiday_calendar::HolidayCalendar;
+    ///
+    /// let ics = HolidayCalendar::new(Some("SP")).unwrap().to_ics(2024..=2025);
+    ///
+    /// assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+    /// assert!(ics.contains("SUMMARY:Revolução Constitucionalista de 1932\r\n"));
+    /// assert!(ics.contains("DTSTART;VALUE=DATE:20240709\r\n"));
+    /// assert!(ics.contains("UID:20240709-sp-revolucao-constitucionalista-de-1932@brazilian-utils\r\n"));
+    /// ```
+    pub fn to_ics(&self, years: RangeInclusive<i32>) -> String {
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_string(),
+            "VERSION:2.0".to_string(),
+            "PRODID:-//brazilian-utils//Feriados//PT-BR".to_string(),
+            "CALSCALE:GREGORIAN".to_string(),
+            "METHOD:PUBLISH".to_string(),
+            format!("X-WR-CALNAME:{}", escape_text(&self.ics_name())),
+        ];
+
+        for holiday in years.flat_map(|year| self.list_holidays(year)) {
+            let start = holiday.date.format("%Y%m%d");
+            let end = holiday.date.succ_opt().unwrap_or(holiday.date);
+
+            lines.extend([
+                "BEGIN:VEVENT".to_string(),
+                format!("UID:{}", holiday_uid(&holiday)),
+                format!("DTSTAMP:{}T000000Z", start),
+                format!("DTSTART;VALUE=DATE:{}", start),
+                format!("DTEND;VALUE=DATE:{}", end.format("%Y%m%d")),
+                format!("SUMMARY:{}", escape_text(&holiday.name)),
+                format!(
+                    "DESCRIPTION:{}",
+                    escape_text(&holiday_description(&holiday))
+                ),
+                format!("CATEGORIES:{}", kind_category(holiday.kind)),
+                "TRANSP:TRANSPARENT".to_string(),
+                "END:VEVENT".to_string(),
+            ]);
+        }
+        lines.push("END:VCALENDAR".to_string());
+
+        lines.iter().map(|line| fold_line(line)).collect()
+    }
+
+    /// Imports an iCalendar file (RFC 5545) as a calendar without built-in
+    /// holidays.
+    ///
+    /// Every day covered by a `VEVENT` becomes a rule named after its
+    /// `SUMMARY`. Yearly recurring events (`RRULE:FREQ=YEARLY`, with an
+    /// optional `COUNT` or `UNTIL`) become fixed-date rules bounded by their
+    /// years, other events single-date rules. The kind is read from the
+    /// categories written by [`to_ics`](Self::to_ics), and cancelled events are
+    /// skipped. Events longer than 366 days are rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::holiday_calendar::HolidayCalendar;
+    /// use chrono::NaiveDate;
+    ///
+    /// let calendar = HolidayCalendar::from_ics(
+    ///     "BEGIN:VCALENDAR\r\n\
+    ///      VERSION:2.0\r\n\
+    ///      BEGIN:VEVENT\r\n\
+    ///      DTSTART;VALUE=DATE:20240315\r\n\
+    ///      SUMMARY:Aniversário da empresa\r\n\
+    ///      RRULE:FREQ=YEARLY\r\n\
+    ///      END:VEVENT\r\n\
+    ///      END:VCALENDAR\r\n",
+    /// )
+    /// .unwrap();
+    ///
+    /// assert!(calendar.is_holiday(NaiveDate::from_ymd_opt(2030, 3, 15).unwrap()));
+    /// assert!(!calendar.is_holiday(NaiveDate::from_ymd_opt(2023, 3, 15).unwrap()));
+    /// ```
+    pub fn from_ics(ics: &str) -> Result<Self, HolidayCalendarError> {
+        let lines = unfold_lines(ics);
+        if lines.first().map(|line| line.trim_end()) != Some("BEGIN:VCALENDAR") {
+            return Err(invalid_ics("missing BEGIN:VCALENDAR"));
+        }
+
+        let mut calendar = Self::empty();
+        let mut event: Option<Vec<(String, String)>> = None;
+
+        for line in lines.iter().filter(|line| !line.trim().is_empty()) {
+            let (property, value) =
+                split_property(line).ok_or_else(|| invalid_ics("line without a value"))?;
+
+            match (property.to_ascii_uppercase().as_str(), value) {
+                ("BEGIN", "VEVENT") => event = Some(Vec::new()),
+                ("END", "VEVENT") => {
+                    let properties = event
+                        .take()
+                        .ok_or_else(|| invalid_ics("END:VEVENT without BEGIN:VEVENT"))?;
+                    calendar.rules.extend(event_rules(&properties)?);
+                }
+                _ => {
+                    if let Some(properties) = event.as_mut() {
+                        properties.push((property.to_string(), value.to_string()));
+                    }
+                }
+            }
+        }
+
+        if event.is_some() {
+            return Err(invalid_ics("VEVENT without END:VEVENT"));
+        }
+
+        Ok(calendar)
+    }
+
+    /// Name of the calendar in iCalendar exports.
+    fn ics_name(&self) -> String {
+        match (&self.uf, &self.ibge) {
+            (Some(uf), Some(ibge)) => format!("Feriados - {} (IBGE {})", uf, ibge),
+            (Some(uf), None) => format!("Feriados - {}", uf),
+            (None, _) if self.builtin => "Feriados - Brasil".to_string(),
+            (None, _) => "Feriados".to_string(),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================
+/// Domain of the UIDs of exported iCalendar events.
+const ICS_UID_DOMAIN: &str = "brazilian-utils";
+
+/// Longest event accepted from an iCalendar file, in days. An event becomes
+/// one rule per day, so a longer one is rejected rather than expanded.
+const MAX_ICS_EVENT_DAYS: i64 = 366;
+
+/// iCalendar category of each holiday kind.
+fn kind_category(kind: HolidayKind) -> &'static str {
+    match kind {
+        HolidayKind::Holiday => "Feriado",
+        HolidayKind::Optional => "Ponto facultativo",
+        HolidayKind::HalfDay => "Meio expediente",
+    }
+}
+
+/// Builds a UID that is the same in every export of the holiday.
+fn holiday_uid(holiday: &Holiday) -> String {
+    use unicode_normalization::UnicodeNormalization;
+
+    let scope = match &holiday.scope {
+        HolidayScope::National => "br".to_string(),
+        HolidayScope::State(uf) => uf.to_lowercase(),
+        HolidayScope::Municipal(ibge) => ibge.clone(),
+        HolidayScope::Custom => "custom".to_string(),
+    };
+    let slug: String = holiday
+        .name
+        .nfd()
+        .filter(|c| !unicode_normalization::char::is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-");
+
+    format!(
+        "{}-{}-{}@{}",
+        holiday.date.format("%Y%m%d"),
+        scope,
+        slug,
+        ICS_UID_DOMAIN
+    )
+}
+
+/// Describes the scope and legal basis of a holiday, one per line.
+fn holiday_description(holiday: &Holiday) -> String {
+    let scope = match &holiday.scope {
+        HolidayScope::National => "nacional".to_string(),
+        HolidayScope::State(uf) => format!("estadual ({})", uf),
+        HolidayScope::Municipal(ibge) => format!("municipal (IBGE {})", ibge),
+        HolidayScope::Custom => "personalizada".to_string(),
+    };
+
+    if holiday.legal_basis.is_empty() {
+        format!("Abrangência: {}", scope)
+    } else {
+        format!(
+            "Abrangência: {}\nBase legal: {}",
+            scope, holiday.legal_basis
+        )
+    }
+}
+
+/// Escapes a TEXT value (RFC 5545, section 3.3.11).
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Reverts [`escape_text`].
+fn unescape_text(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, c == '\\') {
+            (_, true) => match chars.next() {
+                Some('n' | 'N') => unescaped.push('\n'),
+                Some(escaped) => unescaped.push(escaped),
+                None => unescaped.push('\\'),
+            },
+            (c, false) => unescaped.push(c),
+        }
+    }
+
+    unescaped
+}
+
+/// Folds a content line at 75 octets without splitting characters, and ends
+/// it with CRLF (RFC 5545, section 3.1).
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut width = 0;
+
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+
+    folded.push_str("\r\n");
+    folded
+}
+
+/// Joins folded content lines.
+fn unfold_lines(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+
+    for line in ics.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    lines
+}
+
+/// Splits a content line into its name (with parameters) and its value, at
+/// the first colon outside a quoted parameter value.
+fn split_property(line: &str) -> Option<(&str, &str)> {
+    let mut quoted = false;
+
+    for (index, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ':' if !quoted => return Some((&line[..index], &line[index + 1..])),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Builds the error for an invalid iCalendar file.
+fn invalid_ics(message: &str) -> HolidayCalendarError {
+    HolidayCalendarError::Parse(format!("invalid iCalendar: {}", message))
+}
+
+/// Reads the date of a DATE or DATE-TIME value.
+fn parse_ics_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value.get(..8)?, "%Y%m%d").ok()
+}
+
+/// Converts the properties of a VEVENT into rules, one per day.
+fn event_rules(properties: &[(String, String)]) -> Result<Vec<HolidayRule>, HolidayCalendarError> {
+    let property = |name: &str| {
+        properties
+            .iter()
+            .find(|(property, _)| {
+                let property_name = property.split(';').next().unwrap_or_default();
+                property_name.eq_ignore_ascii_case(name)
+            })
+            .map(|(_, value)| value.as_str())
+    };
+
+    if property("STATUS").is_some_and(|status| status.eq_ignore_ascii_case("CANCELLED")) {
+        return Ok(Vec::new());
+    }
+
+    let start = property("DTSTART")
+        .and_then(parse_ics_date)
+        .ok_or_else(|| invalid_ics("VEVENT without a valid DTSTART"))?;
+    let days = property("DTEND")
+        .and_then(parse_ics_date)
+        .map_or(1, |end| (end - start).num_days().max(1));
+    if days > MAX_ICS_EVENT_DAYS {
+        return Err(invalid_ics("event too long"));
+    }
+    let name = unescape_text(property("SUMMARY").unwrap_or_default());
+
+    let categories = unescape_text(property("CATEGORIES").unwrap_or_default()).to_lowercase();
+    let kind = if categories.contains("meio expediente") {
+        HolidayKind::HalfDay
+    } else if categories.contains("ponto facultativo") {
+        HolidayKind::Optional
+    } else {
+        HolidayKind::Holiday
+    };
+
+    let legal_basis = unescape_text(property("DESCRIPTION").unwrap_or_default())
+        .lines()
+        .find_map(|line| line.strip_prefix("Base legal: ").map(str::to_string));
+
+    // Yearly recurrence, as the last year of the event or None if endless
+    let recurrence = match property("RRULE") {
+        Some(rrule) => Some(parse_yearly_rrule(rrule, start.year())?),
+        None => None,
+    };
+
+    let rules = (0..days)
+        .filter_map(|offset| start.checked_add_signed(chrono::Duration::days(offset)))
+        .map(|date| {
+            let rule = match recurrence {
+                Some(until) => {
+                    let rule =
+                        HolidayRule::fixed(&name, date.month(), date.day()).since(date.year());
+                    match until {
+                        Some(year) => rule.until(year),
+                        None => rule,
+                    }
+                }
+                None => HolidayRule::on(&name, date),
+            };
+            let rule = rule.kind(kind);
+
+            match &legal_basis {
+                Some(legal_basis) => rule.legal_basis(legal_basis),
+                None => rule,
+            }
+        })
+        .collect();
+
+    Ok(rules)
+}
+
+/// Parses a yearly RRULE, returning the last year of the recurrence, if any.
+fn parse_yearly_rrule(rrule: &str, first_year: i32) -> Result<Option<i32>, HolidayCalendarError> {
+    let mut yearly = false;
+    let mut until = None;
+
+    for part in rrule.split(';') {
+        let (key, value) = part
+            .split_once('=')
+            .ok_or_else(|| invalid_ics("malformed RRULE"))?;
+
+        match key.to_ascii_uppercase().as_str() {
+            "FREQ" => yearly = value.eq_ignore_ascii_case("YEARLY"),
+            "INTERVAL" if value == "1" => {}
+            "WKST" => {}
+            "COUNT" => {
+                let count: i32 = value
+                    .parse()
+                    .ok()
+                    .filter(|count| *count > 0)
+                    .ok_or_else(|| invalid_ics("invalid RRULE COUNT"))?;
+                until = Some(first_year + count - 1);
+            }
+            "UNTIL" => {
+                let date =
+                    parse_ics_date(value).ok_or_else(|| invalid_ics("invalid RRULE UNTIL"))?;
+                until = Some(date.year());
+            }
+            _ => return Err(invalid_ics("unsupported RRULE")),
+        }
+    }
+
+    if !yearly {
+        return Err(invalid_ics("unsupported RRULE"));
+    }
+
+    Ok(until)
+}
+
+/// Finds the `nth` weekday of a month, counting from the end if `nth` is negative.
+fn nth_weekday(year: i32, month: u32, weekday: Weekday, nth: i8) -> Option<NaiveDate> {
+    if nth > 0 {
+        return NaiveDate::from_weekday_of_month_opt(year, month, weekday, nth as u8);
+    }
+
+    let next_month = match month {
+        12 => NaiveDate::from_ymd_opt(year + 1, 1, 1)?,
+        _ => NaiveDate::from_ymd_opt(year, month + 1, 1)?,
+    };
+    let last_day = next_month.pred_opt()?;
+    let back = (last_day.weekday().num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7;
+    let date = last_day - chrono::Duration::days(back as i64 + 7 * (-(nth as i64) - 1));
+
+    (date.month() == month).then_some(date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_holiday_date_in_year() {
+        let fixed = HolidayDate::Fixed { month: 2, day: 29 };
+        assert_eq!(fixed.in_year(2024), Some(date(2024, 2, 29)));
+        assert_eq!(fixed.in_year(2023), None);
+
+        let carnival = HolidayDate::EasterOffset { days: -47 };
+        assert_eq!(carnival.in_year(2024), Some(date(2024, 2, 13)));
+
+        let mothers_day = HolidayDate::NthWeekday {
+            month: 5,
+            weekday: Weekday::Sun,
+            nth: 2,
+        };
+        assert_eq!(mothers_day.in_year(2024), Some(date(2024, 5, 12)));
+
+        let fifth_monday = HolidayDate::NthWeekday {
+            month: 2,
+            weekday: Weekday::Mon,
+            nth: 5,
+        };
+        assert_eq!(fifth_monday.in_year(2024), None);
+
+        let last_sunday = HolidayDate::NthWeekday {
+            month: 12,
+            weekday: Weekday::Sun,
+            nth: -1,
+        };
+        assert_eq!(last_sunday.in_year(2024), Some(date(2024, 12, 29)));
+        let second_to_last = HolidayDate::NthWeekday {
+            month: 12,
+            weekday: Weekday::Tue,
+            nth: -2,
+        };
+        assert_eq!(second_to_last.in_year(2024), Some(date(2024, 12, 24)));
+
+        let single = HolidayDate::Date {
+            date: date(2024, 5, 10),
+        };
+        assert_eq!(single.in_year(2024), Some(date(2024, 5, 10)));
+        assert_eq!(single.in_year(2025), None);
+    }
+
+    #[test]
+    fn test_builtin_holidays() {
+        let calendar = HolidayCalendar::new(Some("BA")).unwrap();
+
+        assert!(calendar.is_holiday(date(2024, 7, 2)));
+        assert!(!calendar.is_holiday(date(2024, 7, 9)));
+        // Carnival is a ponto facultativo, not a holiday
+        assert!(!calendar.is_holiday(date(2024, 2, 13)));
+        assert_eq!(
+            calendar.get_holiday(date(2024, 2, 13)).unwrap().kind,
+            HolidayKind::Optional
+        );
+
+        let municipal = HolidayCalendar::for_municipality("3550308").unwrap();
+        assert_eq!(municipal.uf(), Some("SP"));
+        assert!(municipal.is_holiday(date(2024, 1, 25)));
+        // Corpus Christi is a municipal holiday in São Paulo
+        assert!(municipal.is_holiday(date(2024, 5, 30)));
+        assert_eq!(
+            municipal.get_holiday(date(2024, 5, 30)).unwrap().kind,
+            HolidayKind::Holiday
+        );
+
+        assert!(HolidayCalendar::new(Some("XX")).is_none());
+        assert!(HolidayCalendar::for_municipality("0000000").is_none());
+    }
+
+    #[test]
+    fn test_builtin_matches_date_utils() {
+        for uf in [None, Some("SP"), Some("RJ"), Some("AC")] {
+            let calendar = HolidayCalendar::new(uf).unwrap();
+            for day in date(2024, 1, 1).iter_days().take(366) {
+                assert_eq!(
+                    Some(calendar.is_holiday(day)),
+                    crate::date_utils::is_holiday(day, uf)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_rules_with_year_ranges() {
+        let calendar = HolidayCalendar::empty()
+            .with_rule(HolidayRule::fixed("Feriado antigo", 4, 10).until(2019))
+            .with_rule(HolidayRule::fixed("Feriado novo", 4, 12).since(2020))
+            .with_rule(
+                HolidayRule::easter_offset("Ponto facultativo", 1).kind(HolidayKind::Optional),
+            );
+
+        assert!(calendar.is_holiday(date(2019, 4, 10)));
+        assert!(!calendar.is_holiday(date(2020, 4, 10)));
+        assert!(!calendar.is_holiday(date(2019, 4, 12)));
+        assert!(calendar.is_holiday(date(2020, 4, 12)));
+        assert!(!calendar.is_holiday(date(2024, 4, 1)));
+
+        let holiday = calendar.get_holiday(date(2020, 4, 12)).unwrap();
+        assert_eq!(holiday.name, "Feriado novo");
+        assert_eq!(holiday.scope, HolidayScope::Custom);
+
+        let names: Vec<String> = calendar
+            .list_holidays(2024)
+            .into_iter()
+            .map(|holiday| holiday.name)
+            .collect();
+        assert_eq!(names, vec!["Ponto facultativo", "Feriado novo"]);
+    }
+
+    #[test]
+    fn test_rules_override_builtin() {
+        // A law moving a holiday: removed on the old date, added on the new one
+        let calendar = HolidayCalendar::new(Some("SP"))
+            .unwrap()
+            .with_rule(HolidayRule::fixed("", 7, 9).since(2025).removing())
+            .with_rule(
+                HolidayRule::fixed("Revolução Constitucionalista de 1932", 7, 10)
+                    .since(2025)
+                    .legal_basis("Lei estadual fictícia"),
+            );
+
+        assert!(calendar.is_holiday(date(2024, 7, 9)));
+        assert!(!calendar.is_holiday(date(2024, 7, 10)));
+        assert!(!calendar.is_holiday(date(2025, 7, 9)));
+        assert!(calendar.is_holiday(date(2025, 7, 10)));
+        assert_eq!(
+            calendar.get_holiday(date(2025, 7, 10)).unwrap().legal_basis,
+            "Lei estadual fictícia"
+        );
+
+        // A holiday added on the same date as a built-in one comes after it
+        let calendar = HolidayCalendar::new(None)
+            .unwrap()
+            .with_rule(HolidayRule::fixed("Dia da empresa", 1, 1));
+        assert_eq!(calendar.holidays_on(date(2024, 1, 1)).len(), 2);
+        assert_eq!(
+            calendar.get_holiday(date(2024, 1, 1)).unwrap().name,
+            "Confraternização Universal"
+        );
+    }
+
+    #[test]
+    fn test_from_json() {
+        let calendar = HolidayCalendar::from_json(
+            r#"{
+                "ibge": "3304557",
+                "holidays": [
+                    {"name": "Dia das Mães", "type": "nth_weekday", "month": 5, "weekday": "Sun", "nth": 2},
+                    {"name": "São Sebastião", "type": "fixed", "month": 1, "day": 20, "since": 2030, "remove": true}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(calendar.uf(), Some("RJ"));
+        assert_eq!(calendar.ibge(), Some("3304557"));
+        assert_eq!(calendar.rules().len(), 2);
+        assert!(calendar.is_holiday(date(2024, 5, 12)));
+        assert!(calendar.is_holiday(date(2024, 1, 20)));
+        assert!(!calendar.is_holiday(date(2030, 1, 20)));
+
+        let calendar = HolidayCalendar::from_json(r#"{"builtin": false}"#).unwrap();
+        assert!(calendar.list_holidays(2024).is_empty());
+    }
+
+    #[test]
+    fn test_from_toml() {
+        let calendar = HolidayCalendar::from_toml(
+            r#"
+            uf = "MG"
+
+            [[holidays]]
+            name = "Aniversário da cidade"
+            type = "fixed"
+            month = 12
+            day = 12
+            since = 1897
+
+            [[holidays]]
+            name = "Quinta-feira Santa"
+            type = "easter_offset"
+            days = -3
+            kind = "half_day"
+
+            [[holidays]]
+            name = "Enchente"
+            type = "date"
+            date = "2024-05-10"
+            "#,
+        )
+        .unwrap();
+
+        assert!(calendar.is_holiday(date(2024, 12, 12)));
+        assert!(calendar.is_holiday(date(2024, 4, 21)));
+        assert!(calendar.is_holiday(date(2024, 5, 10)));
+        assert!(!calendar.is_holiday(date(2024, 3, 28)));
+        assert_eq!(
+            calendar.get_holiday(date(2024, 3, 28)).unwrap().kind,
+            HolidayKind::HalfDay
+        );
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let rule = HolidayRule::nth_weekday("Dia dos Pais", 8, Weekday::Sun, 2)
+            .since(1953)
+            .kind(HolidayKind::Optional);
+        let json = serde_json::to_string(&rule).unwrap();
+
+        assert_eq!(serde_json::from_str::<HolidayRule>(&json).unwrap(), rule);
+    }
+
+    #[test]
+    fn test_load_errors() {
+        assert!(matches!(
+            HolidayCalendar::from_json("{"),
+            Err(HolidayCalendarError::Parse(_))
+        ));
+        assert!(matches!(
+            HolidayCalendar::from_toml("holidays = 1"),
+            Err(HolidayCalendarError::Parse(_))
+        ));
+        assert_eq!(
+            HolidayCalendar::from_json(r#"{"uf": "XX"}"#),
+            Err(HolidayCalendarError::InvalidUf("XX".to_string()))
+        );
+        assert_eq!(
+            HolidayCalendar::from_json(r#"{"ibge": "3304557", "uf": "SP"}"#),
+            Err(HolidayCalendarError::InvalidUf("SP".to_string()))
+        );
+        assert_eq!(
+            HolidayCalendar::from_json(r#"{"ibge": "0000000"}"#),
+            Err(HolidayCalendarError::UnknownMunicipality(
+                "0000000".to_string()
+            ))
+        );
+        assert_eq!(
+            HolidayCalendar::from_json(
+                r#"{"holidays": [{"name": "Inválido", "type": "fixed", "month": 13, "day": 1}]}"#
+            ),
+            Err(HolidayCalendarError::InvalidRule("Inválido".to_string()))
+        );
+        assert_eq!(
+            HolidayCalendar::from_json(
+                r#"{"holidays": [{"name": "Vazio", "type": "fixed", "month": 1, "day": 2, "since": 2020, "until": 2010}]}"#
+            ),
+            Err(HolidayCalendarError::InvalidRule("Vazio".to_string()))
+        );
+        assert_eq!(
+            HolidayCalendarError::InvalidUf("XX".to_string()).to_string(),
+            "invalid UF: XX"
+        );
+    }
+
+    #[test]
+    fn test_to_ics() {
+        let ics = HolidayCalendar::for_municipality("3550308")
+            .unwrap()
+            .to_ics(2024..=2024);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+        assert!(ics.contains("X-WR-CALNAME:Feriados - SP (IBGE 3550308)\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 17);
+        assert!(ics.lines().all(|line| line.len() <= 75));
+        assert!(ics.split("\r\n").all(|line| !line.contains('\n')));
+
+        // All-day event with an exclusive end date
+        assert!(ics.contains(
+            "UID:20240125-3550308-aniversario-de-sao-paulo@brazilian-utils\r\n\
+             DTSTAMP:20240125T000000Z\r\n\
+             DTSTART;VALUE=DATE:20240125\r\n\
+             DTEND;VALUE=DATE:20240126\r\n\
+             SUMMARY:Aniversário de São Paulo\r\n"
+        ));
+        assert!(
+            ics.contains("DESCRIPTION:Abrangência: nacional\\nBase legal: Lei nº 14.759/2023\r\n")
+        );
+        assert!(ics.contains(
+            "SUMMARY:Carnaval\r\nDESCRIPTION:Abrangência: nacional\\nBase legal: Portaria anual"
+        ));
+        assert!(ics.contains("CATEGORIES:Meio expediente\r\n"));
+
+        // Exports are stable
+        assert_eq!(
+            ics,
+            HolidayCalendar::for_municipality("3550308")
+                .unwrap()
+                .to_ics(2024..=2024)
+        );
+        assert!(HolidayCalendar::empty()
+            .to_ics(2024..=2024)
+            .contains("X-WR-CALNAME:Feriados\r\n"));
+    }
+
+    #[test]
+    fn test_ics_text_escaping_and_folding() {
+        let name =
+            "Feriado; com vírgula, barra \\ e um nome bem longo para dobrar a linha do arquivo";
+        let calendar = HolidayCalendar::empty().with_rule(HolidayRule::fixed(name, 3, 15));
+        let ics = calendar.to_ics(2024..=2024);
+
+        assert!(ics.contains("SUMMARY:Feriado\\; com vírgula\\, barra \\\\ e"));
+        assert!(ics.contains("\r\n "));
+        assert!(ics.split("\r\n").all(|line| line.len() <= 75));
+
+        let imported = HolidayCalendar::from_ics(&ics).unwrap();
+        assert_eq!(imported.rules()[0].name, name);
+    }
+
+    #[test]
+    fn test_ics_round_trip() {
+        let calendar = HolidayCalendar::new(Some("RJ")).unwrap();
+        let imported = HolidayCalendar::from_ics(&calendar.to_ics(2023..=2025)).unwrap();
+
+        assert_eq!(imported.uf(), None);
+        for year in 2023..=2025 {
+            let original = calendar.list_holidays(year);
+            let restored = imported.list_holidays(year);
+
+            assert_eq!(original.len(), restored.len());
+            for (original, restored) in original.iter().zip(&restored) {
+                assert_eq!(original.date, restored.date);
+                assert_eq!(original.name, restored.name);
+                assert_eq!(original.kind, restored.kind);
+                assert_eq!(original.legal_basis, restored.legal_basis);
+                assert_eq!(restored.scope, HolidayScope::Custom);
+            }
+        }
+        assert!(imported.list_holidays(2026).is_empty());
+    }
+
+    #[test]
+    fn test_from_ics_recurrence() {
+        let calendar = HolidayCalendar::from_ics(
+            "BEGIN:VCALENDAR\n\
+             BEGIN:VEVENT\n\
+             DTSTART;VALUE=DATE:20200315\n\
+             RRULE:FREQ=YEARLY;COUNT=3\n\
+             SUMMARY:Três anos\n\
+             END:VEVENT\n\
+             BEGIN:VEVENT\n\
+             DTSTART:20210410T090000Z\n\
+             RRULE:FREQ=YEARLY;UNTIL=20231231T235959Z\n\
+             SUMMARY:Até 2023\n\
+             END:VEVENT\n\
+             BEGIN:VEVENT\n\
+             DTSTART;VALUE=DATE:20241224\n\
+             DTEND;VALUE=DATE:20250102\n\
+             SUMMARY:Recesso\n\
+             CATEGORIES:Ponto facultativo\n\
+             END:VEVENT\n\
+             BEGIN:VEVENT\n\
+             DTSTART;VALUE=DATE:20240601\n\
+             SUMMARY:Cancelado\n\
+             STATUS:CANCELLED\n\
+             END:VEVENT\n\
+             END:VCALENDAR\n",
+        )
+        .unwrap();
+
+        assert!(!calendar.is_holiday(date(2019, 3, 15)));
+        assert!(calendar.is_holiday(date(2022, 3, 15)));
+        assert!(!calendar.is_holiday(date(2023, 3, 15)));
+        assert!(calendar.is_holiday(date(2023, 4, 10)));
+        assert!(!calendar.is_holiday(date(2024, 4, 10)));
+        assert!(!calendar.is_holiday(date(2024, 6, 1)));
+
+        let recess: Vec<Holiday> = calendar
+            .list_holidays(2024)
+            .into_iter()
+            .chain(calendar.list_holidays(2025))
+            .filter(|holiday| holiday.name == "Recesso")
+            .collect();
+        assert_eq!(recess.len(), 9);
+        assert!(recess.iter().all(|h| h.kind == HolidayKind::Optional));
+        assert_eq!(recess.last().unwrap().date, date(2025, 1, 1));
+    }
+
+    #[test]
+    fn test_from_ics_errors() {
+        let event = |body: &str| {
+            format!("BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\n{body}END:VEVENT\r\nEND:VCALENDAR\r\n")
+        };
+
+        assert!(matches!(
+            HolidayCalendar::from_ics("BEGIN:VEVENT\r\n"),
+            Err(HolidayCalendarError::Parse(_))
+        ));
+        assert!(matches!(
+            HolidayCalendar::from_ics(&event("SUMMARY:Sem data\r\n")),
+            Err(HolidayCalendarError::Parse(_))
+        ));
+        assert!(matches!(
+            HolidayCalendar::from_ics(&event(
+                "DTSTART;VALUE=DATE:20240101\r\nRRULE:FREQ=MONTHLY\r\n"
+            )),
+            Err(HolidayCalendarError::Parse(_))
+        ));
+        assert_eq!(
+            HolidayCalendar::from_ics(&event(
+                "DTSTART;VALUE=DATE:00010101\r\nDTEND;VALUE=DATE:99991231\r\n"
+            ))
+            .unwrap_err(),
+            HolidayCalendarError::Parse("invalid iCalendar: event too long".to_string())
+        );
+        assert_eq!(
+            HolidayCalendar::from_ics(&event(
+                "DTSTART;VALUE=DATE:20240101\r\nDTEND;VALUE=DATE:20250101\r\n"
+            ))
+            .unwrap()
+            .rules()
+            .len(),
+            366
+        );
+        assert!(matches!(
+            HolidayCalendar::from_ics("BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nDTSTART:20240101\r\n"),
+            Err(HolidayCalendarError::Parse(_))
+        ));
+        assert_eq!(
+            HolidayCalendar::from_ics("BEGIN:VCALENDAR\r\nEND:VCALENDAR\r\n")
+                .unwrap()
+                .rules()
+                .len(),
+            0
+        );
+    }
+}
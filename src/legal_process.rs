@@ -186,6 +186,122 @@ pub fn is_valid(legal_process_id: &str) -> bool {
     dd == expected_dd
 }
 
+/// Components of a legal process number in the CNJ format NNNNNNN-DD.AAAA.J.TR.OOOO.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LegalProcess {
+    /// Sequential number of the process in the origin unit (NNNNNNN).
+    pub sequence: u32,
+    /// Check digits (DD).
+    pub check_digits: u8,
+    /// Year the process was filed (AAAA).
+    pub year: i32,
+    /// Segment of the Judiciary (J): 1 STF, 2 CNJ, 3 STJ, 4 Federal,
+    /// 5 Labor, 6 Electoral, 7 Military (Union), 8 State, 9 Military (State).
+    pub segment: u8,
+    /// Court within the segment (TR), e.g. the region or the state.
+    pub tribunal: u8,
+    /// Origin unit (OOOO), the forum or court division.
+    pub origin: u16,
+}
+
+impl LegalProcess {
+    /// Returns the state (UF) of the court's seat.
+    ///
+    /// State, electoral and state military courts are identified by their
+    /// state; federal, labor and military courts of the Union by the seat of
+    /// their region; the superior courts by the Federal District.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::legal_process::parse;
+    ///
+    /// // TJSP (J = 8, TR = 26)
+    /// assert_eq!(parse("1234567-21.2024.8.26.0000").unwrap().uf(), Some("SP"));
+    ///
+    /// // TRF da 1ª Região (J = 4, TR = 01), seated in Brasília
+    /// assert_eq!(parse("1018874-82.2023.4.01.8200").unwrap().uf(), Some("DF"));
+    /// ```
+    pub fn uf(&self) -> Option<&'static str> {
+        // States in the order of the TR codes of the state and electoral courts
+        const STATES: [&str; 27] = [
+            "AC", "AL", "AP", "AM", "BA", "CE", "DF", "ES", "GO", "MA", "MT", "MS", "MG", "PA",
+            "PB", "PR", "PE", "PI", "RJ", "RN", "RS", "RO", "RR", "SC", "SE", "SP", "TO",
+        ];
+        // Seats of the Federal Regional Courts (TRF1-TRF6)
+        const FEDERAL: [&str; 6] = ["DF", "RJ", "SP", "RS", "PE", "MG"];
+        // Seats of the Regional Labor Courts (TRT1-TRT24)
+        const LABOR: [&str; 24] = [
+            "RJ", "SP", "MG", "RS", "BA", "PE", "CE", "PA", "PR", "DF", "AM", "SC", "PB", "RO",
+            "SP", "MA", "ES", "GO", "AL", "SE", "RN", "PI", "MT", "MS",
+        ];
+        // Seats of the Military Judicial Circumscriptions (1ª-12ª CJM)
+        const MILITARY: [&str; 12] = [
+            "RJ", "SP", "RS", "MG", "PR", "BA", "PE", "PA", "MS", "CE", "DF", "AM",
+        ];
+
+        let index = (self.tribunal as usize).checked_sub(1);
+
+        match self.segment {
+            1..=3 => Some("DF"),
+            4 => index.and_then(|i| FEDERAL.get(i)).copied(),
+            5 => index.and_then(|i| LABOR.get(i)).copied(),
+            6 | 8 => index.and_then(|i| STATES.get(i)).copied(),
+            7 if self.tribunal == 0 => Some("DF"),
+            7 => index.and_then(|i| MILITARY.get(i)).copied(),
+            9 => match self.tribunal {
+                13 => Some("MG"),
+                21 => Some("RS"),
+                26 => Some("SP"),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Parses a legal process ID into its components.
+///
+/// # Arguments
+///
+/// * `legal_process_id` - A string representing the legal process ID (with or without symbols).
+///
+/// # Returns
+///
+/// The components of the legal process ID, or `None` if it is not valid
+/// (see [`is_valid`]).
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::legal_process::parse;
+///
+/// let process = parse("1018874-82.2023.4.01.8200").unwrap();
+/// assert_eq!(process.sequence, 1018874);
+/// assert_eq!(process.year, 2023);
+/// assert_eq!(process.segment, 4);
+/// assert_eq!(process.tribunal, 1);
+/// assert_eq!(process.origin, 8200);
+///
+/// assert_eq!(parse("00000000000000000000"), None);
+/// ```
+pub fn parse(legal_process_id: &str) -> Option<LegalProcess> {
+    if !is_valid(legal_process_id) {
+        return None;
+    }
+
+    let clean = remove_symbols(legal_process_id);
+
+    Some(LegalProcess {
+        sequence: clean[0..7].parse().ok()?,
+        check_digits: clean[7..9].parse().ok()?,
+        year: clean[9..13].parse().ok()?,
+        segment: clean[13..14].parse().ok()?,
+        tribunal: clean[14..16].parse().ok()?,
+        origin: clean[16..20].parse().ok()?,
+    })
+}
+
 /// Generate a random legal process ID number.
 ///
 /// # Arguments
@@ -338,6 +454,61 @@ mod tests {
         assert!(is_valid("4553234-69.2023.4.02.5107"));
     }
 
+    #[test]
+    fn test_parse() {
+        let process = parse("12345672120248260000").unwrap();
+        assert_eq!(
+            process,
+            LegalProcess {
+                sequence: 1234567,
+                check_digits: 21,
+                year: 2024,
+                segment: 8,
+                tribunal: 26,
+                origin: 0,
+            }
+        );
+        assert_eq!(process.uf(), Some("SP"));
+
+        assert_eq!(parse("4553234-69.2023.4.02.5107").unwrap().uf(), Some("RJ"));
+        assert_eq!(parse("7654321-28.2023.5.02.0000").unwrap().uf(), Some("SP"));
+
+        assert_eq!(parse("10188748220239918200"), None);
+        assert_eq!(parse("123"), None);
+    }
+
+    #[test]
+    fn test_uf_by_segment() {
+        let court = |segment, tribunal| {
+            LegalProcess {
+                sequence: 0,
+                check_digits: 0,
+                year: 2024,
+                segment,
+                tribunal,
+                origin: 0,
+            }
+            .uf()
+        };
+
+        assert_eq!(court(1, 1), Some("DF"));
+        assert_eq!(court(3, 3), Some("DF"));
+        assert_eq!(court(4, 5), Some("PE"));
+        assert_eq!(court(4, 6), Some("MG"));
+        assert_eq!(court(4, 7), None);
+        assert_eq!(court(5, 15), Some("SP"));
+        assert_eq!(court(5, 24), Some("MS"));
+        assert_eq!(court(6, 5), Some("BA"));
+        assert_eq!(court(7, 0), Some("DF"));
+        assert_eq!(court(7, 11), Some("DF"));
+        assert_eq!(court(8, 1), Some("AC"));
+        assert_eq!(court(8, 27), Some("TO"));
+        assert_eq!(court(8, 0), None);
+        assert_eq!(court(8, 28), None);
+        assert_eq!(court(9, 13), Some("MG"));
+        assert_eq!(court(9, 1), None);
+    }
+
     #[test]
     fn test_generate() {
         let current_year = chrono::Local::now().year();
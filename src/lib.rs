@@ -1,17 +1,23 @@
 pub mod boleto;
+pub mod business_days;
 pub mod cep;
 pub mod cnh;
 pub mod cnpj;
+pub mod court_deadline;
 pub mod cpf;
 pub mod currency;
 pub mod date_utils;
 pub mod email;
+pub mod financing;
+pub mod holiday_calendar;
 pub mod legal_nature;
 pub mod legal_process;
 pub mod license_plate;
 pub mod phone;
 pub mod pis;
 pub mod renavam;
+pub mod tax_calendar;
+pub mod time_zone;
 pub mod voter_id;
 
 #[cfg(test)]
@@ -75,6 +81,22 @@ mod tests {
 
         let generated = cep::generate();
         assert!(cep::is_valid(&generated));
+
+        // Test that CEP providers are accessible
+        use cep::CepProvider;
+        let provider = cep::FallbackProvider::new()
+            .with(cep::MockProvider::unavailable())
+            .with(cep::MockProvider::new());
+        assert_eq!(
+            provider.get_address("01310-200"),
+            Err(cep::CepError::NotFound("01310200".to_string()))
+        );
+        let cached = cep::CachedProvider::new(provider);
+        assert!(cached.get_address("01310-200").is_err());
+        assert_eq!(cached.stats().misses, 1);
+
+        let results = cep::BatchLookup::new(&cached).run(["01310-200", "01310200"]);
+        assert_eq!(results.len(), 1);
     }
 
     #[test]
@@ -101,6 +123,31 @@ mod tests {
         assert_eq!(generated.len(), 14);
     }
 
+    #[test]
+    fn test_business_days_module_accessible() {
+        use chrono::NaiveDate;
+
+        // Test that Business Days module functions are accessible
+        let date = NaiveDate::from_ymd_opt(2024, 7, 8).unwrap();
+        assert_eq!(business_days::is_business_day(date, Some("SP")), Some(true));
+        assert_eq!(
+            business_days::add_business_days(date, 1, Some("SP")),
+            NaiveDate::from_ymd_opt(2024, 7, 10)
+        );
+    }
+
+    #[test]
+    fn test_court_deadline_module_accessible() {
+        use chrono::NaiveDate;
+
+        // Test that Court Deadline module functions are accessible
+        let start = NaiveDate::from_ymd_opt(2024, 7, 8).unwrap();
+        assert_eq!(
+            court_deadline::deadline(start, 1, "1234567-21.2024.8.26.0000"),
+            NaiveDate::from_ymd_opt(2024, 7, 10)
+        );
+    }
+
     #[test]
     fn test_currency_module_accessible() {
         // Test that Currency module functions are accessible
@@ -116,6 +163,20 @@ mod tests {
         assert_eq!(currency::format_currency(f64::NAN), None);
     }
 
+    #[test]
+    fn test_financing_module_accessible() {
+        // Test that Financing module functions are accessible
+        let loan = financing::Loan::new(
+            currency::Brl::from_reais(1_000).unwrap(),
+            0.01,
+            3,
+            financing::AmortizationSystem::Price,
+        );
+        let schedule = financing::schedule(&loan).unwrap();
+        assert_eq!(schedule.installments.len(), 3);
+        assert_eq!(schedule.total_principal(), loan.principal);
+    }
+
     #[test]
     fn test_date_utils_module_accessible() {
         use chrono::NaiveDate;
@@ -138,6 +199,46 @@ mod tests {
         assert_eq!(date_utils::is_holiday(new_year, Some("XX")), None);
     }
 
+    #[test]
+    fn test_holiday_calendar_module_accessible() {
+        use chrono::NaiveDate;
+
+        // Test that HolidayCalendar is accessible
+        let calendar = holiday_calendar::HolidayCalendar::new(Some("SP"))
+            .unwrap()
+            .with_rule(holiday_calendar::HolidayRule::fixed("Feriado", 3, 15));
+        assert!(calendar.is_holiday(NaiveDate::from_ymd_opt(2024, 3, 15).unwrap()));
+        assert!(calendar.is_holiday(NaiveDate::from_ymd_opt(2024, 7, 9).unwrap()));
+    }
+
+    #[test]
+    fn test_tax_calendar_module_accessible() {
+        use chrono::NaiveDate;
+
+        // Test that due dates of federal obligations are accessible
+        assert_eq!(
+            tax_calendar::due_date(tax_calendar::Obligation::Irrf, "08/2024"),
+            NaiveDate::from_ymd_opt(2024, 9, 20)
+        );
+        assert!(tax_calendar::Competence::parse("13/2024")
+            .unwrap()
+            .is_thirteenth_salary());
+    }
+
+    #[test]
+    fn test_time_zone_module_accessible() {
+        // Test that time zone lookups are accessible
+        assert_eq!(
+            time_zone::time_zone("AM"),
+            Some(time_zone::Tz::America__Manaus)
+        );
+        assert_eq!(
+            time_zone::municipality_time_zone("1301407"),
+            Some(time_zone::Tz::America__Eirunepe)
+        );
+        assert_eq!(time_zone::time_zone("XX"), None);
+    }
+
     #[test]
     fn test_email_module_accessible() {
         // Test valid emails
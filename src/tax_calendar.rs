@@ -0,0 +1,575 @@
+//! Tax competence periods (competência) and due dates of federal obligations.
+
+use crate::business_days::BusinessCalendar;
+use crate::date_utils::{parse_month_year, MonthYear};
+use chrono::{Datelike, Months, NaiveDate};
+use std::fmt;
+use std::sync::OnceLock;
+
+// TYPES
+// =====
+
+/// A competence (competência): the month whose facts an obligation refers to.
+///
+/// Besides the twelve months, payroll obligations have competence 13 for the
+/// 13th salary (décimo terceiro salário). Competences are ordered
+/// chronologically, with 13 between December and the next January, and
+/// displayed as `MM/AAAA`.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::tax_calendar::Competence;
+///
+/// let competence = Competence::parse("08/2024").unwrap();
+/// assert_eq!((competence.year(), competence.month()), (2024, 8));
+/// assert_eq!(competence.next().to_string(), "09/2024");
+///
+/// let thirteenth = Competence::parse("13/2024").unwrap();
+/// assert!(thirteenth.is_thirteenth_salary());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Competence {
+    year: i32,
+    month: u32,
+}
+
+impl Competence {
+    /// Creates a competence, or returns `None` if the month is not 1-13 or
+    /// the year is out of the range supported by `chrono`.
+    pub fn new(year: i32, month: u32) -> Option<Self> {
+        if month == 13 {
+            MonthYear::new(year, 12)?;
+        } else {
+            MonthYear::new(year, month)?;
+        }
+        Some(Competence { year, month })
+    }
+
+    /// The competence of the month of a date.
+    pub fn from_date(date: NaiveDate) -> Self {
+        Competence {
+            year: date.year(),
+            month: date.month(),
+        }
+    }
+
+    /// Reads a competence such as "08/2024", "082024", "ago/2024" or "13/2024".
+    ///
+    /// # Returns
+    ///
+    /// The competence, or `None` if the text is not a valid competence.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let digits: String = text.chars().filter(|c| *c != '/').collect();
+
+        if digits.len() == 6 && digits.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Self::new(digits[2..].parse().ok()?, digits[..2].parse().ok()?);
+        }
+
+        parse_month_year(text).map(Self::from)
+    }
+
+    /// The year.
+    pub fn year(&self) -> i32 {
+        self.year
+    }
+
+    /// The month (1-12), or 13 for the 13th salary.
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    /// Whether this is competence 13, the 13th salary.
+    pub fn is_thirteenth_salary(&self) -> bool {
+        self.month == 13
+    }
+
+    /// The calendar month of the competence. Competence 13 refers to December.
+    pub fn month_year(&self) -> MonthYear {
+        MonthYear::new(self.year, self.month.min(12)).expect("validated on creation")
+    }
+
+    /// The next monthly competence. Competence 13 is skipped: the competence
+    /// after 12/2024 and 13/2024 is 01/2025.
+    pub fn next(&self) -> Self {
+        if self.month >= 12 {
+            Competence {
+                year: self.year + 1,
+                month: 1,
+            }
+        } else {
+            Competence {
+                year: self.year,
+                month: self.month + 1,
+            }
+        }
+    }
+}
+
+impl From<MonthYear> for Competence {
+    fn from(month_year: MonthYear) -> Self {
+        Competence {
+            year: month_year.year(),
+            month: month_year.month(),
+        }
+    }
+}
+
+impl fmt::Display for Competence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02}/{:04}", self.month, self.year)
+    }
+}
+
+/// The day of the month an obligation falls due.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DueDay {
+    /// A fixed day of the month, moved by the rule's [`Adjustment`] if it is
+    /// not a business day. Days past the end of the month fall on its last day.
+    Day(u32),
+    /// The n-th business day of the month (1-based).
+    BusinessDay(u32),
+    /// The last business day of the month.
+    LastBusinessDay,
+}
+
+/// What happens to a due date that is not a business day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Adjustment {
+    /// Anticipated to the previous business day (antecipação).
+    #[default]
+    Previous,
+    /// Postponed to the next business day (prorrogação).
+    Next,
+    /// Kept on the same day.
+    Unadjusted,
+}
+
+/// A due-date rule, such as "day 20 of the following month, anticipated to
+/// the previous business day if it is not a business day".
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::tax_calendar::{Adjustment, Competence, DueDateRule, DueDay};
+/// use chrono::NaiveDate;
+///
+/// let rule = DueDateRule::new(DueDay::Day(20));
+/// let competence = Competence::new(2024, 9).unwrap();
+///
+/// // October 20, 2024 was a Sunday
+/// assert_eq!(rule.due_date(competence), NaiveDate::from_ymd_opt(2024, 10, 18));
+/// assert_eq!(
+///     rule.adjustment(Adjustment::Next).due_date(competence),
+///     NaiveDate::from_ymd_opt(2024, 10, 21)
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DueDateRule {
+    day: DueDay,
+    months_after: u32,
+    adjustment: Adjustment,
+}
+
+impl DueDateRule {
+    /// Creates a rule falling due on the given day of the month following
+    /// the competence, anticipated to the previous business day.
+    pub fn new(day: DueDay) -> Self {
+        DueDateRule {
+            day,
+            months_after: 1,
+            adjustment: Adjustment::Previous,
+        }
+    }
+
+    /// Sets how many months after the competence the obligation falls due
+    /// (0 for the month of the competence itself).
+    pub fn months_after(mut self, months: u32) -> Self {
+        self.months_after = months;
+        self
+    }
+
+    /// Sets what happens to a due date that is not a business day.
+    pub fn adjustment(mut self, adjustment: Adjustment) -> Self {
+        self.adjustment = adjustment;
+        self
+    }
+
+    /// Computes the due date of a competence over the national banking
+    /// calendar (see [`BusinessCalendar::anbima`]).
+    ///
+    /// # Returns
+    ///
+    /// The due date, or `None` if it is out of the range supported by `chrono`.
+    pub fn due_date(&self, competence: Competence) -> Option<NaiveDate> {
+        self.due_date_in(competence, banking_calendar())
+    }
+
+    /// Computes the due date of a competence over the given calendar, e.g.
+    /// one with the holidays of the taxpayer's municipality.
+    ///
+    /// # Returns
+    ///
+    /// The due date, or `None` if it is out of the range supported by `chrono`.
+    pub fn due_date_in(
+        &self,
+        competence: Competence,
+        calendar: &BusinessCalendar,
+    ) -> Option<NaiveDate> {
+        let month = competence
+            .month_year()
+            .first_day()
+            .checked_add_months(Months::new(self.months_after))?;
+        let month = MonthYear::new(month.year(), month.month())?;
+
+        match self.day {
+            DueDay::Day(day) => {
+                let date = month
+                    .first_day()
+                    .with_day(day.max(1))
+                    .unwrap_or_else(|| month.last_day());
+                if calendar.is_business_day(date) {
+                    return Some(date);
+                }
+                match self.adjustment {
+                    Adjustment::Previous => calendar.previous_business_day(date),
+                    Adjustment::Next => calendar.next_business_day(date),
+                    Adjustment::Unadjusted => Some(date),
+                }
+            }
+            DueDay::BusinessDay(nth) => {
+                let before = month.first_day().pred_opt()?;
+                calendar.add_business_days(before, i64::from(nth.max(1)))
+            }
+            DueDay::LastBusinessDay => {
+                let after = month.last_day().succ_opt()?;
+                calendar.previous_business_day(after)
+            }
+        }
+    }
+}
+
+/// A federal tax or payroll obligation with a predefined due-date rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Obligation {
+    /// Income tax withheld at source (IRRF), paid by DARF: day 20 of the
+    /// following month, anticipated (Lei nº 11.196/2005, art. 70).
+    Irrf,
+    /// PIS/Pasep and Cofins, paid by DARF: day 25 of the following month,
+    /// anticipated (Lei nº 11.933/2009).
+    PisCofins,
+    /// Monthly estimates of IRPJ and CSLL, paid by DARF: last business day
+    /// of the following month (Lei nº 9.430/1996, art. 6º).
+    IrpjCsll,
+    /// Simples Nacional (DAS), including the MEI: day 20 of the following
+    /// month, postponed (Resolução CGSN nº 140/2018, art. 40).
+    Das,
+    /// FGTS: day 20 of the following month, anticipated, from competence
+    /// 03/2024 (FGTS Digital, Lei nº 14.438/2022); day 7 before.
+    Fgts,
+    /// Social security contributions of companies (GPS/DARF previdenciário):
+    /// day 20 of the following month, anticipated; competence 13 falls due
+    /// on December 20 (Lei nº 8.212/1991, art. 30, I, "b").
+    Gps,
+    /// Social security contributions of self-employed and optional
+    /// contributors (GPS): day 15 of the following month, postponed (Lei nº
+    /// 8.212/1991, art. 30, II).
+    GpsSelfEmployed,
+}
+
+/// First competence of FGTS Digital, due on day 20.
+const FGTS_DIGITAL_START: Competence = Competence {
+    year: 2024,
+    month: 3,
+};
+
+impl Obligation {
+    /// The due-date rule of the obligation for a competence.
+    ///
+    /// # Returns
+    ///
+    /// The rule, or `None` if the obligation has no such competence (only
+    /// [`Gps`](Self::Gps) has competence 13).
+    pub fn rule(&self, competence: Competence) -> Option<DueDateRule> {
+        if competence.is_thirteenth_salary() {
+            return match self {
+                Obligation::Gps => Some(DueDateRule::new(DueDay::Day(20)).months_after(0)),
+                _ => None,
+            };
+        }
+
+        let rule = match self {
+            Obligation::Irrf | Obligation::Gps => DueDateRule::new(DueDay::Day(20)),
+            Obligation::PisCofins => DueDateRule::new(DueDay::Day(25)),
+            Obligation::IrpjCsll => DueDateRule::new(DueDay::LastBusinessDay),
+            Obligation::Das => DueDateRule::new(DueDay::Day(20)).adjustment(Adjustment::Next),
+            Obligation::Fgts if competence < FGTS_DIGITAL_START => DueDateRule::new(DueDay::Day(7)),
+            Obligation::Fgts => DueDateRule::new(DueDay::Day(20)),
+            Obligation::GpsSelfEmployed => {
+                DueDateRule::new(DueDay::Day(15)).adjustment(Adjustment::Next)
+            }
+        };
+        Some(rule)
+    }
+
+    /// Computes the due date of the obligation for a competence over the
+    /// national banking calendar.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::tax_calendar::{Competence, Obligation};
+    /// use chrono::NaiveDate;
+    ///
+    /// let competence = Competence::new(2024, 10).unwrap();
+    ///
+    /// // November 15 and 20, 2024 are holidays
+    /// assert_eq!(Obligation::Irrf.due_date(competence), NaiveDate::from_ymd_opt(2024, 11, 19));
+    /// assert_eq!(Obligation::Das.due_date(competence), NaiveDate::from_ymd_opt(2024, 11, 21));
+    /// assert_eq!(
+    ///     Obligation::GpsSelfEmployed.due_date(competence),
+    ///     NaiveDate::from_ymd_opt(2024, 11, 18)
+    /// );
+    /// ```
+    pub fn due_date(&self, competence: Competence) -> Option<NaiveDate> {
+        self.rule(competence)?.due_date(competence)
+    }
+
+    /// Computes the due date of the obligation for a competence over the
+    /// given calendar.
+    pub fn due_date_in(
+        &self,
+        competence: Competence,
+        calendar: &BusinessCalendar,
+    ) -> Option<NaiveDate> {
+        self.rule(competence)?.due_date_in(competence, calendar)
+    }
+}
+
+// OPERATIONS
+// ==========
+
+/// Computes the due date of a federal obligation for a competence.
+///
+/// See [`Obligation`] for the rules. Due dates are moved over the national
+/// banking calendar, where Carnival and Corpus Christi are not business days.
+///
+/// # Arguments
+///
+/// * `obligation` - The obligation.
+/// * `competence` - The competence, such as "08/2024" or "13/2024".
+///
+/// # Returns
+///
+/// The due date, or `None` if the competence is invalid or does not apply
+/// to the obligation.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::tax_calendar::{due_date, Obligation};
+/// use chrono::NaiveDate;
+///
+/// assert_eq!(due_date(Obligation::Fgts, "02/2024"), NaiveDate::from_ymd_opt(2024, 3, 7));
+/// assert_eq!(due_date(Obligation::Fgts, "03/2024"), NaiveDate::from_ymd_opt(2024, 4, 19));
+/// assert_eq!(due_date(Obligation::Gps, "13/2024"), NaiveDate::from_ymd_opt(2024, 12, 20));
+/// assert_eq!(due_date(Obligation::Irrf, "13/2024"), None);
+/// ```
+pub fn due_date(obligation: Obligation, competence: &str) -> Option<NaiveDate> {
+    obligation.due_date(Competence::parse(competence)?)
+}
+
+// HELPER FUNCTIONS
+// ================
+
+/// The national banking calendar, shared by all lookups.
+fn banking_calendar() -> &'static BusinessCalendar {
+    static CALENDAR: OnceLock<BusinessCalendar> = OnceLock::new();
+    CALENDAR.get_or_init(BusinessCalendar::anbima)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn competence(year: i32, month: u32) -> Competence {
+        Competence::new(year, month).unwrap()
+    }
+
+    #[test]
+    fn test_competence_new() {
+        assert!(Competence::new(2024, 1).is_some());
+        assert!(Competence::new(2024, 13).is_some());
+        assert_eq!(Competence::new(2024, 0), None);
+        assert_eq!(Competence::new(2024, 14), None);
+    }
+
+    #[test]
+    fn test_competence_parse() {
+        assert_eq!(Competence::parse("08/2024"), Some(competence(2024, 8)));
+        assert_eq!(Competence::parse("082024"), Some(competence(2024, 8)));
+        assert_eq!(Competence::parse(" ago/2024 "), Some(competence(2024, 8)));
+        assert_eq!(Competence::parse("13/2024"), Some(competence(2024, 13)));
+        assert_eq!(Competence::parse("132024"), Some(competence(2024, 13)));
+        assert_eq!(Competence::parse("14/2024"), None);
+        assert_eq!(Competence::parse("15/08/2024"), None);
+        assert_eq!(Competence::parse(""), None);
+    }
+
+    #[test]
+    fn test_competence_order_and_display() {
+        assert!(competence(2024, 12) < competence(2024, 13));
+        assert!(competence(2024, 13) < competence(2025, 1));
+        assert_eq!(competence(2024, 13).to_string(), "13/2024");
+        assert_eq!(competence(2024, 1).to_string(), "01/2024");
+        assert_eq!(competence(2024, 12).next(), competence(2025, 1));
+        assert_eq!(competence(2024, 13).next(), competence(2025, 1));
+        assert_eq!(
+            competence(2024, 13).month_year(),
+            MonthYear::new(2024, 12).unwrap()
+        );
+        assert_eq!(
+            Competence::from_date(date(2024, 2, 29)),
+            competence(2024, 2)
+        );
+    }
+
+    #[test]
+    fn test_rule_fixed_day() {
+        let rule = DueDateRule::new(DueDay::Day(20));
+        assert_eq!(rule.due_date(competence(2024, 8)), Some(date(2024, 9, 20)));
+        assert_eq!(rule.due_date(competence(2024, 12)), Some(date(2025, 1, 20)));
+
+        // Saturday and Sunday
+        assert_eq!(
+            rule.adjustment(Adjustment::Unadjusted)
+                .due_date(competence(2024, 6)),
+            Some(date(2024, 7, 20))
+        );
+        assert_eq!(rule.due_date(competence(2024, 6)), Some(date(2024, 7, 19)));
+        assert_eq!(
+            rule.adjustment(Adjustment::Next)
+                .due_date(competence(2024, 6)),
+            Some(date(2024, 7, 22))
+        );
+    }
+
+    #[test]
+    fn test_rule_day_past_end_of_month() {
+        let rule = DueDateRule::new(DueDay::Day(31)).adjustment(Adjustment::Unadjusted);
+        assert_eq!(rule.due_date(competence(2024, 1)), Some(date(2024, 2, 29)));
+        assert_eq!(rule.due_date(competence(2024, 2)), Some(date(2024, 3, 31)));
+    }
+
+    #[test]
+    fn test_rule_months_after() {
+        let rule = DueDateRule::new(DueDay::Day(10)).months_after(0);
+        assert_eq!(rule.due_date(competence(2024, 4)), Some(date(2024, 4, 10)));
+
+        let rule = DueDateRule::new(DueDay::LastBusinessDay).months_after(3);
+        assert_eq!(rule.due_date(competence(2024, 3)), Some(date(2024, 6, 28)));
+    }
+
+    #[test]
+    fn test_rule_business_days() {
+        // Fifth business day of March 2025: Carnival on 3 and 4
+        let rule = DueDateRule::new(DueDay::BusinessDay(5));
+        assert_eq!(rule.due_date(competence(2025, 2)), Some(date(2025, 3, 11)));
+        assert_eq!(rule.due_date(competence(2024, 1)), Some(date(2024, 2, 7)));
+
+        let rule = DueDateRule::new(DueDay::LastBusinessDay);
+        assert_eq!(rule.due_date(competence(2024, 2)), Some(date(2024, 3, 28)));
+    }
+
+    #[test]
+    fn test_rule_custom_calendar() {
+        // July 9 is a holiday in São Paulo
+        let calendar = BusinessCalendar::new(Some("SP")).unwrap();
+        let rule = DueDateRule::new(DueDay::Day(9));
+        assert_eq!(rule.due_date(competence(2024, 6)), Some(date(2024, 7, 9)));
+        assert_eq!(
+            rule.due_date_in(competence(2024, 6), &calendar),
+            Some(date(2024, 7, 8))
+        );
+    }
+
+    #[test]
+    fn test_obligations() {
+        let september = competence(2024, 9);
+        assert_eq!(
+            Obligation::Irrf.due_date(september),
+            Some(date(2024, 10, 18))
+        );
+        assert_eq!(
+            Obligation::PisCofins.due_date(september),
+            Some(date(2024, 10, 25))
+        );
+        assert_eq!(
+            Obligation::IrpjCsll.due_date(september),
+            Some(date(2024, 10, 31))
+        );
+        assert_eq!(
+            Obligation::Das.due_date(september),
+            Some(date(2024, 10, 21))
+        );
+        assert_eq!(
+            Obligation::Fgts.due_date(september),
+            Some(date(2024, 10, 18))
+        );
+        assert_eq!(
+            Obligation::Gps.due_date(september),
+            Some(date(2024, 10, 18))
+        );
+        assert_eq!(
+            Obligation::GpsSelfEmployed.due_date(september),
+            Some(date(2024, 10, 15))
+        );
+    }
+
+    #[test]
+    fn test_fgts_digital() {
+        assert_eq!(
+            Obligation::Fgts.due_date(competence(2024, 1)),
+            Some(date(2024, 2, 7))
+        );
+        // March 7, 2021 was a Sunday
+        assert_eq!(
+            Obligation::Fgts.due_date(competence(2021, 2)),
+            Some(date(2021, 3, 5))
+        );
+        assert_eq!(
+            Obligation::Fgts.due_date(competence(2024, 3)),
+            Some(date(2024, 4, 19))
+        );
+    }
+
+    #[test]
+    fn test_thirteenth_salary() {
+        assert_eq!(
+            Obligation::Gps.due_date(competence(2024, 13)),
+            Some(date(2024, 12, 20))
+        );
+        // December 20, 2025 is a Saturday
+        assert_eq!(
+            Obligation::Gps.due_date(competence(2025, 13)),
+            Some(date(2025, 12, 19))
+        );
+        assert_eq!(Obligation::Fgts.due_date(competence(2024, 13)), None);
+        assert_eq!(Obligation::Das.rule(competence(2024, 13)), None);
+    }
+
+    #[test]
+    fn test_due_date() {
+        assert_eq!(
+            due_date(Obligation::PisCofins, "01/2024"),
+            Some(date(2024, 2, 23))
+        );
+        assert_eq!(due_date(Obligation::Irrf, "invalid"), None);
+        assert_eq!(due_date(Obligation::Irrf, "00/2024"), None);
+    }
+}
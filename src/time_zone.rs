@@ -0,0 +1,564 @@
+//! Brazilian time zones (fusos horários) by state (UF) and municipality.
+
+use chrono::{DateTime, Duration, FixedOffset, LocalResult, NaiveDate, NaiveDateTime, TimeZone};
+use chrono::{Offset, Utc};
+use chrono_tz::OffsetComponents;
+pub use chrono_tz::Tz;
+
+// TYPES
+// =====
+
+/// The legal time zones of Brazil (Decreto nº 2.784/1913, as amended by
+/// Lei nº 12.876/2013), as standard offsets from UTC.
+///
+/// Daylight saving time (horário de verão) was last observed in the summer
+/// of 2018–2019 and was abolished by Decreto nº 9.772/2019. Historical
+/// conversions should use the IANA zone returned by [`time_zone`] or
+/// [`municipality_time_zone`], which also knows the past changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StandardTime {
+    /// UTC-2: Fernando de Noronha and the other Atlantic islands.
+    Noronha,
+    /// UTC-3: hora de Brasília, the legal time of most of the country.
+    Brasilia,
+    /// UTC-4: Amazonas (except the southwest), Mato Grosso, Mato Grosso do
+    /// Sul, Rondônia and Roraima.
+    Amazon,
+    /// UTC-5: Acre and the southwest of Amazonas.
+    Acre,
+}
+
+impl StandardTime {
+    /// The offset from UTC of the zone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::time_zone::StandardTime;
+    ///
+    /// assert_eq!(StandardTime::Brasilia.utc_offset().local_minus_utc(), -3 * 3600);
+    /// assert_eq!(StandardTime::Acre.utc_offset().local_minus_utc(), -5 * 3600);
+    /// ```
+    pub fn utc_offset(&self) -> FixedOffset {
+        let hours = match self {
+            StandardTime::Noronha => -2,
+            StandardTime::Brasilia => -3,
+            StandardTime::Amazon => -4,
+            StandardTime::Acre => -5,
+        };
+        FixedOffset::east_opt(hours * 3600).expect("offset is within a day")
+    }
+
+    /// The zone currently observed by a Brazilian IANA time zone.
+    ///
+    /// # Returns
+    ///
+    /// The zone, or `None` if the time zone is not Brazilian.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use brazilian_utils::time_zone::{StandardTime, Tz};
+    ///
+    /// assert_eq!(StandardTime::from_time_zone(Tz::America__Manaus), Some(StandardTime::Amazon));
+    /// assert_eq!(StandardTime::from_time_zone(Tz::Europe__Lisbon), None);
+    /// ```
+    pub fn from_time_zone(tz: Tz) -> Option<Self> {
+        match tz {
+            Tz::America__Noronha => Some(StandardTime::Noronha),
+            Tz::America__Sao_Paulo
+            | Tz::America__Bahia
+            | Tz::America__Fortaleza
+            | Tz::America__Recife
+            | Tz::America__Maceio
+            | Tz::America__Araguaina
+            | Tz::America__Belem
+            | Tz::America__Santarem => Some(StandardTime::Brasilia),
+            Tz::America__Manaus
+            | Tz::America__Boa_Vista
+            | Tz::America__Porto_Velho
+            | Tz::America__Cuiaba
+            | Tz::America__Campo_Grande => Some(StandardTime::Amazon),
+            Tz::America__Rio_Branco | Tz::America__Eirunepe => Some(StandardTime::Acre),
+            _ => None,
+        }
+    }
+}
+
+/// The IANA time zone of each state (UF), for the state's capital.
+const STATE_TIME_ZONES: &[(&str, Tz)] = &[
+    ("AC", Tz::America__Rio_Branco),
+    ("AL", Tz::America__Maceio),
+    ("AM", Tz::America__Manaus),
+    ("AP", Tz::America__Belem),
+    ("BA", Tz::America__Bahia),
+    ("CE", Tz::America__Fortaleza),
+    ("DF", Tz::America__Sao_Paulo),
+    ("ES", Tz::America__Sao_Paulo),
+    ("GO", Tz::America__Sao_Paulo),
+    ("MA", Tz::America__Fortaleza),
+    ("MG", Tz::America__Sao_Paulo),
+    ("MS", Tz::America__Campo_Grande),
+    ("MT", Tz::America__Cuiaba),
+    ("PA", Tz::America__Belem),
+    ("PB", Tz::America__Fortaleza),
+    ("PE", Tz::America__Recife),
+    ("PI", Tz::America__Fortaleza),
+    ("PR", Tz::America__Sao_Paulo),
+    ("RJ", Tz::America__Sao_Paulo),
+    ("RN", Tz::America__Fortaleza),
+    ("RO", Tz::America__Porto_Velho),
+    ("RR", Tz::America__Boa_Vista),
+    ("RS", Tz::America__Sao_Paulo),
+    ("SC", Tz::America__Sao_Paulo),
+    ("SE", Tz::America__Maceio),
+    ("SP", Tz::America__Sao_Paulo),
+    ("TO", Tz::America__Araguaina),
+];
+
+/// The IBGE code prefix of each state.
+const STATE_CODES: &[(&str, &str)] = &[
+    ("11", "RO"),
+    ("12", "AC"),
+    ("13", "AM"),
+    ("14", "RR"),
+    ("15", "PA"),
+    ("16", "AP"),
+    ("17", "TO"),
+    ("21", "MA"),
+    ("22", "PI"),
+    ("23", "CE"),
+    ("24", "RN"),
+    ("25", "PB"),
+    ("26", "PE"),
+    ("27", "AL"),
+    ("28", "SE"),
+    ("29", "BA"),
+    ("31", "MG"),
+    ("32", "ES"),
+    ("33", "RJ"),
+    ("35", "SP"),
+    ("41", "PR"),
+    ("42", "SC"),
+    ("43", "RS"),
+    ("50", "MS"),
+    ("51", "MT"),
+    ("52", "GO"),
+    ("53", "DF"),
+];
+
+/// IBGE code of Fernando de Noronha (PE), on UTC-2.
+const FERNANDO_DE_NORONHA_IBGE: &str = "2605459";
+
+/// Municipalities of southwest Amazonas on UTC-5, like Acre (Lei nº 12.876/2013).
+/// They observed UTC-4 from June 2008 to November 2013.
+const AMAZONAS_WEST_IBGE: &[&str] = &[
+    "1300201", // Atalaia do Norte
+    "1300607", // Benjamin Constant
+    "1300706", // Boca do Acre
+    "1301407", // Eirunepé
+    "1301506", // Envira
+    "1301654", // Guajará
+    "1301803", // Ipixuna
+    "1301951", // Itamarati
+    "1302306", // Jutaí
+    "1302405", // Lábrea
+    "1303502", // Pauini
+    "1303908", // São Paulo de Olivença
+    "1304062", // Tabatinga
+];
+
+/// Municipalities of Pará west of the Paru and Xingu rivers, which observed
+/// UTC-4 until June 2008 (Lei nº 11.662/2008).
+const PARA_WEST_IBGE: &[&str] = &[
+    "1500404", // Alenquer
+    "1501006", // Aveiro
+    "1501451", // Belterra
+    "1501725", // Brasil Novo
+    "1502855", // Curuá
+    "1503002", // Faro
+    "1503606", // Itaituba
+    "1503754", // Jacareacanga
+    "1503903", // Juruti
+    "1504455", // Medicilândia
+    "1504752", // Mojuí dos Campos
+    "1504802", // Monte Alegre
+    "1505031", // Novo Progresso
+    "1505106", // Óbidos
+    "1505304", // Oriximiná
+    "1505650", // Placas
+    "1506005", // Prainha
+    "1506195", // Rurópolis
+    "1506807", // Santarém
+    "1507979", // Terra Santa
+    "1508050", // Trairão
+    "1508159", // Uruará
+];
+
+// OPERATIONS
+// ==========
+
+/// Returns the IANA time zone of a state (UF).
+///
+/// States split across zones are reported by the zone of their capital: use
+/// [`municipality_time_zone`] for Amazonas, Pará and Pernambuco (Fernando de
+/// Noronha).
+///
+/// # Arguments
+///
+/// * `uf` - The state abbreviation (UF).
+///
+/// # Returns
+///
+/// The time zone, or `None` if the UF is invalid.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::time_zone::{time_zone, Tz};
+///
+/// assert_eq!(time_zone("SP"), Some(Tz::America__Sao_Paulo));
+/// assert_eq!(time_zone("AC"), Some(Tz::America__Rio_Branco));
+/// assert_eq!(time_zone("XX"), None);
+/// ```
+pub fn time_zone(uf: &str) -> Option<Tz> {
+    STATE_TIME_ZONES
+        .iter()
+        .find(|(state, _)| *state == uf)
+        .map(|(_, tz)| *tz)
+}
+
+/// Returns the IANA time zone of a municipality.
+///
+/// The state is taken from the first two digits of the code, so any
+/// municipality is supported. The municipalities of southwest Amazonas
+/// (America/Eirunepe), western Pará (America/Santarem) and Fernando de
+/// Noronha (America/Noronha) get their own zone.
+///
+/// # Arguments
+///
+/// * `ibge` - The 7-digit IBGE code of the municipality.
+///
+/// # Returns
+///
+/// The time zone, or `None` if the code is malformed or its state is unknown.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::time_zone::{municipality_time_zone, Tz};
+///
+/// assert_eq!(municipality_time_zone("1302603"), Some(Tz::America__Manaus)); // Manaus
+/// assert_eq!(municipality_time_zone("1304062"), Some(Tz::America__Eirunepe)); // Tabatinga
+/// assert_eq!(municipality_time_zone("2605459"), Some(Tz::America__Noronha));
+/// assert_eq!(municipality_time_zone("123"), None);
+/// ```
+pub fn municipality_time_zone(ibge: &str) -> Option<Tz> {
+    if ibge.len() != 7 || !ibge.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    if ibge == FERNANDO_DE_NORONHA_IBGE {
+        return Some(Tz::America__Noronha);
+    }
+    if AMAZONAS_WEST_IBGE.contains(&ibge) {
+        return Some(Tz::America__Eirunepe);
+    }
+    if PARA_WEST_IBGE.contains(&ibge) {
+        return Some(Tz::America__Santarem);
+    }
+
+    let uf = STATE_CODES
+        .iter()
+        .find(|(code, _)| *code == &ibge[..2])
+        .map(|(_, uf)| *uf)?;
+    time_zone(uf)
+}
+
+/// Returns the local calendar date of an instant in a time zone.
+///
+/// Deadlines and business days are counted on local dates: a filing at
+/// 01:00 UTC is still on the previous day in every Brazilian zone.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::time_zone::{local_date, Tz};
+/// use chrono::{NaiveDate, TimeZone, Utc};
+///
+/// let instant = Utc.with_ymd_and_hms(2024, 3, 1, 2, 30, 0).unwrap();
+/// assert_eq!(
+///     local_date(instant, Tz::America__Sao_Paulo),
+///     NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+/// );
+/// assert_eq!(
+///     local_date(instant, Tz::America__Noronha),
+///     NaiveDate::from_ymd_opt(2024, 3, 1).unwrap()
+/// );
+/// ```
+pub fn local_date(instant: DateTime<Utc>, tz: Tz) -> NaiveDate {
+    instant.with_timezone(&tz).date_naive()
+}
+
+/// Returns the current date in a time zone.
+pub fn today(tz: Tz) -> NaiveDate {
+    local_date(Utc::now(), tz)
+}
+
+/// Converts a local wall-clock time to an instant in a time zone.
+///
+/// Times skipped when daylight saving time started (clocks moved from 00:00
+/// to 01:00) are moved forward by the length of the gap, and times repeated
+/// when it ended resolve to their first occurrence, still on summer time.
+///
+/// # Returns
+///
+/// The instant, or `None` if the time cannot be resolved.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::time_zone::{from_local, Tz};
+/// use chrono::{NaiveDate, TimeZone, Utc};
+///
+/// let local = NaiveDate::from_ymd_opt(2024, 7, 22).unwrap().and_hms_opt(23, 59, 59).unwrap();
+/// let instant = from_local(local, Tz::America__Sao_Paulo).unwrap();
+/// assert_eq!(instant, Utc.with_ymd_and_hms(2024, 7, 23, 2, 59, 59).unwrap());
+///
+/// // Daylight saving time started on November 4, 2018 at 00:00
+/// let local = NaiveDate::from_ymd_opt(2018, 11, 4).unwrap().and_hms_opt(0, 30, 0).unwrap();
+/// let instant = from_local(local, Tz::America__Sao_Paulo).unwrap();
+/// assert_eq!(instant, Utc.with_ymd_and_hms(2018, 11, 4, 3, 30, 0).unwrap());
+/// ```
+pub fn from_local(local: NaiveDateTime, tz: Tz) -> Option<DateTime<Tz>> {
+    match tz.from_local_datetime(&local) {
+        LocalResult::Single(instant) => Some(instant),
+        LocalResult::Ambiguous(earliest, _) => Some(earliest),
+        LocalResult::None => {
+            // Read the time with the offset in force before the gap
+            let before = tz
+                .from_local_datetime(&(local - Duration::days(1)))
+                .earliest()?;
+            let offset = before.offset().fix();
+            let instant = (local - offset).and_utc();
+            Some(instant.with_timezone(&tz))
+        }
+    }
+}
+
+/// Checks whether daylight saving time (horário de verão) was in force at
+/// an instant in a time zone.
+///
+/// # Examples
+///
+/// ```
+/// use brazilian_utils::time_zone::{is_daylight_saving, Tz};
+/// use chrono::{TimeZone, Utc};
+///
+/// let summer_2018 = Utc.with_ymd_and_hms(2018, 12, 15, 12, 0, 0).unwrap();
+/// let summer_2019 = Utc.with_ymd_and_hms(2019, 12, 15, 12, 0, 0).unwrap();
+/// assert!(is_daylight_saving(summer_2018, Tz::America__Sao_Paulo));
+/// assert!(!is_daylight_saving(summer_2019, Tz::America__Sao_Paulo));
+/// assert!(!is_daylight_saving(summer_2018, Tz::America__Manaus));
+/// ```
+pub fn is_daylight_saving(instant: DateTime<Utc>, tz: Tz) -> bool {
+    !instant.with_timezone(&tz).offset().dst_offset().is_zero()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Timelike;
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    fn local(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn offset_hours(instant: DateTime<Utc>, tz: Tz) -> i32 {
+        instant.with_timezone(&tz).offset().fix().local_minus_utc() / 3600
+    }
+
+    #[test]
+    fn test_time_zone_every_state() {
+        let ufs = [
+            "AC", "AL", "AM", "AP", "BA", "CE", "DF", "ES", "GO", "MA", "MG", "MS", "MT", "PA",
+            "PB", "PE", "PI", "PR", "RJ", "RN", "RO", "RR", "RS", "SC", "SE", "SP", "TO",
+        ];
+        for uf in ufs {
+            let tz = time_zone(uf).unwrap();
+            assert!(StandardTime::from_time_zone(tz).is_some(), "{}", uf);
+        }
+    }
+
+    #[test]
+    fn test_time_zone_invalid() {
+        assert_eq!(time_zone(""), None);
+        assert_eq!(time_zone("XX"), None);
+        assert_eq!(time_zone("sp"), None);
+    }
+
+    #[test]
+    fn test_standard_time_by_state() {
+        let standard = |uf| StandardTime::from_time_zone(time_zone(uf).unwrap()).unwrap();
+        assert_eq!(standard("SP"), StandardTime::Brasilia);
+        assert_eq!(standard("BA"), StandardTime::Brasilia);
+        assert_eq!(standard("PA"), StandardTime::Brasilia);
+        assert_eq!(standard("AM"), StandardTime::Amazon);
+        assert_eq!(standard("MT"), StandardTime::Amazon);
+        assert_eq!(standard("RR"), StandardTime::Amazon);
+        assert_eq!(standard("AC"), StandardTime::Acre);
+    }
+
+    #[test]
+    fn test_municipality_time_zone() {
+        // Capitals follow the state
+        assert_eq!(
+            municipality_time_zone("3550308"),
+            Some(Tz::America__Sao_Paulo)
+        );
+        assert_eq!(municipality_time_zone("2611606"), Some(Tz::America__Recife));
+        assert_eq!(municipality_time_zone("1501402"), Some(Tz::America__Belem));
+        assert_eq!(
+            municipality_time_zone("5300108"),
+            Some(Tz::America__Sao_Paulo)
+        );
+
+        // Split states
+        assert_eq!(municipality_time_zone("1302603"), Some(Tz::America__Manaus));
+        assert_eq!(
+            municipality_time_zone("1301407"),
+            Some(Tz::America__Eirunepe)
+        );
+        assert_eq!(
+            municipality_time_zone("1506807"),
+            Some(Tz::America__Santarem)
+        );
+        assert_eq!(
+            municipality_time_zone("2605459"),
+            Some(Tz::America__Noronha)
+        );
+    }
+
+    #[test]
+    fn test_municipality_time_zone_invalid() {
+        assert_eq!(municipality_time_zone(""), None);
+        assert_eq!(municipality_time_zone("355030"), None);
+        assert_eq!(municipality_time_zone("35503080"), None);
+        assert_eq!(municipality_time_zone("35503O8"), None);
+        assert_eq!(municipality_time_zone("9900000"), None);
+    }
+
+    #[test]
+    fn test_standard_time_offsets() {
+        let instant = utc(2024, 6, 1, 12, 0);
+        for (tz, standard) in [
+            (Tz::America__Noronha, StandardTime::Noronha),
+            (Tz::America__Sao_Paulo, StandardTime::Brasilia),
+            (Tz::America__Cuiaba, StandardTime::Amazon),
+            (Tz::America__Rio_Branco, StandardTime::Acre),
+        ] {
+            assert_eq!(
+                offset_hours(instant, tz) * 3600,
+                standard.utc_offset().local_minus_utc()
+            );
+        }
+    }
+
+    #[test]
+    fn test_historical_daylight_saving() {
+        // Summer 2018-2019 was the last one with daylight saving time
+        assert_eq!(
+            offset_hours(utc(2018, 12, 1, 12, 0), Tz::America__Sao_Paulo),
+            -2
+        );
+        assert_eq!(
+            offset_hours(utc(2019, 12, 1, 12, 0), Tz::America__Sao_Paulo),
+            -3
+        );
+        assert_eq!(
+            offset_hours(utc(2018, 12, 1, 12, 0), Tz::America__Cuiaba),
+            -3
+        );
+        assert_eq!(
+            offset_hours(utc(2018, 12, 1, 12, 0), Tz::America__Manaus),
+            -4
+        );
+
+        // The northeast stopped observing it long before
+        assert!(!is_daylight_saving(
+            utc(2018, 12, 1, 12, 0),
+            Tz::America__Recife
+        ));
+    }
+
+    #[test]
+    fn test_historical_zone_changes() {
+        // Acre and southwest Amazonas observed UTC-4 from 2008 to 2013
+        assert_eq!(
+            offset_hours(utc(2010, 6, 1, 12, 0), Tz::America__Rio_Branco),
+            -4
+        );
+        assert_eq!(
+            offset_hours(utc(2014, 6, 1, 12, 0), Tz::America__Rio_Branco),
+            -5
+        );
+        assert_eq!(
+            offset_hours(utc(2010, 6, 1, 12, 0), Tz::America__Eirunepe),
+            -4
+        );
+
+        // Western Pará observed UTC-4 until 2008
+        assert_eq!(
+            offset_hours(utc(2007, 6, 1, 12, 0), Tz::America__Santarem),
+            -4
+        );
+        assert_eq!(offset_hours(utc(2007, 6, 1, 12, 0), Tz::America__Belem), -3);
+        assert_eq!(
+            offset_hours(utc(2009, 6, 1, 12, 0), Tz::America__Santarem),
+            -3
+        );
+    }
+
+    #[test]
+    fn test_local_date() {
+        let instant = utc(2024, 1, 1, 2, 0);
+        assert_eq!(
+            local_date(instant, Tz::America__Sao_Paulo),
+            NaiveDate::from_ymd_opt(2023, 12, 31).unwrap()
+        );
+        assert_eq!(
+            local_date(instant, Tz::America__Noronha),
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_from_local() {
+        let instant = from_local(local(2024, 7, 22, 12, 0), Tz::America__Rio_Branco).unwrap();
+        assert_eq!(instant, utc(2024, 7, 22, 17, 0));
+        assert_eq!(instant.hour(), 12);
+    }
+
+    #[test]
+    fn test_from_local_daylight_saving_gap() {
+        // Clocks moved from 00:00 to 01:00 on November 4, 2018
+        let instant = from_local(local(2018, 11, 4, 0, 30), Tz::America__Sao_Paulo).unwrap();
+        assert_eq!(instant, utc(2018, 11, 4, 3, 30));
+        assert_eq!(instant.naive_local(), local(2018, 11, 4, 1, 30));
+    }
+
+    #[test]
+    fn test_from_local_daylight_saving_overlap() {
+        // Clocks moved from 00:00 back to 23:00 on February 17, 2019
+        let instant = from_local(local(2019, 2, 16, 23, 30), Tz::America__Sao_Paulo).unwrap();
+        assert_eq!(instant, utc(2019, 2, 17, 1, 30));
+    }
+}